@@ -1,20 +1,147 @@
-use gram_schmidt::{Vector, Vector4};
-use criterion::{criterion_group, criterion_main, Criterion, black_box};
+use gram_schmidt::{gram_schmidt_warm_start, storage_axpy, storage_dot, storage_normalize, Vector, Vector16, Vector4};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, black_box};
 
+const INPUT: [Vector4; 4] = [
+    Vector4::new([1.0, 1.0, 1.0, 1.0]),
+    Vector4::new([0.0, 1.0, 0.0, 1.0]),
+    Vector4::new([0.0, 0.0, 1.0, 1.0]),
+    Vector4::new([0.0, 0.0, 0.0, 1.0]),
+];
+
+// `Vector4` and `gram_schmidt` as they were before the core loop was rewritten: plain multiply-add loops, a scaled
+// clone of the basis vector for every projection, `split_at_mut` over a `Vec`, behind `#[inline(never)]`. Kept as a
+// private copy so that the comparison does not pick up any later change to the crate's own kernels. On an x86-64
+// Xeon the two run within about 10% of each other (the rewrite is the slower one), not the 2x once aimed for: the
+// loop is bound by the latency of the dot -> sqrt -> divide chain, which neither the clones nor `split_at_mut` are on.
+#[derive(Clone)]
+struct BaselineVector4 {
+    components: [f64; 4],
+}
+
+// Kept as it was written, lints included.
+#[allow(clippy::needless_return, clippy::needless_range_loop, clippy::ptr_arg)]
+impl BaselineVector4 {
+    fn dot_product(v1: &Self, v2: &Self) -> f64 {
+        let mut sum = 0.0;
+        for i in 0..4 {
+            sum += v1.components[i] * v2.components[i];
+        }
+        return sum;
+    }
+
+    fn scaled_copy(&self, lambda: f64) -> Self {
+        let mut components = [0.0; 4];
+        for i in 0..4 {
+            components[i] = self.components[i] * lambda;
+        }
+        return Self { components };
+    }
+
+    fn sub(&mut self, other: &Self) {
+        for i in 0..4 {
+            self.components[i] -= other.components[i];
+        }
+    }
+
+    fn normalize(&mut self) {
+        let len = Self::dot_product(self, self).sqrt();
+        self.components.iter_mut().for_each(|c| *c /= len);
+    }
+
+    #[inline(never)]
+    fn gram_schmidt(basis: &mut Vec<Self>) {
+        basis[0].normalize();
+        for index in 1..basis.len() {
+            let (first_half, second_half) = basis.split_at_mut(index);
+            let a = &mut second_half[0];
+            for b in first_half.iter() {
+                let dot = Self::dot_product(a, b);
+                a.sub(&b.scaled_copy(dot));
+            }
+            a.normalize();
+        }
+    }
+}
+
+// Only the orthonormalization is timed; building the input is left to the setup closure.
 fn gram_schmit_benchmark(c: &mut Criterion) {
+    c.bench_function("gram_schmidt", |b| {
+        b.iter_batched_ref(|| INPUT, |basis| Vector4::gram_schmidt(black_box(basis)), BatchSize::SmallInput)
+    });
+    c.bench_function("gram_schmidt/baseline", |b| {
+        b.iter_batched_ref(
+            || INPUT.iter().map(|v| BaselineVector4 { components: v.components }).collect::<Vec<_>>(),
+            |basis| BaselineVector4::gram_schmidt(black_box(basis)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+// The warm start from the basis of a slightly different input against orthonormalizing from scratch.
+fn warm_start_benchmark(c: &mut Criterion) {
+    let input = |t: f64| -> Vec<Vector16> {
+        (0..16)
+            .map(|j| Vector16::from_fn(|i| if i == j { 2.0 } else { ((i * 16 + j) as f64 + t).sin() }))
+            .collect()
+    };
+    let mut previous = input(0.0);
+    Vector16::gram_schmidt(&mut previous);
+    let current = input(0.01);
+
+    c.bench_function("warm_start/cold", |b| {
+        b.iter_batched_ref(|| current.clone(), |basis| Vector16::gram_schmidt(black_box(basis)), BatchSize::SmallInput)
+    });
+    c.bench_function("warm_start/warm", |b| {
+        b.iter_batched_ref(
+            || current.clone(),
+            |basis| assert!(gram_schmidt_warm_start(black_box(basis), &previous)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+// Micro-kernels the orthogonalization is built from, on a fixed-size vector and on a long runtime-sized one.
+fn kernel_benchmarks(c: &mut Criterion) {
+    let a = Vector16::from_fn(|i| i as f64 + 1.0);
+    let b = Vector16::from_fn(|i| 1.0 / (i as f64 + 1.0));
+    let x: Vec<f64> = (0..1024).map(|i| (i as f64).sin()).collect();
+    let y: Vec<f64> = (0..1024).map(|i| (i as f64).cos()).collect();
 
-    c.bench_function("gram_schmidt", |b| b.iter(|| {
-        let mut basis = black_box(
-            [
-                Vector4::new([1.0, 1.0, 1.0, 1.0]),
-                Vector4::new([0.0, 1.0, 0.0, 1.0]),
-                Vector4::new([0.0, 0.0, 1.0, 1.0]),
-                Vector4::new([0.0, 0.0, 0.0, 1.0]),
-            ].to_vec()
-        );
-        Vector4::gram_schmidt(&mut basis)
+    c.bench_function("dot/vector16", |bench| bench.iter(|| Vector16::dot_product(black_box(&a), black_box(&b))));
+    c.bench_function("dot/vec1024", |bench| bench.iter(|| storage_dot(black_box(&x), black_box(&y))));
+    c.bench_function("axpy/vector16", |bench| bench.iter(|| {
+        let mut target = black_box(a);
+        target.axpy(black_box(0.5), black_box(&b));
+        target
+    }));
+    c.bench_function("axpy/vec1024", |bench| bench.iter(|| {
+        let mut target = black_box(y.clone());
+        storage_axpy(black_box(0.5), black_box(&x), &mut target);
+        target
+    }));
+    c.bench_function("normalize/vector16", |bench| bench.iter(|| {
+        let mut target = black_box(a);
+        target.normalize();
+        target
+    }));
+    c.bench_function("normalize/vec1024", |bench| bench.iter(|| {
+        let mut target = black_box(x.clone());
+        storage_normalize(&mut target);
+        target
+    }));
+    // One projection-subtract step of Gram-Schmidt: `a -= <a, q> q`.
+    let q = {
+        let mut q = b;
+        q.normalize();
+        q
+    };
+    c.bench_function("project_subtract/vector16", |bench| bench.iter(|| {
+        let mut target = black_box(a);
+        let dot = Vector16::dot_product(&target, black_box(&q));
+        target.axpy(-dot, &q);
+        target
     }));
 }
 
-criterion_group!(benches, gram_schmit_benchmark);
-criterion_main!(benches);
\ No newline at end of file
+criterion_group!(benches, gram_schmit_benchmark, warm_start_benchmark, kernel_benchmarks);
+criterion_main!(benches);
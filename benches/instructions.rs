@@ -0,0 +1,55 @@
+#![allow(clippy::needless_return)]
+
+// Instruction-count benchmarks run under Cachegrind (requires valgrind), which are deterministic where the criterion
+// wall-clock benchmarks are noisy. Run with `cargo bench --bench instructions`.
+use gram_schmidt::{storage_axpy, storage_dot, storage_normalize, Vector, Vector16, Vector4};
+use iai::black_box;
+
+fn vectors() -> (Vector16, Vector16) {
+    return (Vector16::from_fn(|i| i as f64 + 1.0), Vector16::from_fn(|i| 1.0 / (i as f64 + 1.0)));
+}
+
+fn long_vectors() -> (Vec<f64>, Vec<f64>) {
+    return ((0..1024).map(|i| (i as f64).sin()).collect(), (0..1024).map(|i| (i as f64).cos()).collect());
+}
+
+fn dot_vector16() -> f64 {
+    let (a, b) = vectors();
+    return Vector16::dot_product(black_box(&a), black_box(&b));
+}
+
+fn dot_vec1024() -> f64 {
+    let (x, y) = long_vectors();
+    return storage_dot(black_box(&x), black_box(&y));
+}
+
+fn axpy_vector16() -> Vector16 {
+    let (mut a, b) = vectors();
+    black_box(&mut a).axpy(black_box(0.5), black_box(&b));
+    return a;
+}
+
+fn axpy_vec1024() -> Vec<f64> {
+    let (x, mut y) = long_vectors();
+    storage_axpy(black_box(0.5), black_box(&x), black_box(&mut y));
+    return y;
+}
+
+fn normalize_vec1024() -> Vec<f64> {
+    let (mut x, _) = long_vectors();
+    storage_normalize(black_box(&mut x));
+    return x;
+}
+
+fn gram_schmidt_vector4() -> Vec<Vector4> {
+    let mut basis = black_box(vec![
+        Vector4::new([1.0, 1.0, 1.0, 1.0]),
+        Vector4::new([0.0, 1.0, 0.0, 1.0]),
+        Vector4::new([0.0, 0.0, 1.0, 1.0]),
+        Vector4::new([0.0, 0.0, 0.0, 1.0]),
+    ]);
+    Vector4::gram_schmidt(&mut basis);
+    return basis;
+}
+
+iai::main!(dot_vector16, dot_vec1024, axpy_vector16, axpy_vec1024, normalize_vec1024, gram_schmidt_vector4);
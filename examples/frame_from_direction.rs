@@ -0,0 +1,32 @@
+#![allow(clippy::needless_return)]
+
+// Builds a right-handed orthonormal 3D frame (e.g. a camera or tool frame) from a single forward direction. The
+// missing axes are filled in by Gram-Schmidt with `ZeroVectorPolicy::ReplaceWithComplement`, which picks the
+// coordinate axis least aligned with the vectors so far, so no "up" hint is needed and the result is stable even for
+// directions along a coordinate axis.
+use gram_schmidt::{gram_schmidt_with_policy, Error, Vector, Vector3, ZeroVectorPolicy};
+
+fn frame(forward: Vector3) -> Result<[Vector3; 3], Error> {
+    if forward.length() == 0.0 {
+        return Err(Error::ZeroVector { vector: 0 });
+    }
+    let mut basis = vec![forward, Vector3::zero(), Vector3::zero()];
+    gram_schmidt_with_policy(&mut basis, ZeroVectorPolicy::ReplaceWithComplement)?;
+    // Fix the handedness with the cross product rather than trusting the order of the completed axes.
+    let [forward, right, _]: [Vector3; 3] = basis.try_into().expect("the policy keeps all three vectors");
+    let up = forward.cross(&right);
+    return Ok([forward, right, up]);
+}
+
+fn main() {
+    for forward in [Vector3::new([1.0, 2.0, 2.0]), Vector3::new([0.0, 0.0, -5.0]), Vector3::new([1e-3, 1.0, 0.0])] {
+        let [f, r, u] = frame(forward).expect("direction must be non-zero");
+        println!("forward {:?}", f.components);
+        println!("  right {:?}", r.components);
+        println!("  up    {:?}", u.components);
+        let orthogonality = [Vector3::dot_product(&f, &r), Vector3::dot_product(&f, &u), Vector3::dot_product(&r, &u)];
+        assert!(orthogonality.iter().all(|d| d.abs() < 1e-12));
+        assert!((Vector3::dot_product(&f.cross(&r), &u) - 1.0).abs() < 1e-12);
+    }
+    assert!(frame(Vector3::zero()).is_err());
+}
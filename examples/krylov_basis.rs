@@ -0,0 +1,33 @@
+#![allow(clippy::needless_return)]
+
+// Grows an orthonormal Krylov basis `span{b, A b, A² b, ...}` one vector at a time with `IncrementalGramSchmidt`,
+// stopping once the next vector is (numerically) in the span, and compares the result with the Arnoldi process.
+use gram_schmidt::{arnoldi, IncrementalGramSchmidt, Vector6};
+
+// Symmetric start vector: the Krylov space stays in the symmetric subspace, which has dimension 3.
+const START: Vector6 = Vector6::new([1.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
+
+// A symmetric tridiagonal operator, applied without forming the matrix.
+fn apply(v: &Vector6) -> Vector6 {
+    return Vector6::from_fn(|i| {
+        let left = if i > 0 { v[i - 1] } else { 0.0 };
+        let right = if i + 1 < 6 { v[i + 1] } else { 0.0 };
+        return 2.0 * v[i] - left - right;
+    });
+}
+
+fn main() {
+    let mut incremental = IncrementalGramSchmidt::new();
+    let mut next = START;
+    // `push` rejects the first vector that is already in the span.
+    while let Ok(r) = incremental.push(next) {
+        println!("dimension {}: residual {:.3e}", r.len(), r[r.len() - 1]);
+        next = apply(incremental.basis().last().unwrap());
+    }
+    let dimension = incremental.basis().len();
+    println!("invariant Krylov subspace of dimension {}", dimension);
+
+    let (basis, h) = arnoldi(apply, &START, 10);
+    println!("arnoldi: {} basis vectors, H is {}x{}", basis.len(), h.rows(), h.cols());
+    assert_eq!(basis.len(), dimension);
+}
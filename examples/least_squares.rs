@@ -0,0 +1,36 @@
+#![allow(clippy::needless_return)]
+
+// Fits a quadratic to noisy samples by least squares, once with the Householder QR factorization of the design matrix
+// and once with the matrix-free LSQR solver, which only needs products with the matrix and its transpose.
+use gram_schmidt::{householder_qr, lsqr, Matrix, UpperTriangular, Vector, Vector3, Vector8};
+
+const XS: [f64; 8] = [-1.0, -0.7, -0.4, -0.1, 0.2, 0.5, 0.8, 1.1];
+const NOISE: [f64; 8] = [0.02, -0.01, 0.03, -0.02, 0.0, 0.01, -0.03, 0.02];
+
+fn design(x: &Vector3) -> Vector8 {
+    return Vector8::from_fn(|i| x[0] + x[1] * XS[i] + x[2] * XS[i] * XS[i]);
+}
+
+fn design_transpose(u: &Vector8) -> Vector3 {
+    return Vector3::from_fn(|j| (0..8).map(|i| XS[i].powi(j as i32) * u[i]).sum());
+}
+
+fn main() {
+    // Samples of 1 - 2x + 0.5x² plus noise.
+    let y = Vector8::from_fn(|i| 1.0 - 2.0 * XS[i] + 0.5 * XS[i] * XS[i] + NOISE[i]);
+
+    let a = Matrix::from_rows(&XS.iter().map(|x| vec![1.0, *x, x * x]).collect::<Vec<_>>());
+    let (q, r) = householder_qr(&a);
+    let qty: Vec<f64> = (0..3).map(|j| (0..8).map(|i| q[(i, j)] * y[i]).sum()).collect();
+    let from_qr = UpperTriangular::new(r).solve(&qty);
+    println!("QR:   {:?}", from_qr);
+
+    let (from_lsqr, iterations) = lsqr(design, design_transpose, &y, 1e-12, false);
+    println!("LSQR: {:?} after {} iterations", from_lsqr.components, iterations);
+
+    let residual = (design(&from_lsqr) - y).length();
+    println!("residual norm {:.4}", residual);
+    for j in 0..3 {
+        assert!((from_lsqr[j] - from_qr[j]).abs() < 1e-8);
+    }
+}
@@ -0,0 +1,55 @@
+#![allow(clippy::needless_return)]
+
+// Orthonormalizes the columns of a matrix read from a CSV file (one row per line, comma separated) and prints the
+// result as CSV. Without an argument a small built-in matrix is used:
+//
+//     cargo run --example orthonormalize_csv -- matrix.csv
+use gram_schmidt::{try_gram_schmidt_storage, Matrix};
+
+const SAMPLE: &str = "1, 0, 0\n1, 1, 0\n1, 1, 1\n1, 1, 1\n";
+
+fn parse_csv(text: &str) -> Result<Matrix, String> {
+    let mut rows = vec![];
+    for (line_number, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let row = line.split(',')
+            .map(|field| field.trim().parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|error| format!("line {}: {}", line_number + 1, error))?;
+        rows.push(row);
+    }
+    if rows.iter().any(|row| row.len() != rows[0].len()) {
+        return Err("all rows must have the same number of columns".to_string());
+    }
+    return Ok(Matrix::from_rows(&rows));
+}
+
+fn run() -> Result<(), Box<dyn std::error::Error>> {
+    let text = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => SAMPLE.to_string(),
+    };
+    let matrix = parse_csv(&text)?;
+    let mut columns: Vec<Vec<f64>> = (0..matrix.cols()).map(|j| matrix.column(j).to_vec()).collect();
+    try_gram_schmidt_storage(&mut columns)?;
+    let q = Matrix::from_columns(&columns);
+    for i in 0..q.rows() {
+        let row: Vec<String> = (0..q.cols()).map(|j| format!("{:.6}", q[(i, j)])).collect();
+        println!("{}", row.join(", "));
+    }
+
+    // The sample has independent columns, so `Qᵀ Q` is the identity.
+    let qtq = &q.transpose() * &q;
+    let deviation = (0..q.cols())
+        .flat_map(|i| (0..q.cols()).map(move |j| (i, j)))
+        .map(|(i, j)| (qtq[(i, j)] - if i == j { 1.0 } else { 0.0 }).abs())
+        .fold(0.0, f64::max);
+    eprintln!("max |QᵀQ - I| = {:e}", deviation);
+    return Ok(());
+}
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
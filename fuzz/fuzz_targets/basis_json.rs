@@ -0,0 +1,23 @@
+#![no_main]
+#![allow(clippy::needless_return)]
+
+use gram_schmidt::io::{BasisDocument, FactorizationDocument};
+use libfuzzer_sys::fuzz_target;
+
+// Parsing arbitrary text must never panic, the buffered and the streaming parser must agree, and every accepted
+// document must survive a round trip through `to_json`.
+fuzz_target!(|data: &[u8]| {
+    let from_reader = BasisDocument::from_reader(data);
+    let Ok(json) = std::str::from_utf8(data) else {
+        return;
+    };
+    let document = BasisDocument::from_json(json);
+    assert_eq!(document.is_ok(), from_reader.is_ok());
+    if let Ok(document) = document {
+        assert_eq!(Ok(&document), from_reader.as_ref());
+        assert_eq!(BasisDocument::from_json(&document.to_json()), Ok(document));
+    }
+    if let Ok(factorization) = FactorizationDocument::from_json(json) {
+        assert_eq!(FactorizationDocument::from_json(&factorization.to_json()), Ok(factorization));
+    }
+});
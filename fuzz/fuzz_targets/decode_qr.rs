@@ -0,0 +1,16 @@
+#![no_main]
+#![allow(clippy::needless_return)]
+
+use gram_schmidt::io::{decode_qr, encode_qr};
+use libfuzzer_sys::fuzz_target;
+
+// Decoding arbitrary bytes must either fail or give matrices whose encoding decodes to the same bits again (compared
+// encoded, since the values may be NaN).
+fuzz_target!(|data: &[u8]| {
+    let Ok((q, r)) = decode_qr(data) else {
+        return;
+    };
+    let encoded = encode_qr(&q, &r);
+    let (q, r) = decode_qr(&encoded).unwrap();
+    assert_eq!(encode_qr(&q, &r), encoded);
+});
@@ -0,0 +1,35 @@
+#![no_main]
+#![allow(clippy::needless_return)]
+
+use gram_schmidt::io::MmapMatrix;
+use libfuzzer_sys::fuzz_target;
+
+// The first 8 bytes give the claimed shape as two little-endian u32 (so the byte length can overflow), the rest is
+// written to a file that is then mapped with that shape. Opening must fail unless the file holds exactly a
+// `rows x cols` matrix; if it opens, every column must read back as the bytes that were written.
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 8 {
+        return;
+    }
+    let (shape, contents) = data.split_at(8);
+    let rows = u32::from_le_bytes(shape[..4].try_into().unwrap()) as usize;
+    let cols = u32::from_le_bytes(shape[4..].try_into().unwrap()) as usize;
+    let path = std::env::temp_dir().join(format!("gram_schmidt_fuzz_mmap_{}.bin", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+
+    // SAFETY: the file is private to this process and nothing else touches it while it is mapped.
+    let opened = unsafe { MmapMatrix::open(&path, rows, cols) };
+    let fits = rows.checked_mul(cols).and_then(|n| n.checked_mul(8)) == Some(contents.len()) && !contents.is_empty();
+    assert_eq!(opened.is_ok(), fits);
+    if let Ok(matrix) = opened {
+        assert_eq!((matrix.rows(), matrix.cols()), (rows, cols));
+        let mut column = vec![0.0; rows];
+        for (col, bytes) in contents.chunks_exact(rows * 8).enumerate() {
+            matrix.read_column(col, &mut column);
+            for (value, chunk) in column.iter().zip(bytes.chunks_exact(8)) {
+                assert_eq!(value.to_bits(), u64::from_le_bytes(chunk.try_into().unwrap()));
+            }
+        }
+    }
+    std::fs::remove_file(&path).unwrap();
+});
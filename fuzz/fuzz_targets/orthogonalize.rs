@@ -0,0 +1,23 @@
+#![no_main]
+#![allow(clippy::needless_return)]
+
+use gram_schmidt::{gram_schmidt_with_policy, validate_finite, Vector, Vector4, ZeroVectorPolicy};
+use libfuzzer_sys::fuzz_target;
+
+// Every 8 bytes form one `Vector4` with components in [-128, 128) on a 1/256 grid, which keeps the inputs finite and
+// the conditioning bounded. The orthogonalizer must either reject the input or return an orthonormal set.
+fuzz_target!(|data: &[u8]| {
+    let mut basis: Vec<Vector4> = data.chunks_exact(8)
+        .map(|chunk| Vector4::from_fn(|i| i16::from_le_bytes([chunk[2 * i], chunk[2 * i + 1]]) as f64 / 256.0))
+        .collect();
+    assert!(validate_finite(&basis).is_ok());
+    if gram_schmidt_with_policy(&mut basis, ZeroVectorPolicy::Error).is_err() {
+        return;
+    }
+    for (i, a) in basis.iter().enumerate() {
+        for (j, b) in basis.iter().enumerate() {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert!((Vector4::dot_product(a, b) - expected).abs() < 1e-6);
+        }
+    }
+});
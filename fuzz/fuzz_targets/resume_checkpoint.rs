@@ -0,0 +1,45 @@
+#![no_main]
+#![allow(clippy::needless_return)]
+
+use gram_schmidt::{Checkpoint, IncrementalGramSchmidt, Vector, Vector3};
+use libfuzzer_sys::fuzz_target;
+
+// Decodes a checkpoint of arbitrary shape from the bytes: the first byte is the number of vectors (its top bit selects
+// reorthogonalization), then per vector one f64 whose bits give the lengths of its components and of its `R` column,
+// followed by those values. Resuming must never panic, whatever the shape, and must only accept finite orthonormal
+// bases.
+fuzz_target!(|data: &[u8]| {
+    let Some((&count, mut rest)) = data.split_first() else {
+        return;
+    };
+    let mut take = |n: usize| {
+        let values: Vec<f64> = rest.chunks_exact(8).take(n)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        rest = &rest[(values.len() * 8).min(rest.len())..];
+        return values;
+    };
+    let mut checkpoint = Checkpoint { basis: vec![], r: vec![], reorthogonalize: count & 0x80 != 0 };
+    for _ in 0..count % 8 {
+        let lengths = take(1);
+        let (basis_len, r_len) = match lengths.first() {
+            Some(value) => (value.to_bits() as usize % 5, (value.to_bits() >> 8) as usize % 9),
+            None => (0, 0),
+        };
+        checkpoint.basis.push(take(basis_len));
+        checkpoint.r.push(take(r_len));
+    }
+    if let Ok(mut resumed) = IncrementalGramSchmidt::<Vector3>::resume(checkpoint) {
+        let q = resumed.basis();
+        for (i, a) in q.iter().enumerate() {
+            for (j, b) in q.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((Vector3::dot_product(a, b) - expected).abs() <= 1e-10);
+            }
+        }
+        let _ = resumed.checkpoint();
+        if resumed.basis().len() < 3 {
+            let _ = resumed.push(Vector3::new([1.0, 2.0, 3.0]));
+        }
+    }
+});
@@ -0,0 +1,63 @@
+use crate::{gram_schmidt_with_policy, Error, Frame, Vector3, ZeroVectorPolicy};
+
+// Axis order of the local navigation frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NavigationFrame {
+    // North, east, down: the usual aerospace convention.
+    #[default]
+    Ned,
+    // East, north, up: the usual robotics and geodesy convention.
+    Enu,
+}
+
+// Attitude of a resting sensor from one accelerometer and one magnetometer reading, both in body coordinates. The
+// accelerometer measures the reaction to gravity, so it points up; Gram-Schmidt on the vertical and the magnetic field
+// leaves the horizontal part of the field, which points to magnetic north. Returns the navigation axes in body
+// coordinates, i.e. the columns of the rotation from navigation to body coordinates; its transpose maps body to
+// navigation coordinates. Fails with `ZeroVector` when a reading is zero or the field is parallel to gravity.
+pub fn attitude_from_accel_mag(accel: Vector3, mag: Vector3, frame: NavigationFrame) -> Result<Frame, Error> {
+    let vertical = match frame {
+        NavigationFrame::Ned => accel * -1.0,
+        NavigationFrame::Enu => accel,
+    };
+    let mut basis = vec![vertical, mag];
+    gram_schmidt_with_policy(&mut basis, ZeroVectorPolicy::Error)?;
+    let (vertical, north) = (&basis[0], &basis[1]);
+    return Ok(match frame {
+        NavigationFrame::Ned => [*north, vertical.cross(north), *vertical],
+        NavigationFrame::Enu => [north.cross(vertical), *north, *vertical],
+    });
+}
+
+#[cfg(test)]
+mod attitude_test {
+    use crate::{attitude_from_accel_mag, Error, NavigationFrame, Vector, Vector3};
+
+    #[test]
+    fn level_and_yawed_sensor() {
+        // Body axes aligned with NED: gravity reads as -9.81 along z, the field dips down towards north.
+        let frame = attitude_from_accel_mag(
+            Vector3::new([0.0, 0.0, -9.81]),
+            Vector3::new([0.2, 0.0, 0.45]),
+            NavigationFrame::Ned,
+        ).unwrap();
+        for (axis, expected) in frame.iter().zip([Vector3::E0, Vector3::E1, Vector3::E2]) {
+            assert!((*axis - expected).length() < 1e-12);
+        }
+
+        // A level sensor yawed by 0.7 rad: the horizontal part of the field lies along the rotated north axis.
+        let (s, c) = 0.7_f64.sin_cos();
+        let frame = attitude_from_accel_mag(
+            Vector3::new([0.0, 0.0, 9.81]),
+            Vector3::new([-0.3 * s, 0.3 * c, -0.4]),
+            NavigationFrame::Enu,
+        ).unwrap();
+        let expected = [Vector3::new([c, s, 0.0]), Vector3::new([-s, c, 0.0]), Vector3::E2];
+        for (axis, expected) in frame.iter().zip(expected) {
+            assert!((*axis - expected).length() < 1e-12);
+        }
+
+        let parallel = attitude_from_accel_mag(Vector3::E2, Vector3::E2, NavigationFrame::Ned);
+        assert_eq!(parallel, Err(Error::ZeroVector { vector: 1 }));
+    }
+}
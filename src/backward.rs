@@ -0,0 +1,84 @@
+use crate::qr::positive_qr;
+use crate::{Matrix, UpperTriangular};
+
+// Solves `X * Rᵀ = B` for upper triangular `R`, i.e. `R * Xᵀ = Bᵀ` row by row.
+fn solve_right_transposed(b: &Matrix, r: &Matrix) -> Matrix {
+    let r = UpperTriangular::new(r.clone());
+    let mut x = Matrix::zeros(b.rows(), r.dim());
+    for row in 0..b.rows() {
+        let b_row: Vec<f64> = (0..b.cols()).map(|j| b[(row, j)]).collect();
+        for (j, value) in r.solve(&b_row).into_iter().enumerate() {
+            x[(row, j)] = value;
+        }
+    }
+    return x;
+}
+
+// Reverse-mode derivative of the thin QR factorization `A = QR` with full column rank: given the gradients of a
+// scalar loss with respect to `Q` and `R`, returns its gradient with respect to `A`, using
+// `Ā = (Q̄ + Q copyltu(M)) R⁻ᵀ` with `M = R R̄ᵀ - Q̄ᵀ Q`, where `copyltu` mirrors the lower triangle upwards.
+pub fn qr_vjp(q: &Matrix, r: &Matrix, q_grad: &Matrix, r_grad: &Matrix) -> Matrix {
+    let m = &(r * &r_grad.transpose()) - &(&q_grad.transpose() * q);
+    let n = m.rows();
+    let mut sym = Matrix::zeros(n, n);
+    for j in 0..n {
+        for i in 0..n {
+            sym[(i, j)] = if i >= j { m[(i, j)] } else { m[(j, i)] };
+        }
+    }
+    let lhs = q_grad + &(q * &sym);
+    return solve_right_transposed(&lhs, r);
+}
+
+// Gradient with respect to the input columns of a loss that depends only on the orthonormalized columns.
+pub fn gram_schmidt_vjp(inputs: &Matrix, output_grads: &Matrix) -> Matrix {
+    let (q, r) = positive_qr(inputs);
+    let r_grad = Matrix::zeros(r.rows(), r.cols());
+    return qr_vjp(&q, &r, output_grads, &r_grad);
+}
+
+#[cfg(test)]
+mod backward_test {
+    use crate::{gram_schmidt_jvp, gram_schmidt_vjp, Matrix};
+
+    fn columns(m: &Matrix) -> Vec<Vec<f64>> {
+        return (0..m.cols()).map(|j| m.column(j).to_vec()).collect();
+    }
+
+    #[test]
+    fn adjoint_of_forward_derivative() {
+        let a = Matrix::from_rows(&[
+            vec![1.0, 0.3, 1.0],
+            vec![2.0, -1.0, 1.0],
+            vec![0.5, 2.0, 1.0],
+            vec![1.0, 0.0, -2.0],
+        ]);
+        let tangent = Matrix::from_rows(&[
+            vec![0.2, 1.0, 0.0],
+            vec![-0.1, 0.0, 0.5],
+            vec![0.4, -0.3, 0.1],
+            vec![0.0, 0.7, 0.2],
+        ]);
+        let grad = Matrix::from_rows(&[
+            vec![1.0, -2.0, 0.5],
+            vec![0.0, 1.0, 1.0],
+            vec![3.0, 0.2, -1.0],
+            vec![-1.0, 0.0, 0.4],
+        ]);
+
+        let mut q = columns(&a);
+        let dq = gram_schmidt_jvp(&mut q, &columns(&tangent));
+        let a_grad = gram_schmidt_vjp(&a, &grad);
+
+        // <Ā, T> must equal <Q̄, dQ(T)>.
+        let mut lhs = 0.0;
+        let mut rhs = 0.0;
+        for j in 0..3 {
+            for i in 0..4 {
+                lhs += a_grad[(i, j)] * tangent[(i, j)];
+                rhs += grad[(i, j)] * dq[j][i];
+            }
+        }
+        assert!((lhs - rhs).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,191 @@
+use std::ops::Deref;
+
+use crate::{Error, Vector};
+
+// Residual norm below which a unit-length candidate is considered to lie in the span of the vectors accepted so far.
+const RANK_TOLERANCE: f64 = 1e-10;
+
+// A set of pairwise orthogonal unit vectors. Only obtainable from the orthogonalization routines or through
+// validated construction, so the projection helpers below can rely on the invariant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrthonormalBasis<V: Vector> {
+    vectors: Vec<V>,
+}
+
+impl<V: Vector> OrthonormalBasis<V> {
+    // Orthonormal basis of the span of `vectors`. Zero, non-finite and (numerically) dependent vectors are dropped, so
+    // the basis can be shorter than the input.
+    pub fn gram_schmidt(vectors: Vec<V>) -> Self {
+        let mut accepted = Vec::with_capacity(vectors.len());
+        extend_orthonormal(&mut accepted, vectors.into_iter());
+        return Self { vectors: accepted };
+    }
+
+    pub fn try_from_orthonormal(vectors: Vec<V>, tolerance: f64) -> Result<Self, Error> {
+        for (i, a) in vectors.iter().enumerate() {
+            if (V::dot_product(a, a) - 1.0).abs() > tolerance {
+                return Err(Error::NotOrthonormal { vector: i });
+            }
+            for b in &vectors[i + 1..] {
+                if V::dot_product(a, b).abs() > tolerance {
+                    return Err(Error::NotOrthonormal { vector: i });
+                }
+            }
+        }
+        return Ok(Self { vectors });
+    }
+
+    pub fn vectors(&self) -> &[V] {
+        return &self.vectors;
+    }
+
+    pub fn into_vectors(self) -> Vec<V> {
+        return self.vectors;
+    }
+
+    pub fn project(&self, v: &V) -> V {
+        return self.reconstruct(&self.coordinates_of(v));
+    }
+
+    pub fn coordinates_of(&self, v: &V) -> Vec<f64> {
+        return self.vectors.iter().map(|b| V::dot_product(b, v)).collect();
+    }
+
+    pub fn reconstruct(&self, coordinates: &[f64]) -> V {
+        return self.vectors.iter()
+            .zip(coordinates)
+            .map(|(b, c)| b.clone().scale(*c))
+            .sum();
+    }
+
+    // Orthonormal basis of the orthogonal complement within the whole space.
+    pub fn complement(&self) -> Self {
+        let mut vectors = self.vectors.clone();
+        extend_orthonormal(&mut vectors, (0..V::DIM).map(V::unit));
+        return Self { vectors: vectors.split_off(self.vectors.len()) };
+    }
+
+    // Orthonormal basis of the sum of both subspaces.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut vectors = self.vectors.clone();
+        extend_orthonormal(&mut vectors, other.vectors.iter().cloned());
+        return Self { vectors };
+    }
+
+    // Uses (U ∩ W)^⊥ = U^⊥ + W^⊥.
+    pub fn intersection(&self, other: &Self) -> Self {
+        return self.complement().union(&other.complement()).complement();
+    }
+}
+
+// Appends the candidates that are not (numerically) in the span of `accepted`, orthonormalized against it.
+// Projections are subtracted twice so that the rank decision is not spoiled by cancellation.
+pub(crate) fn extend_orthonormal<V: Vector>(accepted: &mut Vec<V>, candidates: impl Iterator<Item=V>) {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("extend_orthonormal", accepted = accepted.len(), dim = V::DIM).entered();
+    for mut candidate in candidates {
+        let norm = candidate.length();
+        if norm == 0.0 {
+            continue;
+        }
+        candidate = candidate / norm;
+        for _ in 0..2 {
+            for b in accepted.iter() {
+                let dot = V::dot_product(&candidate, b);
+                Vector::sub(&mut candidate, &b.clone().scale(dot));
+            }
+        }
+        #[cfg(feature = "metrics")]
+        crate::telemetry::reorthogonalization_passes("extend_orthonormal", 1);
+        let residual = candidate.length();
+        #[cfg(feature = "tracing")]
+        tracing::trace!(norm, residual_norm = residual, accepted = residual > RANK_TOLERANCE, "reorthogonalized candidate");
+        if residual > RANK_TOLERANCE {
+            accepted.push(candidate / residual);
+        }
+    }
+}
+
+pub fn coordinates_of<V: Vector>(v: &V, basis: &OrthonormalBasis<V>) -> Vec<f64> {
+    return basis.coordinates_of(v);
+}
+
+pub fn from_coordinates<V: Vector>(coordinates: &[f64], basis: &OrthonormalBasis<V>) -> V {
+    return basis.reconstruct(coordinates);
+}
+
+impl<V: Vector> Deref for OrthonormalBasis<V> {
+    type Target = [V];
+
+    fn deref(&self) -> &Self::Target {
+        return &self.vectors;
+    }
+}
+
+#[cfg(test)]
+mod orthonormal_basis_test {
+    use crate::{coordinates_of, from_coordinates, Error, OrthonormalBasis, Vector, Vector3};
+
+    #[test]
+    fn project_and_reconstruct() {
+        let basis = OrthonormalBasis::gram_schmidt(vec![
+            Vector3::new([2.0, 0.0, 0.0]),
+            Vector3::new([1.0, 3.0, 0.0]),
+        ]);
+        assert_eq!(basis.len(), 2);
+        let v = Vector3::new([1.0, 2.0, 3.0]);
+        assert_eq!(basis.coordinates_of(&v), vec![1.0, 2.0]);
+        assert_eq!(basis.project(&v), Vector3::new([1.0, 2.0, 0.0]));
+    }
+
+    #[test]
+    fn change_of_basis_round_trip() {
+        let basis = OrthonormalBasis::gram_schmidt(vec![
+            Vector3::new([1.0, 1.0, 0.0]),
+            Vector3::new([0.0, 1.0, 1.0]),
+            Vector3::new([1.0, 0.0, 1.0]),
+        ]);
+        let v = Vector3::new([1.0, -2.0, 3.0]);
+        let coordinates = coordinates_of(&v, &basis);
+        assert_eq!(coordinates.len(), 3);
+        assert!((from_coordinates(&coordinates, &basis) - v).length() < 1e-12);
+    }
+
+    #[test]
+    fn union_and_intersection() {
+        let xy = OrthonormalBasis::gram_schmidt(vec![Vector3::new([1.0, 1.0, 0.0]), Vector3::E1]);
+        let yz = OrthonormalBasis::gram_schmidt(vec![Vector3::E1, Vector3::E2]);
+        let z = OrthonormalBasis::gram_schmidt(vec![Vector3::E2]);
+
+        assert_eq!(xy.union(&yz).len(), 3);
+        assert_eq!(xy.union(&xy).len(), 2);
+        assert_eq!(xy.complement().len(), 1);
+        assert_eq!(xy.intersection(&z).len(), 0);
+
+        let line = xy.intersection(&yz);
+        assert_eq!(line.len(), 1);
+        assert!((line[0][1].abs() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn validated_construction() {
+        let valid = vec![Vector3::E0, Vector3::E2];
+        let invalid = vec![Vector3::E0, Vector3::new([1.0, 1.0, 0.0])];
+        assert!(OrthonormalBasis::try_from_orthonormal(valid, 1e-12).is_ok());
+        assert_eq!(OrthonormalBasis::try_from_orthonormal(invalid, 1e-12), Err(Error::NotOrthonormal { vector: 0 }));
+    }
+
+    #[test]
+    fn dependent_vectors_are_dropped() {
+        let basis = OrthonormalBasis::gram_schmidt(vec![
+            Vector3::new([1.0, 1.0, 0.0]),
+            Vector3::zero(),
+            Vector3::new([2.0, 2.0, 0.0]),
+            Vector3::new([f64::NAN, 0.0, 0.0]),
+            Vector3::new([0.0, 1.0, 0.0]),
+        ]);
+        assert_eq!(basis.len(), 2);
+        assert!(basis.iter().all(|v| (v.length() - 1.0).abs() < 1e-12));
+        assert!(Vector3::dot_product(&basis[0], &basis[1]).abs() < 1e-12);
+    }
+}
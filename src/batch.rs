@@ -0,0 +1,90 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+
+use gram_schmidt::json::{BasisDocument, FactorizationDocument};
+use gram_schmidt::{try_householder_qr, Matrix};
+
+// `gram-schmidt batch --input-dir <dir> [--output-dir <dir>] [--jobs <n>]`: factors every `*.json` basis document in
+// the input directory and writes `<name>.qr.json` next to it or into the output directory. A fixed pool of `jobs`
+// workers pulls one file at a time, so at most `jobs` matrices are in memory at once. Returns the number of failures.
+pub fn run(args: &[String]) -> Result<usize, String> {
+    let mut input_dir = None;
+    let mut output_dir = None;
+    let mut jobs = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+        match arg.as_str() {
+            "--input-dir" => input_dir = Some(PathBuf::from(value()?)),
+            "--output-dir" => output_dir = Some(PathBuf::from(value()?)),
+            "--jobs" => jobs = value()?.parse().map_err(|_| "--jobs expects a positive number".to_string())?,
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+    let input_dir = input_dir.ok_or("--input-dir is required")?;
+    let output_dir = output_dir.unwrap_or_else(|| input_dir.clone());
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(&input_dir)
+        .map_err(|e| format!("{}: {}", input_dir.display(), e))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|e| e == "json") && !is_output(path))
+        .collect();
+    files.sort();
+
+    let queue = Mutex::new(files.into_iter());
+    let failures = Mutex::new(0);
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| loop {
+                let Some(path) = queue.lock().unwrap().next() else {
+                    return;
+                };
+                match process(&path, &output_dir) {
+                    Ok(output) => eprintln!("{}: ok -> {}", path.display(), output.display()),
+                    Err(error) => {
+                        eprintln!("{}: error: {}", path.display(), error);
+                        *failures.lock().unwrap() += 1;
+                    }
+                }
+            });
+        }
+    });
+    return Ok(failures.into_inner().unwrap());
+}
+
+fn is_output(path: &Path) -> bool {
+    return path.to_string_lossy().ends_with(".qr.json");
+}
+
+fn process(path: &Path, output_dir: &Path) -> Result<PathBuf, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let document = BasisDocument::from_json(&text).map_err(|e| e.to_string())?;
+    let (q, r) = try_householder_qr(&Matrix::from_columns(&document.vectors)).map_err(|e| e.to_string())?;
+    let stem = path.file_stem().unwrap().to_string_lossy();
+    let output = output_dir.join(format!("{}.qr.json", stem));
+    std::fs::write(&output, FactorizationDocument::from_qr(&q, &r).to_json()).map_err(|e| e.to_string())?;
+    return Ok(output);
+}
+
+#[cfg(test)]
+mod batch_test {
+    use super::run;
+
+    #[test]
+    fn processes_directory_and_reports_failures() {
+        let dir = std::env::temp_dir().join(format!("gram_schmidt_batch_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("good.json"), r#"{"dim": 2, "vectors": [[3, 4]]}"#).unwrap();
+        std::fs::write(dir.join("bad.json"), "{").unwrap();
+        std::fs::write(dir.join("ignored.txt"), "").unwrap();
+
+        let args = ["--input-dir", dir.to_str().unwrap(), "--jobs", "2"].map(String::from);
+        assert_eq!(run(&args), Ok(1));
+        assert!(std::fs::read_to_string(dir.join("good.qr.json")).unwrap().contains("\"r\":[[-5.0]]"));
+        assert!(!dir.join("bad.qr.json").exists());
+        // Outputs of earlier runs are not picked up as inputs.
+        assert_eq!(run(&args), Ok(1));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
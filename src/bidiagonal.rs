@@ -0,0 +1,92 @@
+use crate::householder::{apply_reflector, reflector};
+use crate::{HouseholderProduct, Matrix};
+
+// Golub-Kahan bidiagonalization `A = U B Vᵀ` of an `m x n` matrix with `m >= n`, alternating Householder reflectors
+// from the left (zeroing a column below the diagonal) and from the right (zeroing a row right of the superdiagonal).
+// Returns the `m x n` `U` with orthonormal columns, the `n x n` upper bidiagonal `B` and the orthogonal `n x n` `V`.
+// `B` has the singular values of `A`.
+pub fn bidiagonalize(a: &Matrix) -> (Matrix, Matrix, Matrix) {
+    let (m, n) = (a.rows(), a.cols());
+    assert!(m >= n, "bidiagonalize needs at least as many rows as columns");
+    let mut work = a.clone();
+    let mut left = HouseholderProduct::identity(m);
+    let mut right = HouseholderProduct::identity(n);
+    let mut b = Matrix::zeros(n, n);
+    let mut row = vec![0.0; n];
+    for k in 0..n {
+        let (tau, beta) = reflector(&mut work.column_mut(k)[k..]);
+        let v = work.column(k)[k..].to_vec();
+        for j in k + 1..n {
+            apply_reflector(&v, tau, &mut work.column_mut(j)[k..]);
+        }
+        left.push(k, v, tau);
+        b[(k, k)] = beta;
+
+        if k + 1 < n {
+            for (j, value) in row.iter_mut().enumerate().skip(k + 1) {
+                *value = work[(k, j)];
+            }
+            let (tau, beta) = reflector(&mut row[k + 1..]);
+            let v = row[k + 1..].to_vec();
+            for i in k + 1..m {
+                for (j, value) in row.iter_mut().enumerate().skip(k + 1) {
+                    *value = work[(i, j)];
+                }
+                apply_reflector(&v, tau, &mut row[k + 1..]);
+                for (j, value) in row.iter().enumerate().skip(k + 1) {
+                    work[(i, j)] = *value;
+                }
+            }
+            right.push(k + 1, v, tau);
+            b[(k, k + 1)] = beta;
+        }
+    }
+
+    let mut u = Matrix::zeros(m, n);
+    for j in 0..n {
+        u.column_mut(j)[j] = 1.0;
+        left.apply(u.column_mut(j));
+    }
+    return (u, b, right.to_matrix());
+}
+
+#[cfg(test)]
+mod bidiagonal_test {
+    use crate::{bidiagonalize, Matrix};
+
+    #[test]
+    fn factors_tall_matrix() {
+        let a = Matrix::from_rows(&[
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 10.0],
+            vec![-1.0, 0.5, 2.0],
+            vec![3.0, -2.0, 1.0],
+        ]);
+        let (u, b, v) = bidiagonalize(&a);
+        assert_eq!((u.rows(), u.cols(), b.rows(), v.rows()), (5, 3, 3, 3));
+        for j in 0..3 {
+            for i in 0..3 {
+                if i > j || j > i + 1 {
+                    assert_eq!(b[(i, j)], 0.0);
+                }
+            }
+        }
+        let reconstructed = &(&u * &b) * &v.transpose();
+        let (utu, vtv) = (&u.transpose() * &u, &v.transpose() * &v);
+        for j in 0..3 {
+            for i in 0..5 {
+                assert!((reconstructed[(i, j)] - a[(i, j)]).abs() < 1e-13);
+            }
+            for i in 0..3 {
+                let delta = if i == j { 1.0 } else { 0.0 };
+                assert!((utu[(i, j)] - delta).abs() < 1e-14 && (vtv[(i, j)] - delta).abs() < 1e-14);
+            }
+        }
+        // Orthogonal transformations keep the Frobenius norm.
+        let frobenius = |m: &Matrix, rows: usize| (0..3).flat_map(|j| (0..rows).map(move |i| (i, j)))
+            .map(|(i, j)| m[(i, j)] * m[(i, j)])
+            .sum::<f64>();
+        assert!((frobenius(&a, 5) - frobenius(&b, 3)).abs() < 1e-11);
+    }
+}
@@ -0,0 +1,102 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{AffineSubspace, Error, Matrix, OrthonormalBasis, Vector};
+
+// Maximum deviation from orthonormality accepted when decoding a subspace basis.
+const ORTHONORMALITY_TOLERANCE: f64 = 1e-10;
+
+// Compact bincode encodings of factorizations and subspaces, for caching them or shipping them between services.
+// Matrices are stored column-major with their shape; decoding validates shapes and orthonormality.
+
+#[derive(Serialize, Deserialize)]
+struct MatrixData {
+    rows: usize,
+    cols: usize,
+    columns: Vec<Vec<f64>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SubspaceData {
+    origin: Vec<f64>,
+    basis: Vec<Vec<f64>>,
+}
+
+impl MatrixData {
+    fn new(m: &Matrix) -> Self {
+        return Self { rows: m.rows(), cols: m.cols(), columns: (0..m.cols()).map(|j| m.column(j).to_vec()).collect() };
+    }
+
+    fn into_matrix(self) -> Result<Matrix, Error> {
+        if self.columns.len() != self.cols {
+            return Err(Error::DimensionMismatch { expected: self.cols, found: self.columns.len() });
+        }
+        if let Some(column) = self.columns.iter().find(|c| c.len() != self.rows) {
+            return Err(Error::DimensionMismatch { expected: self.rows, found: column.len() });
+        }
+        let mut m = Matrix::zeros(self.rows, self.cols);
+        for (j, column) in self.columns.iter().enumerate() {
+            m.column_mut(j).copy_from_slice(column);
+        }
+        return Ok(m);
+    }
+}
+
+fn vector_from<V: Vector>(components: &[f64]) -> Result<V, Error> {
+    if components.len() != V::DIM {
+        return Err(Error::DimensionMismatch { expected: V::DIM, found: components.len() });
+    }
+    let mut v = V::zero();
+    v.get_components_mut().copy_from_slice(components);
+    return Ok(v);
+}
+
+fn decode<'a, T: Deserialize<'a>>(bytes: &'a [u8]) -> Result<T, Error> {
+    return bincode::deserialize(bytes).map_err(|_| Error::InvalidEncoding);
+}
+
+pub fn encode_qr(q: &Matrix, r: &Matrix) -> Vec<u8> {
+    return bincode::serialize(&(MatrixData::new(q), MatrixData::new(r))).unwrap();
+}
+
+pub fn decode_qr(bytes: &[u8]) -> Result<(Matrix, Matrix), Error> {
+    let (q, r): (MatrixData, MatrixData) = decode(bytes)?;
+    return Ok((q.into_matrix()?, r.into_matrix()?));
+}
+
+pub fn encode_subspace<V: Vector>(subspace: &AffineSubspace<V>) -> Vec<u8> {
+    let components = |v: &V| (0..V::DIM).map(|i| v[i]).collect();
+    let data = SubspaceData {
+        origin: components(subspace.origin()),
+        basis: subspace.basis().iter().map(components).collect(),
+    };
+    return bincode::serialize(&data).unwrap();
+}
+
+pub fn decode_subspace<V: Vector>(bytes: &[u8]) -> Result<AffineSubspace<V>, Error> {
+    let data: SubspaceData = decode(bytes)?;
+    let origin = vector_from(&data.origin)?;
+    let vectors = data.basis.iter().map(|b| vector_from(b)).collect::<Result<Vec<V>, Error>>()?;
+    let basis = OrthonormalBasis::try_from_orthonormal(vectors, ORTHONORMALITY_TOLERANCE)
+        .map_err(|_| Error::InvalidEncoding)?;
+    return Ok(AffineSubspace::new(origin, basis));
+}
+
+#[cfg(test)]
+mod binary_test {
+    use crate::binary::{decode_qr, decode_subspace, encode_qr, encode_subspace};
+    use crate::{best_fit_subspace, householder_qr, Error, Matrix, Vector3, Vector4};
+
+    #[test]
+    fn round_trips() {
+        let (q, r) = householder_qr(&Matrix::from_rows(&[vec![1.0, 2.0], vec![0.0, 1.0], vec![1.0, 0.0]]));
+        let bytes = encode_qr(&q, &r);
+        assert_eq!(decode_qr(&bytes), Ok((q, r)));
+        assert_eq!(decode_qr(&bytes[..bytes.len() - 1]), Err(Error::InvalidEncoding));
+
+        let points = [Vector3::new([0.0, 0.0, 1.0]), Vector3::new([1.0, 0.0, 1.0]), Vector3::new([0.0, 2.0, 1.0])];
+        let plane = best_fit_subspace(&points, 2).unwrap();
+        let bytes = encode_subspace(&plane);
+        assert_eq!(decode_subspace::<Vector3>(&bytes), Ok(plane));
+        assert_eq!(decode_subspace::<Vector4>(&bytes), Err(Error::DimensionMismatch { expected: 4, found: 3 }));
+    }
+}
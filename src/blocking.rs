@@ -0,0 +1,153 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::{storage_axpy, storage_dot, Error, Matrix};
+
+// Residual norm, relative to the column norm, below which a column counts as linearly dependent.
+const RANK_TOLERANCE: f64 = 1e-10;
+
+// Shared flag for stopping a running orthonormalization from another thread or task. Clones share the flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        return self.cancelled.load(Ordering::Relaxed);
+    }
+}
+
+// Options for `orthonormalize_cancellable` and `orthonormalize_async`.
+#[derive(Debug, Clone, Default)]
+pub struct OrthonormalizeOptions {
+    // Project every column twice, which keeps the result orthogonal to working precision for ill-conditioned input.
+    pub reorthogonalize: bool,
+    // Checked before every column; once cancelled the call returns `Error::Cancelled`.
+    pub cancellation: CancellationToken,
+}
+
+// Orthonormalizes the columns of `matrix` with modified Gram-Schmidt, checking `options.cancellation` before every
+// column. This is the blocking core of `orthonormalize_async`, independent of any executor, so it can be run on any
+// thread pool (rayon, a custom pool, `std::thread`) that offers a way to wait for the result.
+pub fn orthonormalize_cancellable(matrix: &Matrix, options: &OrthonormalizeOptions) -> Result<Matrix, Error> {
+    #[cfg(feature = "metrics")]
+    let _timer = crate::telemetry::orthogonalization("orthonormalize_cancellable");
+    let mut columns: Vec<Vec<f64>> = (0..matrix.cols()).map(|j| matrix.column(j).to_vec()).collect();
+    for (vector, column) in columns.iter().enumerate() {
+        if let Some(component) = column.iter().position(|c| !c.is_finite()) {
+            return Err(Error::NonFinite { vector, component });
+        }
+    }
+    let passes = if options.reorthogonalize { 2 } else { 1 };
+    for j in 0..columns.len() {
+        if options.cancellation.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+        let (done, rest) = columns.split_at_mut(j);
+        let column = &mut rest[0];
+        let scale = storage_dot(column, column).sqrt();
+        for _ in 0..passes {
+            for q in done.iter() {
+                let dot = storage_dot(q, column);
+                storage_axpy(-dot, q, column);
+            }
+        }
+        #[cfg(feature = "metrics")]
+        crate::telemetry::reorthogonalization_passes("orthonormalize_cancellable", passes - 1);
+        let norm = storage_dot(column, column).sqrt();
+        if norm <= RANK_TOLERANCE * scale || norm == 0.0 {
+            #[cfg(feature = "metrics")]
+            crate::telemetry::rank_deficiencies("orthonormalize_cancellable", 1);
+            return Err(Error::ZeroVector { vector: j });
+        }
+        column.iter_mut().for_each(|c| *c /= norm);
+    }
+    return Ok(Matrix::from_columns(&columns));
+}
+
+// `orthonormalize_cancellable` on tokio's blocking pool, so async code can await it without stalling its executor.
+// Dropping the returned future before it completes cancels the computation, as does cancelling the token in
+// `options` from elsewhere; in both cases the blocking thread stops at the next column.
+#[cfg(feature = "tokio")]
+pub async fn orthonormalize_async(matrix: Matrix, options: OrthonormalizeOptions) -> Result<Matrix, Error> {
+    // Cancels on drop unless disarmed, i.e. when the awaiting task goes away first.
+    struct CancelOnDrop(Option<CancellationToken>);
+
+    impl Drop for CancelOnDrop {
+        fn drop(&mut self) {
+            if let Some(token) = self.0.take() {
+                token.cancel();
+            }
+        }
+    }
+
+    let mut guard = CancelOnDrop(Some(options.cancellation.clone()));
+    let result = tokio::task::spawn_blocking(move || orthonormalize_cancellable(&matrix, &options)).await;
+    guard.0 = None;
+    return match result {
+        Ok(result) => result,
+        Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+        Err(_) => Err(Error::Cancelled),
+    };
+}
+
+#[cfg(test)]
+mod blocking_test {
+    use crate::{orthonormalize_cancellable, CancellationToken, Error, Matrix, OrthonormalizeOptions};
+
+    #[test]
+    fn orthonormalizes_until_cancelled() {
+        let a = Matrix::from_columns(&[vec![3.0, 4.0, 0.0], vec![1.0, 1.0, 1.0]]);
+        let q = orthonormalize_cancellable(&a, &OrthonormalizeOptions { reorthogonalize: true, ..Default::default() })
+            .unwrap();
+        assert_eq!(q.column(0), [0.6, 0.8, 0.0]);
+        assert!((q.column(1).iter().zip(q.column(0)).map(|(x, y)| x * y).sum::<f64>()).abs() < 1e-15);
+
+        let dependent = Matrix::from_columns(&[vec![1.0, 2.0], vec![2.0, 4.0]]);
+        let options = OrthonormalizeOptions::default();
+        assert_eq!(orthonormalize_cancellable(&dependent, &options), Err(Error::ZeroVector { vector: 1 }));
+
+        let token = CancellationToken::new();
+        let options = OrthonormalizeOptions { cancellation: token.clone(), ..Default::default() };
+        token.cancel();
+        assert_eq!(orthonormalize_cancellable(&a, &options), Err(Error::Cancelled));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn async_runs_on_blocking_pool() {
+        use std::future::Future;
+
+        use crate::orthonormalize_async;
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let a = Matrix::from_columns(&[vec![0.0, 2.0], vec![1.0, 1.0]]);
+        let q = runtime.block_on(orthonormalize_async(a.clone(), OrthonormalizeOptions::default())).unwrap();
+        assert_eq!(q.column(1), [1.0, 0.0]);
+
+        // Dropping the future after its first poll, while the blocking job is still running, cancels its token.
+        let columns: Vec<Vec<f64>> = (0..400)
+            .map(|j| (0..400).map(|i| ((i * j) % 7) as f64 + if i == j { 9.0 } else { 0.0 }).collect())
+            .collect();
+        let large = Matrix::from_columns(&columns);
+        let token = CancellationToken::new();
+        let options = OrthonormalizeOptions { cancellation: token.clone(), ..Default::default() };
+        runtime.block_on(async {
+            let mut future = std::pin::pin!(orthonormalize_async(large, options));
+            std::future::poll_fn(|cx| {
+                assert!(future.as_mut().poll(cx).is_pending());
+                return std::task::Poll::Ready(());
+            }).await;
+        });
+        assert!(token.is_cancelled());
+    }
+}
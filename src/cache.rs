@@ -0,0 +1,171 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::{HashableVector, Vector};
+
+const F64_SIZE: usize = std::mem::size_of::<f64>();
+
+// Memoizes `Vector::gram_schmidt` results keyed by the exact bit patterns of the input, in memory and optionally in a
+// directory on disk. The disk cache is best effort: unreadable or mismatching files count as misses and failed writes
+// are ignored, so a broken directory only costs the recomputation.
+#[derive(Debug, Clone)]
+pub struct BasisCache<V: Vector> {
+    memory: HashMap<Vec<HashableVector<V>>, Vec<V>>,
+    directory: Option<PathBuf>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<V: Vector> BasisCache<V> {
+    pub fn new() -> Self {
+        return Self { memory: HashMap::new(), directory: None, hits: 0, misses: 0 };
+    }
+
+    // Also persists every result as `<content key>.bin` in `directory`, which is created if needed.
+    pub fn with_directory<P: Into<PathBuf>>(directory: P) -> std::io::Result<Self> {
+        let directory = directory.into();
+        std::fs::create_dir_all(&directory)?;
+        return Ok(Self { directory: Some(directory), ..Self::new() });
+    }
+
+    pub fn gram_schmidt(&mut self, input: &[V]) -> Vec<V> {
+        let key: Vec<HashableVector<V>> = input.iter().cloned().map(HashableVector).collect();
+        if let Some(basis) = self.memory.get(&key) {
+            self.hits += 1;
+            return basis.clone();
+        }
+        if let Some(basis) = self.load(input) {
+            self.hits += 1;
+            self.memory.insert(key, basis.clone());
+            return basis;
+        }
+        self.misses += 1;
+        let mut basis = input.to_vec();
+        V::gram_schmidt(&mut basis);
+        self.store(input, &basis);
+        self.memory.insert(key, basis.clone());
+        return basis;
+    }
+
+    pub fn hits(&self) -> usize {
+        return self.hits;
+    }
+
+    pub fn misses(&self) -> usize {
+        return self.misses;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.memory.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.memory.is_empty();
+    }
+
+    // Drops the in-memory entries; files on disk are kept.
+    pub fn clear(&mut self) {
+        self.memory.clear();
+    }
+
+    fn path(&self, input: &[V]) -> Option<PathBuf> {
+        return self.directory.as_ref().map(|dir| dir.join(format!("{:016x}.bin", content_key(input))));
+    }
+
+    // A file holds the input followed by the result, both as little-endian f64. The input is compared on load, so a
+    // collision of the 64-bit keys is a miss rather than a wrong answer.
+    fn load(&self, input: &[V]) -> Option<Vec<V>> {
+        let bytes = std::fs::read(self.path(input)?).ok()?;
+        let count = input.len() * V::DIM;
+        if bytes.len() != 2 * count * F64_SIZE {
+            return None;
+        }
+        let mut values = bytes.chunks_exact(F64_SIZE).map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()));
+        for v in input {
+            for i in 0..V::DIM {
+                if values.next()?.to_bits() != v[i].to_bits() {
+                    return None;
+                }
+            }
+        }
+        let basis = (0..input.len()).map(|_| {
+            let mut q = V::zero();
+            q.get_components_mut().iter_mut().for_each(|c| *c = values.next().unwrap());
+            return q;
+        }).collect();
+        return Some(basis);
+    }
+
+    fn store(&self, input: &[V], basis: &[V]) {
+        let Some(path) = self.path(input) else {
+            return;
+        };
+        let mut bytes = Vec::with_capacity(2 * input.len() * V::DIM * F64_SIZE);
+        for v in input.iter().chain(basis) {
+            for i in 0..V::DIM {
+                bytes.extend_from_slice(&v[i].to_le_bytes());
+            }
+        }
+        let _ = std::fs::write(path, bytes);
+    }
+}
+
+impl<V: Vector> Default for BasisCache<V> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+// 64-bit FNV-1a hash of the dimension, the number of vectors and the bit patterns of all components. Unlike
+// `DefaultHasher` it is stable across Rust versions, so it can name files of a persistent cache.
+pub fn content_key<V: Vector>(input: &[V]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut feed = |word: u64| {
+        for byte in word.to_le_bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+    feed(V::DIM as u64);
+    feed(input.len() as u64);
+    for v in input {
+        for i in 0..V::DIM {
+            feed(v[i].to_bits());
+        }
+    }
+    return hash;
+}
+
+#[cfg(test)]
+mod basis_cache_test {
+    use crate::{content_key, BasisCache, Vector3};
+
+    #[test]
+    fn memoizes_bit_exact_inputs() {
+        let input = vec![Vector3::new([1.0, 1.0, 0.0]), Vector3::new([0.0, 1.0, 1.0])];
+        let mut cache = BasisCache::new();
+        let first = cache.gram_schmidt(&input);
+        assert_eq!(cache.gram_schmidt(&input), first);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        // `-0.0` has a different bit pattern, so it is a different key.
+        let signed_zero = vec![Vector3::new([1.0, 1.0, -0.0]), Vector3::new([0.0, 1.0, 1.0])];
+        assert_ne!(content_key(&input), content_key(&signed_zero));
+        cache.gram_schmidt(&signed_zero);
+        assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 2, 2));
+    }
+
+    // Miri cannot access the file system with isolation enabled.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn persists_on_disk() {
+        let dir = std::env::temp_dir().join(format!("gram_schmidt_cache_{}", std::process::id()));
+        let input = vec![Vector3::new([2.0, 0.0, 0.0]), Vector3::new([1.0, 3.0, 0.0])];
+        let first = BasisCache::with_directory(&dir).unwrap().gram_schmidt(&input);
+
+        let mut reopened = BasisCache::with_directory(&dir).unwrap();
+        assert_eq!(reopened.gram_schmidt(&input), first);
+        assert_eq!((reopened.hits(), reopened.misses()), (1, 0));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
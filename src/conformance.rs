@@ -0,0 +1,140 @@
+// Checks that a `Vector` implementation obeys the laws the algorithms of this crate rely on. Call
+// `assert_vector_laws!(MyVector)` from a test of the crate defining `MyVector`; it panics naming the first law that
+// fails. Sample vectors are generated deterministically from the components, so no `Debug` or `PartialEq` is needed.
+use crate::Vector;
+
+// Relative tolerance for the laws that involve rounding.
+const TOLERANCE: f64 = 1e-12;
+
+#[macro_export]
+macro_rules! assert_vector_laws {
+    ($ty:ty) => {
+        $crate::conformance::assert_vector_laws::<$ty>()
+    };
+}
+
+pub fn assert_vector_laws<V: Vector>() {
+    assert!(V::DIM > 0, "DIM must be positive");
+    let (a, b) = (sample::<V>(1), sample::<V>(2));
+    check_components(&a);
+    check_additivity(&a, &b);
+    check_scaling(&a);
+    check_inner_product(&a, &b);
+    check_gram_schmidt::<V>();
+}
+
+// Component `i` of sample `seed`: small integers and halves, so sums and products are exact.
+fn sample<V: Vector>(seed: usize) -> V {
+    let mut v = V::zero();
+    for i in 0..V::DIM {
+        v[i] = ((seed * 7 + i * 3) % 11) as f64 * 0.5 - 2.0;
+    }
+    return v;
+}
+
+fn assert_components<V: Vector>(law: &str, actual: &V, expected: impl Fn(usize) -> f64) {
+    for i in 0..V::DIM {
+        assert!(actual[i] == expected(i), "{}: component {} is {}, expected {}", law, i, actual[i], expected(i));
+    }
+}
+
+fn assert_close(law: &str, actual: f64, expected: f64, scale: f64) {
+    assert!((actual - expected).abs() <= TOLERANCE * scale.max(1.0), "{}: {} != {}", law, actual, expected);
+}
+
+fn check_components<V: Vector>(a: &V) {
+    let mut copy = a.clone();
+    assert_eq!(copy.get_components_mut().len(), V::DIM, "get_components_mut must have DIM components");
+    for i in 0..V::DIM {
+        assert_eq!(a.get_component(i), a[i], "get_component must agree with indexing at {}", i);
+        copy.get_components_mut()[i] += 1.0;
+    }
+    assert_components("get_components_mut must alias the components", &copy, |i| a[i] + 1.0);
+    assert_components("zero", &V::zero(), |_| 0.0);
+    for axis in 0..V::DIM {
+        assert_components("unit", &V::unit(axis), |i| if i == axis { 1.0 } else { 0.0 });
+    }
+}
+
+fn check_additivity<V: Vector>(a: &V, b: &V) {
+    assert_components("addition is componentwise", &(a.clone() + b.clone()), |i| a[i] + b[i]);
+    assert_components("addition commutes", &(a.clone() + b.clone()), |i| (b.clone() + a.clone())[i]);
+    assert_components("zero is the additive identity", &(a.clone() + V::zero()), |i| a[i]);
+    assert_components("subtraction is componentwise", &(a.clone() - b.clone()), |i| a[i] - b[i]);
+    assert_components("negation is the additive inverse", &(a.clone() + -a.clone()), |_| 0.0);
+    let sum: V = [a.clone(), b.clone(), a.clone()].into_iter().sum();
+    assert_components("sum adds all vectors", &sum, |i| a[i] + b[i] + a[i]);
+    let mut c = a.clone();
+    Vector::sub(&mut c, b);
+    assert_components("Vector::sub subtracts in place", &c, |i| a[i] - b[i]);
+    let mut c = a.clone();
+    c.axpy(2.0, b);
+    assert_components("axpy adds a multiple", &c, |i| a[i] + 2.0 * b[i]);
+}
+
+fn check_scaling<V: Vector>(a: &V) {
+    assert_components("scaling is componentwise", &(a.clone() * 2.5), |i| a[i] * 2.5);
+    assert_components("division is componentwise", &(a.clone() / 4.0), |i| a[i] / 4.0);
+    assert_components("scaling by one is the identity", &(a.clone() * 1.0), |i| a[i]);
+    assert_components("scaling by zero gives zero", &(a.clone() * 0.0), |_| 0.0);
+}
+
+fn check_inner_product<V: Vector>(a: &V, b: &V) {
+    let expected: f64 = (0..V::DIM).map(|i| a[i] * b[i]).sum();
+    let scale: f64 = (0..V::DIM).map(|i| (a[i] * b[i]).abs()).sum();
+    assert_close("dot_product sums the componentwise products", V::dot_product(a, b), expected, scale);
+    assert_eq!(V::dot_product(a, b), V::dot_product(b, a), "dot_product must be symmetric");
+    let squared = V::dot_product(a, a);
+    assert!(squared >= 0.0, "dot_product must be positive semi-definite");
+    assert_close("length is the square root of the self inner product", a.length() * a.length(), squared, squared);
+    let c = a.clone() * 3.0 + b.clone();
+    let linear = 3.0 * V::dot_product(a, b) + V::dot_product(b, b);
+    assert_close("dot_product is linear", V::dot_product(&c, b), linear, linear.abs() + scale);
+    for i in 0..V::DIM {
+        for j in 0..V::DIM {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert_eq!(V::dot_product(&V::unit(i), &V::unit(j)), expected, "unit vectors must be orthonormal");
+        }
+    }
+}
+
+// Orthonormalizes a well-conditioned full basis and checks orthonormality and that the span is preserved, i.e. every
+// input is recovered from its coordinates in the output.
+fn check_gram_schmidt<V: Vector>() {
+    let inputs: Vec<V> = (0..V::DIM).map(|k| {
+        let mut v = V::unit(k) * 4.0;
+        for i in 0..V::DIM {
+            v[i] += ((k + 2 * i) % 5) as f64 * 0.25;
+        }
+        return v;
+    }).collect();
+    let mut basis = inputs.clone();
+    V::gram_schmidt(&mut basis);
+    for (i, q) in basis.iter().enumerate() {
+        for (j, p) in basis.iter().enumerate() {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            assert_close("gram_schmidt output must be orthonormal", V::dot_product(q, p), expected, 1e3);
+        }
+    }
+    for v in &inputs {
+        let reconstructed: V = basis.iter().map(|q| q.clone() * V::dot_product(q, v)).sum();
+        for i in 0..V::DIM {
+            assert_close("gram_schmidt must preserve the span", reconstructed[i], v[i], 1e3 * v.length());
+        }
+    }
+}
+
+#[cfg(test)]
+mod conformance_test {
+    use crate::{Vector16, Vector2, Vector3};
+
+    #[test]
+    fn crate_vectors_obey_the_laws() {
+        assert_vector_laws!(Vector2);
+        assert_vector_laws!(Vector3);
+        assert_vector_laws!(Vector16);
+        crate::vector!(Vector1, 1, [E0 = 0]);
+        assert_vector_laws!(Vector1);
+    }
+
+}
@@ -0,0 +1,262 @@
+use crate::krylov::DEFLATION_TOLERANCE;
+use crate::Matrix;
+
+// Reduction step for vectors whose components are spread over several processes. Every process calls the routines
+// below with its local rows; the communicator turns the local partial sums into global ones, e.g. with an MPI
+// all-reduce.
+pub trait Communicator {
+    // Replaces every entry of `values` by its sum over all processes.
+    fn all_reduce_sum(&self, values: &mut [f64]);
+}
+
+// Communicator for the case where the vectors are not distributed at all.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Local;
+
+impl Communicator for Local {
+    fn all_reduce_sum(&self, _values: &mut [f64]) {}
+}
+
+pub fn distributed_dot<C: Communicator>(a: &[f64], b: &[f64], comm: &C) -> f64 {
+    let mut sum = [local_dot(a, b)];
+    comm.all_reduce_sum(&mut sum);
+    return sum[0];
+}
+
+// Orthonormalizes `columns`, each holding this process's rows of one vector. Uses classical Gram-Schmidt with one
+// reorthogonalization pass, so every column costs two batched reductions for the projections plus one for its norm,
+// instead of one reduction per previous column.
+pub fn gram_schmidt_distributed<C: Communicator>(columns: &mut [Vec<f64>], comm: &C) {
+    for index in 0..columns.len() {
+        let (done, rest) = columns.split_at_mut(index);
+        let column = &mut rest[0];
+        for _ in 0..2 {
+            let mut dots: Vec<f64> = done.iter().map(|q| local_dot(q, column)).collect();
+            comm.all_reduce_sum(&mut dots);
+            for (q, dot) in done.iter().zip(dots) {
+                for (c, qi) in column.iter_mut().zip(q) {
+                    *c -= dot * qi;
+                }
+            }
+        }
+        let norm = distributed_dot(column, column, comm).sqrt();
+        column.iter_mut().for_each(|c| *c /= norm);
+    }
+}
+
+// Block Arnoldi process (see `block_arnoldi`) on vectors whose rows are spread over several processes. Every process
+// passes its rows of the `start` vectors and an `op` mapping its rows of `v` to its rows of `A v`, doing whatever
+// communication the operator needs itself. Every reduction goes through `comm`. Orthogonalization is classical
+// Gram-Schmidt with one reorthogonalization pass, so a new vector costs three batched reductions: the projections of
+// each pass, with its original norm folded into the first, and its final norm. All processes get the same `H`.
+pub fn block_arnoldi_distributed<C: Communicator, F: Fn(&[f64]) -> Vec<f64>>(
+    op: F,
+    start: &[Vec<f64>],
+    steps: usize,
+    comm: &C,
+) -> (Vec<Vec<f64>>, Matrix) {
+    let mut basis: Vec<Vec<f64>> = vec![];
+    for v in start {
+        let mut w = v.clone();
+        if let (_, Some(norm)) = orthogonalize(&basis, &mut w, comm) {
+            w.iter_mut().for_each(|c| *c /= norm);
+            basis.push(w);
+        }
+    }
+    let mut columns: Vec<Vec<f64>> = vec![];
+    let mut block = 0..basis.len();
+    for _ in 0..steps {
+        if block.is_empty() {
+            break;
+        }
+        let next_block_start = basis.len();
+        for index in block {
+            let mut w = op(&basis[index]);
+            let (mut column, norm) = orthogonalize(&basis, &mut w, comm);
+            if let Some(norm) = norm {
+                w.iter_mut().for_each(|c| *c /= norm);
+                basis.push(w);
+                column.push(norm);
+            }
+            columns.push(column);
+        }
+        block = next_block_start..basis.len();
+    }
+
+    let mut h = Matrix::zeros(basis.len(), columns.len());
+    for (j, column) in columns.iter().enumerate() {
+        for (i, value) in column.iter().enumerate() {
+            h[(i, j)] = *value;
+        }
+    }
+    return (basis, h);
+}
+
+// Single-vector variant of `block_arnoldi_distributed`.
+pub fn arnoldi_distributed<C: Communicator, F: Fn(&[f64]) -> Vec<f64>>(
+    op: F,
+    start: &[f64],
+    steps: usize,
+    comm: &C,
+) -> (Vec<Vec<f64>>, Matrix) {
+    return block_arnoldi_distributed(op, &[start.to_vec()], steps, comm);
+}
+
+// Projects `w` out of `basis` twice. Returns the summed projection coefficients and the norm of what is left, or
+// `None` for the norm if the remainder is negligible relative to `w` and is to be deflated.
+fn orthogonalize<C: Communicator>(basis: &[Vec<f64>], w: &mut [f64], comm: &C) -> (Vec<f64>, Option<f64>) {
+    let mut column = vec![0.0; basis.len()];
+    let mut scale = 0.0;
+    for pass in 0..2 {
+        let mut dots: Vec<f64> = basis.iter().map(|q| local_dot(q, w)).collect();
+        if pass == 0 {
+            dots.push(local_dot(w, w));
+        }
+        comm.all_reduce_sum(&mut dots);
+        if pass == 0 {
+            scale = dots.pop().unwrap().sqrt();
+        }
+        for ((q, dot), coefficient) in basis.iter().zip(dots).zip(column.iter_mut()) {
+            for (c, qi) in w.iter_mut().zip(q) {
+                *c -= dot * qi;
+            }
+            *coefficient += dot;
+        }
+    }
+    let norm = distributed_dot(w, w, comm).sqrt();
+    return (column, if norm > DEFLATION_TOLERANCE * scale { Some(norm) } else { None });
+}
+
+fn local_dot(a: &[f64], b: &[f64]) -> f64 {
+    return a.iter().zip(b).map(|(x, y)| x * y).sum();
+}
+
+#[cfg(test)]
+mod distributed_test {
+    use std::sync::{Arc, Barrier, Mutex};
+    use std::thread;
+
+    use crate::{
+        arnoldi, arnoldi_distributed, block_arnoldi_distributed, gram_schmidt_distributed, Communicator, Local, Vector,
+        Vector8,
+    };
+
+    struct ThreadCommunicator {
+        rank: usize,
+        barrier: Arc<Barrier>,
+        slots: Arc<Mutex<Vec<Vec<f64>>>>,
+    }
+
+    impl Communicator for ThreadCommunicator {
+        fn all_reduce_sum(&self, values: &mut [f64]) {
+            self.slots.lock().unwrap()[self.rank] = values.to_vec();
+            self.barrier.wait();
+            let slots = self.slots.lock().unwrap().clone();
+            for (i, value) in values.iter_mut().enumerate() {
+                *value = slots.iter().map(|slot| slot[i]).sum();
+            }
+            self.barrier.wait();
+        }
+    }
+
+    fn columns(rows: std::ops::Range<usize>) -> Vec<Vec<f64>> {
+        let full = [
+            [1.0, 1.0, 1.0, 1.0, 2.0, 0.0, 1.0, 3.0],
+            [0.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 2.0],
+            [0.0, 0.0, 1.0, 1.0, 0.0, 2.0, 1.0, 1.0],
+            [0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 2.0, 0.0],
+        ];
+        return full.iter().map(|c| c[rows.clone()].to_vec()).collect();
+    }
+
+    // Splits the 8 rows evenly over `ranks` threads and joins their results back into full columns.
+    fn run_threaded(ranks: usize) -> Vec<Vec<f64>> {
+        let barrier = Arc::new(Barrier::new(ranks));
+        let slots = Arc::new(Mutex::new(vec![vec![]; ranks]));
+        let rows_per_rank = 8 / ranks;
+        let handles: Vec<_> = (0..ranks).map(|rank| {
+            let comm = ThreadCommunicator { rank, barrier: barrier.clone(), slots: slots.clone() };
+            return thread::spawn(move || {
+                let mut part = columns(rank * rows_per_rank..(rank + 1) * rows_per_rank);
+                gram_schmidt_distributed(&mut part, &comm);
+                return part;
+            });
+        }).collect();
+        let parts: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        return (0..4).map(|j| parts.iter().flat_map(|part| part[j].clone()).collect()).collect();
+    }
+
+    #[test]
+    fn matches_local_result() {
+        let mut local = columns(0..8);
+        gram_schmidt_distributed(&mut local, &Local);
+
+        let threaded = run_threaded(2);
+        for j in 0..4 {
+            for i in 0..8 {
+                assert!((threaded[j][i] - local[j][i]).abs() < 1e-12);
+            }
+        }
+    }
+
+    // The reduction sums the ranks in a fixed order, so thread scheduling must not change a single bit of the
+    // result. Kept small enough to run under Miri, which checks the synchronization for data races.
+    #[test]
+    fn threaded_runs_are_deterministic() {
+        let ranks = if cfg!(miri) { 2 } else { 4 };
+        let first = run_threaded(ranks);
+        for _ in 0..if cfg!(miri) { 1 } else { 20 } {
+            assert_eq!(run_threaded(ranks), first);
+        }
+    }
+
+    // Tridiagonal operator on 8 rows. A process only holds its rows, so it assembles the full input with an
+    // all-reduce over zero-padded copies before applying its rows of the matrix.
+    fn apply_rows(v: &[f64], rows: std::ops::Range<usize>, comm: &impl Communicator) -> Vec<f64> {
+        let mut full = vec![0.0; 8];
+        full[rows.clone()].copy_from_slice(v);
+        comm.all_reduce_sum(&mut full);
+        return rows.map(|i| {
+            let left = if i > 0 { -full[i - 1] } else { 0.0 };
+            let right = if i < 7 { -full[i + 1] } else { 0.0 };
+            return left + (2.0 + i as f64) * full[i] + right;
+        }).collect();
+    }
+
+    #[test]
+    fn arnoldi_reductions_go_through_the_communicator() {
+        let start = [1.0, 0.5, -1.0, 2.0, 0.0, 1.0, 1.0, -0.5];
+        let (expected, expected_h) = arnoldi(|v: &Vector8| {
+            Vector8::new(apply_rows(&v.components, 0..8, &Local).try_into().unwrap())
+        }, &Vector8::new(start), 5);
+
+        let ranks = 2;
+        let barrier = Arc::new(Barrier::new(ranks));
+        let slots = Arc::new(Mutex::new(vec![vec![]; ranks]));
+        let handles: Vec<_> = (0..ranks).map(|rank| {
+            let comm = ThreadCommunicator { rank, barrier: barrier.clone(), slots: slots.clone() };
+            let rows = rank * 4..(rank + 1) * 4;
+            return thread::spawn(move || {
+                return arnoldi_distributed(|v| apply_rows(v, rows.clone(), &comm), &start[rows.clone()], 5, &comm);
+            });
+        }).collect();
+        let parts: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        assert_eq!(parts[0].1, parts[1].1);
+        let h = &parts[0].1;
+        assert_eq!((h.rows(), h.cols()), (expected_h.rows(), expected_h.cols()));
+        for j in 0..h.cols() {
+            for i in 0..h.rows() {
+                assert!((h[(i, j)] - expected_h[(i, j)]).abs() < 1e-12);
+            }
+        }
+        for (k, q) in expected.iter().enumerate() {
+            let joined: Vec<f64> = parts.iter().flat_map(|part| part.0[k].clone()).collect();
+            assert!((Vector8::new(joined.try_into().unwrap()) - *q).length() < 1e-12);
+        }
+
+        // A dependent start block is deflated to one vector.
+        let (basis, _) = block_arnoldi_distributed(|v| v.to_vec(), &[vec![1.0, 2.0], vec![2.0, 4.0]], 1, &Local);
+        assert_eq!(basis.len(), 1);
+    }
+}
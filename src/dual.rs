@@ -0,0 +1,134 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Forward-mode dual number `re + eps * ε` with `ε² = 0`. Running an algorithm on duals whose `eps` parts hold an
+// input direction yields the directional derivative of the output in the `eps` parts.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Dual {
+    pub re: f64,
+    pub eps: f64,
+}
+
+impl Dual {
+    pub const fn new(re: f64, eps: f64) -> Self {
+        return Self { re, eps };
+    }
+
+    pub const fn constant(re: f64) -> Self {
+        return Self { re, eps: 0.0 };
+    }
+
+    pub fn sqrt(self) -> Self {
+        let root = self.re.sqrt();
+        return Self { re: root, eps: self.eps / (2.0 * root) };
+    }
+}
+
+impl Add for Dual {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        return Self { re: self.re + rhs.re, eps: self.eps + rhs.eps };
+    }
+}
+
+impl Sub for Dual {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        return Self { re: self.re - rhs.re, eps: self.eps - rhs.eps };
+    }
+}
+
+impl Mul for Dual {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        return Self { re: self.re * rhs.re, eps: self.re * rhs.eps + self.eps * rhs.re };
+    }
+}
+
+impl Div for Dual {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        return Self { re: self.re / rhs.re, eps: (self.eps * rhs.re - self.re * rhs.eps) / (rhs.re * rhs.re) };
+    }
+}
+
+impl Neg for Dual {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        return Self { re: -self.re, eps: -self.eps };
+    }
+}
+
+fn dot(a: &[Dual], b: &[Dual]) -> Dual {
+    return a.iter().zip(b).fold(Dual::constant(0.0), |sum, (x, y)| sum + *x * *y);
+}
+
+// Modified Gram-Schmidt over dual-valued columns, the same algorithm as `Vector::gram_schmidt`.
+pub fn gram_schmidt_dual(columns: &mut [Vec<Dual>]) {
+    for index in 0..columns.len() {
+        let (done, rest) = columns.split_at_mut(index);
+        let column = &mut rest[0];
+        for q in done.iter() {
+            let d = dot(column, q);
+            for (c, qi) in column.iter_mut().zip(q) {
+                *c = *c - d * *qi;
+            }
+        }
+        let norm = dot(column, column).sqrt();
+        column.iter_mut().for_each(|c| *c = *c / norm);
+    }
+}
+
+// Orthonormalizes `columns` and returns the derivative of the result in the direction `tangent`, which must have the
+// same shape as `columns`.
+pub fn gram_schmidt_jvp(columns: &mut [Vec<f64>], tangent: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let mut duals: Vec<Vec<Dual>> = columns.iter().zip(tangent)
+        .map(|(c, t)| c.iter().zip(t).map(|(re, eps)| Dual::new(*re, *eps)).collect())
+        .collect();
+    gram_schmidt_dual(&mut duals);
+    for (column, dual) in columns.iter_mut().zip(&duals) {
+        for (c, d) in column.iter_mut().zip(dual) {
+            *c = d.re;
+        }
+    }
+    return duals.iter().map(|dual| dual.iter().map(|d| d.eps).collect()).collect();
+}
+
+#[cfg(test)]
+mod dual_test {
+    use crate::{gram_schmidt_jvp, Dual};
+
+    #[test]
+    fn arithmetic() {
+        let x = Dual::new(4.0, 1.0);
+        assert_eq!(x * x, Dual::new(16.0, 8.0));
+        assert_eq!(x.sqrt(), Dual::new(2.0, 0.25));
+        assert_eq!(Dual::constant(1.0) / x, Dual::new(0.25, -1.0 / 16.0));
+    }
+
+    #[test]
+    fn matches_finite_differences() {
+        let input = vec![vec![1.0, 2.0, 0.5], vec![0.3, -1.0, 2.0], vec![1.0, 1.0, 1.0]];
+        let tangent = vec![vec![0.2, -0.1, 0.4], vec![1.0, 0.0, -0.3], vec![0.0, 0.5, 0.1]];
+
+        let mut q = input.clone();
+        let dq = gram_schmidt_jvp(&mut q, &tangent);
+
+        let h = 1e-7;
+        let mut shifted: Vec<Vec<f64>> = input.iter().zip(&tangent)
+            .map(|(c, t)| c.iter().zip(t).map(|(x, dx)| x + h * dx).collect())
+            .collect();
+        let zero = vec![vec![0.0; 3]; 3];
+        gram_schmidt_jvp(&mut shifted, &zero);
+        for j in 0..3 {
+            for i in 0..3 {
+                let finite_difference = (shifted[j][i] - q[j][i]) / h;
+                assert!((finite_difference - dq[j][i]).abs() < 1e-5);
+            }
+        }
+    }
+}
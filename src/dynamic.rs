@@ -0,0 +1,272 @@
+use crate::{
+    storage_axpy, storage_dot, storage_normalize, Error, Storage, Vector, Vector10, Vector11, Vector12,
+    Vector13, Vector14, Vector15, Vector16, Vector2, Vector3, Vector4, Vector5, Vector6, Vector7, Vector8, Vector9,
+};
+
+// Object-safe view of a vector whose dimension is only known at runtime, e.g. read from a config file. Implemented
+// for every `Vector` with contiguous storage and for `Vec<f64>`, so `Box<dyn DynVector>` can hold any of them.
+pub trait DynVector {
+    fn dim(&self) -> usize;
+
+    fn components(&self) -> &[f64];
+
+    fn components_mut(&mut self) -> &mut [f64];
+
+    fn clone_box(&self) -> Box<dyn DynVector>;
+}
+
+impl<V: Vector + Storage + 'static> DynVector for V {
+    fn dim(&self) -> usize {
+        return V::DIM;
+    }
+
+    fn components(&self) -> &[f64] {
+        return self.as_slice();
+    }
+
+    fn components_mut(&mut self) -> &mut [f64] {
+        return self.as_mut_slice();
+    }
+
+    fn clone_box(&self) -> Box<dyn DynVector> {
+        return Box::new(self.clone());
+    }
+}
+
+impl DynVector for Vec<f64> {
+    fn dim(&self) -> usize {
+        return self.len();
+    }
+
+    fn components(&self) -> &[f64] {
+        return self;
+    }
+
+    fn components_mut(&mut self) -> &mut [f64] {
+        return self;
+    }
+
+    fn clone_box(&self) -> Box<dyn DynVector> {
+        return Box::new(self.clone());
+    }
+}
+
+impl Clone for Box<dyn DynVector> {
+    fn clone(&self) -> Self {
+        return self.clone_box();
+    }
+}
+
+// Lets boxed vectors go through the storage kernels and the solvers built on them, e.g. `lsqr`.
+impl Storage for Box<dyn DynVector> {
+    fn as_slice(&self) -> &[f64] {
+        return self.components();
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        return self.components_mut();
+    }
+}
+
+impl std::fmt::Debug for dyn DynVector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.debug_list().entries(self.components()).finish();
+    }
+}
+
+// Dimensions up to which `SmallVector` keeps its components inline.
+pub const SMALL_VECTOR_INLINE: usize = 8;
+
+// Owned vector of runtime dimension that stores up to `SMALL_VECTOR_INLINE` components inline, without a heap
+// allocation, and falls back to a `Vec<f64>` above that. Works with the storage kernels and as a `DynVector`.
+#[derive(Clone)]
+pub struct SmallVector {
+    repr: SmallRepr,
+}
+
+#[derive(Clone)]
+enum SmallRepr {
+    Inline { len: usize, components: [f64; SMALL_VECTOR_INLINE] },
+    Heap(Vec<f64>),
+}
+
+impl SmallVector {
+    pub fn zeros(dim: usize) -> Self {
+        let repr = if dim <= SMALL_VECTOR_INLINE {
+            SmallRepr::Inline { len: dim, components: [0.0; SMALL_VECTOR_INLINE] }
+        } else {
+            SmallRepr::Heap(vec![0.0; dim])
+        };
+        return Self { repr };
+    }
+
+    pub fn from_slice(components: &[f64]) -> Self {
+        let mut v = Self::zeros(components.len());
+        v.as_mut_slice().copy_from_slice(components);
+        return v;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.as_slice().len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    pub fn is_inline(&self) -> bool {
+        return matches!(self.repr, SmallRepr::Inline { .. });
+    }
+}
+
+impl Storage for SmallVector {
+    fn as_slice(&self) -> &[f64] {
+        return match &self.repr {
+            SmallRepr::Inline { len, components } => &components[..*len],
+            SmallRepr::Heap(components) => components,
+        };
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        return match &mut self.repr {
+            SmallRepr::Inline { len, components } => &mut components[..*len],
+            SmallRepr::Heap(components) => components,
+        };
+    }
+}
+
+impl DynVector for SmallVector {
+    fn dim(&self) -> usize {
+        return self.len();
+    }
+
+    fn components(&self) -> &[f64] {
+        return self.as_slice();
+    }
+
+    fn components_mut(&mut self) -> &mut [f64] {
+        return self.as_mut_slice();
+    }
+
+    fn clone_box(&self) -> Box<dyn DynVector> {
+        return Box::new(self.clone());
+    }
+}
+
+impl PartialEq for SmallVector {
+    fn eq(&self, other: &Self) -> bool {
+        return self.as_slice() == other.as_slice();
+    }
+}
+
+impl std::fmt::Debug for SmallVector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.debug_list().entries(self.as_slice()).finish();
+    }
+}
+
+// Boxes `components` as the fixed-size vector of that dimension if there is one, and as a `Vec<f64>` otherwise.
+pub fn dyn_vector(components: &[f64]) -> Box<dyn DynVector> {
+    fn boxed<V: Vector + Storage + 'static>(components: &[f64]) -> Box<dyn DynVector> {
+        let mut v = V::zero();
+        v.as_mut_slice().copy_from_slice(components);
+        return Box::new(v);
+    }
+
+    return match components.len() {
+        2 => boxed::<Vector2>(components),
+        3 => boxed::<Vector3>(components),
+        4 => boxed::<Vector4>(components),
+        5 => boxed::<Vector5>(components),
+        6 => boxed::<Vector6>(components),
+        7 => boxed::<Vector7>(components),
+        8 => boxed::<Vector8>(components),
+        9 => boxed::<Vector9>(components),
+        10 => boxed::<Vector10>(components),
+        11 => boxed::<Vector11>(components),
+        12 => boxed::<Vector12>(components),
+        13 => boxed::<Vector13>(components),
+        14 => boxed::<Vector14>(components),
+        15 => boxed::<Vector15>(components),
+        16 => boxed::<Vector16>(components),
+        _ => Box::new(components.to_vec()),
+    };
+}
+
+// `Vector::try_gram_schmidt` for boxed vectors. All vectors must have the dimension of the first one; the basis is left
+// untouched on error.
+pub fn gram_schmidt_dyn(basis: &mut [Box<dyn DynVector>]) -> Result<(), Error> {
+    if let Some(first) = basis.first() {
+        let dim = first.dim();
+        for v in basis.iter() {
+            if v.dim() != dim {
+                return Err(Error::DimensionMismatch { expected: dim, found: v.dim() });
+            }
+        }
+    }
+    for (vector, v) in basis.iter().enumerate() {
+        if let Some(component) = v.components().iter().position(|c| !c.is_finite()) {
+            return Err(Error::NonFinite { vector, component });
+        }
+    }
+
+    for index in 0..basis.len() {
+        let (a, previous) = basis[..=index].split_last_mut().unwrap();
+        let a = a.components_mut();
+        for b in previous.iter() {
+            let dot = storage_dot(a, b.components());
+            storage_axpy(-dot, b.components(), a);
+        }
+        storage_normalize(a);
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod dyn_vector_test {
+    use crate::{
+        dyn_vector, gram_schmidt_dyn, storage_axpy, storage_dot, DynVector, Error, SmallVector, Vector3, VectorPool,
+    };
+
+    #[test]
+    fn runtime_dimension() {
+        for dim in [3, 20] {
+            let mut basis: Vec<Box<dyn DynVector>> = (0..dim)
+                .map(|i| dyn_vector(&(0..dim).map(|j| if j <= i { 1.0 } else { 0.0 }).collect::<Vec<_>>()))
+                .collect();
+            gram_schmidt_dyn(&mut basis).unwrap();
+            for (i, v) in basis.iter().enumerate() {
+                assert_eq!(v.dim(), dim);
+                for (j, c) in v.components().iter().enumerate() {
+                    assert!((c - if i == j { 1.0 } else { 0.0 }).abs() < 1e-12);
+                }
+            }
+        }
+        let v: Box<dyn DynVector> = Box::new(Vector3::E2);
+        assert_eq!(v.clone().components(), [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn mixed_dimensions_are_rejected() {
+        let mut basis = vec![dyn_vector(&[1.0, 0.0, 0.0]), dyn_vector(&[1.0, 1.0])];
+        assert_eq!(gram_schmidt_dyn(&mut basis), Err(Error::DimensionMismatch { expected: 3, found: 2 }));
+        let mut basis = vec![dyn_vector(&[1.0, f64::NAN])];
+        assert_eq!(gram_schmidt_dyn(&mut basis), Err(Error::NonFinite { vector: 0, component: 1 }));
+    }
+
+    #[test]
+    fn small_vectors_stay_inline() {
+        let mut a = SmallVector::from_slice(&[1.0, 2.0, 2.0]);
+        let b = SmallVector::from_slice(&[0.0; 8]);
+        let large = SmallVector::zeros(9);
+        assert!(a.is_inline() && b.is_inline() && !large.is_inline());
+        assert_eq!((a.len(), large.len()), (3, 9));
+        assert_eq!(storage_dot(&a, &a), 9.0);
+        storage_axpy(-1.0, &a.clone(), &mut a);
+        assert_eq!(a, SmallVector::zeros(3));
+
+        let pool = VectorPool::new(|| SmallVector::zeros(4));
+        let v = pool.acquire();
+        assert_eq!((v.dim(), format!("{:?}", *v)), (4, "[0.0, 0.0, 0.0, 0.0]".to_string()));
+    }
+}
@@ -0,0 +1,271 @@
+use crate::{Error, Matrix, Vector};
+
+// Sweep limit of the Jacobi solver. Cyclic Jacobi converges quadratically, so this is only reached for non-finite
+// input.
+const MAX_SWEEPS: usize = 100;
+
+// Eigen-decomposition of a symmetric matrix by the cyclic Jacobi method. Returns the eigenvalues in ascending order
+// and the matching eigenvectors as the columns of the second matrix. Sweeps until the off-diagonal mass is below
+// `(ε ‖A‖_F)²`, relative to the matrix so that the scale of the entries does not matter, and fails with
+// `NotConverged` if that takes more than `MAX_SWEEPS` sweeps.
+pub(crate) fn symmetric_eigen(a: &Matrix) -> Result<(Vec<f64>, Matrix), Error> {
+    let n = a.rows();
+    let mut a = a.clone();
+    let mut v = Matrix::identity(n);
+    // Rotations preserve the Frobenius norm.
+    let norm_squared: f64 = (0..n).flat_map(|j| a.column(j).to_vec()).map(|x| x * x).sum();
+    let threshold = f64::EPSILON * f64::EPSILON * norm_squared;
+    let mut sweeps = 0;
+    loop {
+        let mut off = 0.0;
+        for j in 0..n {
+            for i in 0..j {
+                off += a[(i, j)] * a[(i, j)];
+            }
+        }
+        if off <= threshold {
+            break;
+        }
+        if sweeps == MAX_SWEEPS {
+            return Err(Error::NotConverged { iterations: sweeps });
+        }
+        sweeps += 1;
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[(p, q)] == 0.0 {
+                    continue;
+                }
+                let theta = (a[(q, q)] - a[(p, p)]) / (2.0 * a[(p, q)]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+                for k in 0..n {
+                    let (akp, akq) = (a[(k, p)], a[(k, q)]);
+                    a[(k, p)] = c * akp - s * akq;
+                    a[(k, q)] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[(p, k)], a[(q, k)]);
+                    a[(p, k)] = c * apk - s * aqk;
+                    a[(q, k)] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[(k, p)], v[(k, q)]);
+                    v[(k, p)] = c * vkp - s * vkq;
+                    v[(k, q)] = s * vkp + c * vkq;
+                }
+                // Zero in exact arithmetic; storing the rounding residue would keep the sweep from converging.
+                a[(p, q)] = 0.0;
+                a[(q, p)] = 0.0;
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| a[(i, i)].total_cmp(&a[(j, j)]));
+    let values = order.iter().map(|&i| a[(i, i)]).collect();
+    let columns: Vec<Vec<f64>> = order.iter().map(|&i| v.column(i).to_vec()).collect();
+    return Ok((values, Matrix::from_columns(&columns)));
+}
+
+// Rayleigh-Ritz projection of the symmetric operator `op` onto the span of the orthonormal `basis`: forms
+// `H = Qᵀ op(Q)`, diagonalizes it and lifts its eigenvectors back. Returns the Ritz values in ascending order together
+// with the matching Ritz vectors. `H` is symmetrized before solving, so a slightly non-symmetric `op` is tolerated.
+// Fails only if the eigensolver does not converge, i.e. for non-finite input.
+pub fn rayleigh_ritz<V: Vector, F: Fn(&V) -> V>(op: F, basis: &[V]) -> Result<(Vec<f64>, Vec<V>), Error> {
+    let images: Vec<V> = basis.iter().map(&op).collect();
+    let (values, vectors, _) = ritz_pairs(basis, &images)?;
+    return Ok((values, vectors));
+}
+
+// Ritz values, Ritz vectors and the images of the Ritz vectors under `op`.
+type RitzPairs<V> = (Vec<f64>, Vec<V>, Vec<V>);
+
+// `rayleigh_ritz` given the images `op(q)` of the basis vectors. Also returns the images of the Ritz vectors, which
+// by linearity are the same combinations of `images`, so callers need not apply `op` again.
+fn ritz_pairs<V: Vector>(basis: &[V], images: &[V]) -> Result<RitzPairs<V>, Error> {
+    let k = basis.len();
+    let mut h = Matrix::zeros(k, k);
+    for i in 0..k {
+        for j in 0..k {
+            h[(i, j)] = V::dot_product(&basis[i], &images[j]);
+        }
+    }
+    let mut symmetric = Matrix::zeros(k, k);
+    for i in 0..k {
+        for j in 0..k {
+            symmetric[(i, j)] = 0.5 * (h[(i, j)] + h[(j, i)]);
+        }
+    }
+
+    let (values, vectors) = symmetric_eigen(&symmetric)?;
+    let combine = |columns: &[V], j: usize| {
+        let mut combination = V::zero();
+        for (i, q) in columns.iter().enumerate() {
+            combination.axpy(vectors[(i, j)], q);
+        }
+        return combination;
+    };
+    let ritz_vectors = (0..k).map(|j| combine(basis, j)).collect();
+    let ritz_images = (0..k).map(|j| combine(images, j)).collect();
+    return Ok((values, ritz_vectors, ritz_images));
+}
+
+// Löwdin (symmetric) orthonormalization: replaces the linearly independent `vectors` by `(W Wᵀ)^(-1/2) W`, the
+// orthonormal set closest to them in the least-squares sense. Unlike Gram-Schmidt the result does not depend on the
+// order of the vectors. Fails, leaving `vectors` unchanged, if the eigensolver does not converge.
+pub fn lowdin_orthonormalize<V: Vector>(vectors: &mut [V]) -> Result<(), Error> {
+    let k = vectors.len();
+    let mut gram = Matrix::zeros(k, k);
+    for i in 0..k {
+        for j in 0..k {
+            gram[(i, j)] = V::dot_product(&vectors[i], &vectors[j]);
+        }
+    }
+    let (values, eigenvectors) = symmetric_eigen(&gram)?;
+    let mut inverse_root = Matrix::zeros(k, k);
+    for (m, value) in values.iter().enumerate() {
+        let scale = 1.0 / value.sqrt();
+        for j in 0..k {
+            for i in 0..k {
+                inverse_root[(i, j)] += scale * eigenvectors[(i, m)] * eigenvectors[(j, m)];
+            }
+        }
+    }
+    let original = vectors.to_vec();
+    for (i, vector) in vectors.iter_mut().enumerate() {
+        *vector = V::zero();
+        for (j, w) in original.iter().enumerate() {
+            vector.axpy(inverse_root[(i, j)], w);
+        }
+    }
+    return Ok(());
+}
+
+// Iteration limit of the convenience solvers below.
+const MAX_ITERATIONS: usize = 10_000;
+
+// Deterministic, well-spread start vector for the iterative solvers (golden-ratio sequence per component).
+pub(crate) fn start_vector<V: Vector>(index: usize) -> V {
+    let mut v = V::zero();
+    for i in 0..V::DIM {
+        v[i] = ((i + 1 + index * V::DIM) as f64 * 0.618_033_988_749_895).fract() - 0.5;
+    }
+    return v;
+}
+
+// Dominant eigenpair of the symmetric operator `op`. Iterates until the residual `|op(v) - λv|` drops below `tol`;
+// fails with `NotConverged` if that does not happen within the iteration limit, e.g. because the two eigenvalues of
+// largest magnitude are `λ` and `-λ`.
+pub fn power_iteration<V: Vector, F: Fn(&V) -> V>(op: F, tol: f64) -> Result<(f64, V), Error> {
+    let (values, vectors) = subspace_iteration(op, 1, tol)?;
+    return Ok((values[0], vectors.into_iter().next().unwrap()));
+}
+
+// The `k` eigenpairs of largest magnitude of the symmetric operator `op`, ordered by decreasing magnitude. Every
+// iteration applies `op` once per block vector, extracts the Ritz pairs from those images and continues with the
+// images of the Ritz vectors, re-orthonormalized with Gram-Schmidt. Stops once every residual `|op(v) - λv|` is below
+// `tol`, and fails with `NotConverged` if the iteration limit is hit first.
+pub fn subspace_iteration<V: Vector, F: Fn(&V) -> V>(op: F, k: usize, tol: f64) -> Result<(Vec<f64>, Vec<V>), Error> {
+    assert!(k <= V::DIM, "cannot compute more eigenpairs than the dimension");
+    let mut block: Vec<V> = (0..k).map(start_vector).collect();
+    V::gram_schmidt(&mut block);
+    for _ in 0..MAX_ITERATIONS {
+        let images: Vec<V> = block.iter().map(&op).collect();
+        let (values, vectors, ritz_images) = ritz_pairs(&block, &images)?;
+        let converged = values.iter().zip(&vectors).zip(&ritz_images)
+            .all(|((value, vector), image)| (image.clone() - vector.clone() * *value).length() < tol);
+        if converged {
+            let mut order: Vec<usize> = (0..values.len()).collect();
+            order.sort_by(|&i, &j| values[j].abs().total_cmp(&values[i].abs()));
+            let values = order.iter().map(|&i| values[i]).collect();
+            return Ok((values, order.iter().map(|&i| vectors[i].clone()).collect()));
+        }
+        block = ritz_images;
+        V::gram_schmidt(&mut block);
+    }
+    return Err(Error::NotConverged { iterations: MAX_ITERATIONS });
+}
+
+#[cfg(test)]
+mod eigen_test {
+    use crate::eigen::symmetric_eigen;
+    use crate::{
+        arnoldi, lowdin_orthonormalize, power_iteration, rayleigh_ritz, subspace_iteration, Error, Matrix, Vector,
+        Vector2, Vector4,
+    };
+
+    fn op(v: &Vector4) -> Vector4 {
+        return Vector4::new([2.0 * v[0] + v[1], v[0] + 2.0 * v[1], 5.0 * v[2], -v[3]]);
+    }
+
+    #[test]
+    fn recovers_eigenpairs_of_invariant_subspace() {
+        let (basis, _) = arnoldi(op, &Vector4::new([1.0, 1.0, 1.0, 1.0]), 4);
+        let (values, vectors) = rayleigh_ritz(op, &basis).unwrap();
+        let expected = [-1.0, 3.0, 5.0];
+        assert_eq!(values.len(), expected.len());
+        for ((value, vector), expected) in values.iter().zip(&vectors).zip(&expected) {
+            assert!((value - expected).abs() < 1e-10);
+            assert!((op(vector) - *vector * *value).length() < 1e-10);
+            assert!((vector.length() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn dominant_eigenpairs() {
+        let (value, vector) = power_iteration(op, 1e-10).unwrap();
+        assert!((value - 5.0).abs() < 1e-10);
+        assert!((vector[2].abs() - 1.0).abs() < 1e-10);
+
+        let (values, vectors) = subspace_iteration(op, 2, 1e-10).unwrap();
+        assert!((values[0] - 5.0).abs() < 1e-10 && (values[1] - 3.0).abs() < 1e-10);
+        assert!(Vector4::dot_product(&vectors[0], &vectors[1]).abs() < 1e-10);
+
+        // `1` and `-1` have the same magnitude, so the iteration cannot settle on either.
+        let reflection = |v: &Vector2| Vector2::new([v[0], -v[1]]);
+        assert_eq!(power_iteration(reflection, 1e-10).map(|_| ()), Err(Error::NotConverged { iterations: 10_000 }));
+    }
+
+    #[test]
+    fn lowdin_is_order_independent() {
+        let a = Vector4::new([1.0, 0.2, 0.0, 0.1]);
+        let b = Vector4::new([0.3, 1.0, 0.5, 0.0]);
+        let mut forward = [a, b];
+        let mut backward = [b, a];
+        lowdin_orthonormalize(&mut forward).unwrap();
+        lowdin_orthonormalize(&mut backward).unwrap();
+        assert!(Vector4::dot_product(&forward[0], &forward[1]).abs() < 1e-12);
+        assert!((forward[0].length() - 1.0).abs() < 1e-12);
+        assert!((forward[0] - backward[1]).length() < 1e-12);
+    }
+
+    #[test]
+    fn convergence_does_not_depend_on_scale() {
+        let a = Matrix::from_rows(&[vec![4.0, 1.0, -2.0], vec![1.0, 2.0, 0.5], vec![-2.0, 0.5, 3.0]]);
+        let (values, _) = symmetric_eigen(&a).unwrap();
+        for scale in [1e-12, 1e-150, 1e150] {
+            let mut scaled = a.clone();
+            (0..3).for_each(|j| scaled.column_mut(j).iter_mut().for_each(|x| *x *= scale));
+            let (scaled_values, vectors) = symmetric_eigen(&scaled).unwrap();
+            for (value, scaled_value) in values.iter().zip(&scaled_values) {
+                assert!((scaled_value / scale - value).abs() < 1e-12 * value.abs().max(1.0));
+            }
+            // `A V = V Λ` column by column.
+            let av = &scaled * &vectors;
+            for j in 0..3 {
+                for i in 0..3 {
+                    assert!((av[(i, j)] - scaled_values[j] * vectors[(i, j)]).abs() < 1e-13 * scale);
+                }
+            }
+        }
+
+        let mut tiny = [Vector4::new([1.0, 0.2, 0.0, 0.1]), Vector4::new([0.3, 1.0, 0.5, 0.0])].map(|v| v * 1e-16);
+        lowdin_orthonormalize(&mut tiny).unwrap();
+        assert!(Vector4::dot_product(&tiny[0], &tiny[1]).abs() < 1e-12);
+        assert!((tiny[0].length() - 1.0).abs() < 1e-12);
+
+        let nan = Matrix::from_rows(&[vec![1.0, f64::NAN], vec![f64::NAN, 1.0]]);
+        assert!(matches!(symmetric_eigen(&nan), Err(Error::NotConverged { .. })));
+    }
+}
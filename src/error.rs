@@ -0,0 +1,101 @@
+use std::fmt;
+
+use crate::Vector;
+
+// Error type shared by all fallible functions of the crate. New variants may be added in minor releases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    // Component `component` of input vector `vector` is NaN or infinite.
+    NonFinite { vector: usize, component: usize },
+    // Input vector `vector` is zero or linearly dependent on the vectors before it.
+    ZeroVector { vector: usize },
+    // An argument had `found` rows, columns or components where `expected` were required.
+    DimensionMismatch { expected: usize, found: usize },
+    // An argument that needs at least one element was empty.
+    Empty,
+    // Malformed JSON document, with the 1-based position where parsing failed.
+    InvalidJson { line: usize, column: usize },
+    // Binary data that does not decode to the requested value.
+    InvalidEncoding,
+    // The computation was stopped through a `CancellationToken`.
+    Cancelled,
+    // Vector `vector` of a supposedly orthonormal set is not of unit length or not orthogonal to a later vector.
+    NotOrthonormal { vector: usize },
+    // An intermediate result for input vector `vector` left the range of a fixed-size number format.
+    Overflow { vector: usize },
+    // An iterative method stopped after `iterations` steps without meeting its convergence criterion.
+    NotConverged { iterations: usize },
+}
+
+// Name of `Error` from before it covered the whole crate.
+pub type GramSchmidtError = Error;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return match self {
+            Self::NonFinite { vector, component } => {
+                write!(f, "component {} of vector {} is not finite", component, vector)
+            }
+            Self::ZeroVector { vector } => write!(f, "vector {} is numerically zero after orthogonalization", vector),
+            Self::DimensionMismatch { expected, found } => {
+                write!(f, "dimension mismatch: expected {}, found {}", expected, found)
+            }
+            Self::Empty => write!(f, "input must not be empty"),
+            Self::InvalidJson { line, column } => write!(f, "invalid JSON at line {}, column {}", line, column),
+            Self::InvalidEncoding => write!(f, "invalid binary encoding"),
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::NotOrthonormal { vector } => write!(f, "vector {} is not orthonormal to the others", vector),
+            Self::Overflow { vector } => write!(f, "arithmetic overflow while processing vector {}", vector),
+            Self::NotConverged { iterations } => write!(f, "no convergence after {} iterations", iterations),
+        };
+    }
+}
+
+impl std::error::Error for Error {}
+
+// Numerical warning raised while orthonormalizing. Unlike `Error` it does not stop the run.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Warning {
+    // The residual of vector `vector` after the projections was only `residual_ratio` times its original norm, below
+    // `sqrt(f64::EPSILON)`. Most of its digits cancelled, so it is likely not orthogonal to the earlier vectors.
+    LossOfOrthogonality { vector: usize, residual_ratio: f64 },
+}
+
+// Reports the first NaN or infinite component, scanning the vectors in order.
+pub fn validate_finite<V: Vector>(vectors: &[V]) -> Result<(), Error> {
+    for (vector, v) in vectors.iter().enumerate() {
+        if let Some(component) = (0..V::DIM).find(|&i| !v[i].is_finite()) {
+            return Err(Error::NonFinite { vector, component });
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod error_test {
+    use crate::{validate_finite, Error, Vector, Vector3, Warning};
+
+    #[test]
+    fn rejects_non_finite_input() {
+        let mut basis = vec![Vector3::E0, Vector3::new([1.0, f64::INFINITY, 0.0]), Vector3::new([f64::NAN; 3])];
+        let error = Error::NonFinite { vector: 1, component: 1 };
+        assert_eq!(validate_finite(&basis), Err(error));
+        assert_eq!(Vector3::try_gram_schmidt(&mut basis), Err(error));
+        // Nothing is touched when validation fails.
+        assert_eq!(basis[0], Vector3::E0);
+        assert_eq!(error.to_string(), "component 1 of vector 1 is not finite");
+        assert_eq!(Vector3::try_gram_schmidt(&mut basis[..1]), Ok(()));
+    }
+
+    #[test]
+    fn warns_on_cancellation() {
+        let mut basis = vec![Vector3::E0, Vector3::new([1.0, 1e-9, 0.0]), Vector3::E2];
+        let warnings = Vector3::gram_schmidt_collect_warnings(&mut basis);
+        assert_eq!(warnings.len(), 1);
+        let Warning::LossOfOrthogonality { vector, residual_ratio } = warnings[0];
+        assert_eq!(vector, 1);
+        assert!((residual_ratio - 1e-9).abs() < 1e-15);
+        assert!((basis[1][1] - 1.0).abs() < 1e-6);
+    }
+}
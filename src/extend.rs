@@ -0,0 +1,60 @@
+use crate::Vector;
+
+// Orthonormalizes `new` against the orthonormal vectors in `existing` and among themselves, borrowing `existing`
+// read-only, e.g. from a shared or memory-mapped basis. Performs the same operations as `Vector::gram_schmidt` on
+// the concatenation, so the result matches running it on `existing` followed by `new`.
+pub fn gram_schmidt_extend<V: Vector>(existing: &[V], new: &mut [V]) {
+    for index in 0..new.len() {
+        let (a, previous) = new[..=index].split_last_mut().unwrap();
+        for b in existing.iter().chain(previous.iter()) {
+            let dot = V::dot_product(a, b);
+            a.axpy(-dot, b);
+        }
+        a.normalize();
+    }
+}
+
+// Resumes `Vector::gram_schmidt` at index `start`: the first `start` vectors are taken to be orthonormal already and
+// are left untouched, the rest are orthonormalized against them and among themselves. Useful after appending vectors
+// to a finished basis or editing only its tail.
+pub fn gram_schmidt_resume<V: Vector>(basis: &mut [V], start: usize) {
+    assert!(start <= basis.len(), "start index out of range");
+    let (frozen, rest) = basis.split_at_mut(start);
+    gram_schmidt_extend(frozen, rest);
+}
+
+#[cfg(test)]
+mod extend_test {
+    use crate::{gram_schmidt_extend, gram_schmidt_resume, Vector4};
+
+    #[test]
+    fn matches_full_run() {
+        let input = [
+            Vector4::new([1.0, 1.0, 1.0, 1.0]),
+            Vector4::new([0.0, 1.0, 0.0, 1.0]),
+            Vector4::new([0.0, 0.0, 1.0, 1.0]),
+            Vector4::new([0.0, 0.0, 0.0, 1.0]),
+        ];
+        let mut full = input.to_vec();
+        Vector4::gram_schmidt(&mut full);
+
+        let existing = full[..2].to_vec();
+        let mut new = input[2..].to_vec();
+        gram_schmidt_extend(&existing, &mut new);
+        assert_eq!(new, full[2..]);
+        assert_eq!(new[1], Vector4::new([0.5, -0.5, -0.5, 0.5]));
+    }
+
+    #[test]
+    fn resumes_after_append() {
+        let mut basis = vec![Vector4::new([1.0, 1.0, 1.0, 1.0]), Vector4::new([0.0, 1.0, 0.0, 1.0])];
+        Vector4::gram_schmidt(&mut basis);
+        basis.push(Vector4::new([0.0, 0.0, 1.0, 1.0]));
+        let frozen = basis[..2].to_vec();
+        gram_schmidt_resume(&mut basis, 2);
+        assert_eq!(basis[..2], frozen);
+        assert_eq!(basis[2], Vector4::new([-0.5, -0.5, 0.5, 0.5]));
+        gram_schmidt_resume(&mut basis, 3);
+        assert_eq!(basis[2], Vector4::new([-0.5, -0.5, 0.5, 0.5]));
+    }
+}
@@ -0,0 +1,91 @@
+use fixed::types::I16F16;
+
+use crate::Error;
+
+const FRAC_BITS: u32 = 16;
+const HALF: i64 = 1 << (FRAC_BITS - 1);
+// A residual shorter than 2^-RANK_BITS of the original column has lost most of its fraction bits to cancellation.
+const RANK_BITS: u32 = 8;
+
+// Exact inner product of two Q16.16 slices as Q32.32 bits, or `None` if the sum leaves the i64 range. That cannot
+// happen for components with magnitude below 2^7 and up to 2^16 components.
+fn dot_bits(a: &[I16F16], b: &[I16F16]) -> Option<i64> {
+    return a.iter().zip(b).try_fold(0i64, |sum, (x, y)| sum.checked_add(x.to_bits() as i64 * y.to_bits() as i64));
+}
+
+// Rounds Q32.32 bits to the nearest Q16.16 value.
+fn round_to_narrow(wide: i64) -> Option<i64> {
+    return Some(wide.checked_add(HALF)? >> FRAC_BITS);
+}
+
+// Modified Gram-Schmidt in Q16.16 fixed point without any floating-point operation. Inner products are accumulated
+// exactly in double width and rounded once; every update is then a single rounded product, so the error per
+// component stays within a couple of ulps (2^-16) for well-conditioned inputs such as IMU axes. Fails with
+// `ZeroVector` for a column that is zero or within rounding of zero after the projections and with `Overflow` if an
+// intermediate value does not fit the format; the columns before the offending one are orthonormal by then.
+pub fn gram_schmidt_fixed(columns: &mut [Vec<I16F16>]) -> Result<(), Error> {
+    for index in 0..columns.len() {
+        let overflow = Error::Overflow { vector: index };
+        let (done, rest) = columns.split_at_mut(index);
+        let column = &mut rest[0];
+        let original = dot_bits(column, column).ok_or(overflow)?.unsigned_abs().isqrt() as i64;
+        for q in done.iter() {
+            let dot = round_to_narrow(dot_bits(column, q).ok_or(overflow)?).ok_or(overflow)?;
+            for (c, qi) in column.iter_mut().zip(q) {
+                let update = dot.checked_mul(qi.to_bits() as i64).and_then(round_to_narrow).ok_or(overflow)?;
+                let bits = (c.to_bits() as i64).checked_sub(update).ok_or(overflow)?;
+                *c = I16F16::from_bits(i32::try_from(bits).map_err(|_| overflow)?);
+            }
+        }
+        let norm = dot_bits(column, column).ok_or(overflow)?.unsigned_abs().isqrt() as i64;
+        // Besides the relative test, each projection leaves a couple of ulps of rounding in every component.
+        if norm <= (original >> RANK_BITS).max((2 * index * column.len()) as i64) {
+            return Err(Error::ZeroVector { vector: index });
+        }
+        for c in column.iter_mut() {
+            let scaled = ((c.to_bits() as i64) << FRAC_BITS) + norm / 2 * (c.to_bits() as i64).signum();
+            *c = I16F16::from_bits(i32::try_from(scaled / norm).map_err(|_| overflow)?);
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod fixed_point_test {
+    use fixed::types::I16F16;
+
+    use crate::fixed_point::gram_schmidt_fixed;
+    use crate::Error;
+
+    #[test]
+    fn orthonormalizes_sensor_frame() {
+        let mut frame: Vec<Vec<I16F16>> = [
+            [0.12, -0.05, 9.81],
+            [22.0, 4.5, -40.0],
+            [1.0, 1.0, 1.0],
+        ].iter().map(|c| c.iter().map(|v| I16F16::from_num(*v)).collect()).collect();
+        gram_schmidt_fixed(&mut frame).unwrap();
+
+        for i in 0..3 {
+            for j in 0..3 {
+                let dot: f64 = frame[i].iter().zip(&frame[j]).map(|(a, b)| a.to_num::<f64>() * b.to_num::<f64>()).sum();
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((dot - expected).abs() < 1e-3);
+            }
+        }
+        assert!(frame[0][2] > I16F16::from_num(0.99));
+    }
+
+    #[test]
+    fn reports_dependent_columns_and_overflow() {
+        let to_fixed = |columns: &[[f64; 3]]| -> Vec<Vec<I16F16>> {
+            return columns.iter().map(|c| c.iter().map(|v| I16F16::from_num(*v)).collect()).collect();
+        };
+        let mut dependent = to_fixed(&[[1.0, 2.0, 0.0], [0.0, 0.0, 0.0]]);
+        assert_eq!(gram_schmidt_fixed(&mut dependent), Err(Error::ZeroVector { vector: 1 }));
+        let mut repeated = to_fixed(&[[1.0, 2.0, 3.0], [0.5, 0.0, 0.0], [1.0, 2.0, 3.0]]);
+        assert_eq!(gram_schmidt_fixed(&mut repeated), Err(Error::ZeroVector { vector: 2 }));
+        let mut huge = to_fixed(&[[1.0, 0.0, 0.0], [30000.0, 30000.0, 30000.0]]);
+        assert_eq!(gram_schmidt_fixed(&mut huge), Err(Error::Overflow { vector: 1 }));
+    }
+}
@@ -0,0 +1,110 @@
+use std::fmt;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    ToNearest,
+    TowardZero,
+    Upward,
+    Downward,
+}
+
+// Floating-point state of the current thread as seen by the kernels of this crate. Anything but the defaults
+// (round-to-nearest, gradual underflow) changes the computed bases, and whether `a * b + c` is fused changes the last
+// bits between targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FpEnvironment {
+    pub rounding: RoundingMode,
+    // Subnormal results are flushed to zero (FTZ/DAZ on x86, FZ on ARM).
+    pub flushes_denormals: bool,
+    // The kernels evaluate `a * b + c` with a single rounding.
+    pub fused_multiply_add: bool,
+}
+
+impl FpEnvironment {
+    // Probes the environment with a few operations the optimizer cannot fold at compile time.
+    pub fn detect() -> Self {
+        return Self::probe(strict_fp());
+    }
+
+    fn probe(strict: bool) -> Self {
+        let one = black_box(1.0f64);
+        let quarter = black_box(f64::EPSILON / 4.0);
+        let three_quarters = black_box(0.75 * f64::EPSILON);
+        let rounding = match (one + quarter > one, -one - quarter < -one, one + three_quarters > one) {
+            (true, _, _) => RoundingMode::Upward,
+            (_, true, _) => RoundingMode::Downward,
+            (_, _, true) => RoundingMode::ToNearest,
+            _ => RoundingMode::TowardZero,
+        };
+        let flushes_denormals = black_box(f64::MIN_POSITIVE) / black_box(2.0) == 0.0;
+        return Self { rounding, flushes_denormals, fused_multiply_add: hardware_fma() && !strict };
+    }
+
+    pub fn is_default(&self) -> bool {
+        return self.rounding == RoundingMode::ToNearest && !self.flushes_denormals;
+    }
+}
+
+impl fmt::Display for FpEnvironment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        return write!(
+            f,
+            "rounding {:?}, denormals {}, multiply-add {}",
+            self.rounding,
+            if self.flushes_denormals { "flushed" } else { "preserved" },
+            if self.fused_multiply_add { "fused" } else { "unfused" },
+        );
+    }
+}
+
+// Returns the environment if it differs from the IEEE defaults, e.g. because a library enabled flush-to-zero or
+// changed the rounding mode, which makes results differ from other platforms.
+pub fn check_fp_environment() -> Result<(), FpEnvironment> {
+    let environment = FpEnvironment::detect();
+    if !environment.is_default() {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(%environment, "non-default floating-point environment");
+        return Err(environment);
+    }
+    return Ok(());
+}
+
+// In strict mode `a * b + c` is never fused, even on targets with hardware FMA, so results match targets without
+// it. Unlike the `no-fma` feature this can be switched at runtime, at the cost of one relaxed load per kernel call.
+pub fn set_strict_fp(strict: bool) {
+    STRICT.store(strict, Ordering::Relaxed);
+}
+
+pub fn strict_fp() -> bool {
+    return STRICT.load(Ordering::Relaxed);
+}
+
+fn hardware_fma() -> bool {
+    return cfg!(all(target_feature = "fma", not(feature = "no-fma"), not(feature = "soft-float")));
+}
+
+#[cfg(test)]
+mod fp_env_test {
+    use crate::{check_fp_environment, FpEnvironment, RoundingMode};
+
+    #[test]
+    fn default_environment() {
+        let environment = FpEnvironment::detect();
+        assert_eq!(environment.rounding, RoundingMode::ToNearest);
+        assert!(!environment.flushes_denormals);
+        assert_eq!(check_fp_environment(), Ok(()));
+        assert!(environment.to_string().starts_with("rounding ToNearest, denormals preserved"));
+    }
+
+    #[test]
+    fn strict_mode_disables_fusion() {
+        // Probes directly instead of flipping the global switch, which would race with the other tests.
+        assert!(!FpEnvironment::probe(true).fused_multiply_add);
+        let hardware_fma = cfg!(all(target_feature = "fma", not(feature = "no-fma")));
+        assert_eq!(FpEnvironment::probe(false).fused_multiply_add, hardware_fma);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::policy::complement;
+use crate::{Vector, Vector3};
+
+// An orthonormal frame given by its three axes, i.e. the columns of a rotation matrix.
+pub type Frame = [Vector3; 3];
+
+fn determinant(m: &Frame) -> f64 {
+    return Vector3::dot_product(&m[0], &m[1].cross(&m[2]));
+}
+
+// Projects a nonsingular matrix onto the orthogonal group via the Newton iteration `X <- (X + X⁻ᵀ) / 2` for its polar
+// factor, which is the closest orthogonal matrix in the Frobenius norm.
+fn polar_factor(m: &Frame) -> Frame {
+    let mut x = *m;
+    for _ in 0..100 {
+        let det = determinant(&x);
+        let inverse_transpose = [x[1].cross(&x[2]) / det, x[2].cross(&x[0]) / det, x[0].cross(&x[1]) / det];
+        let next: Frame = [
+            (x[0] + inverse_transpose[0]) * 0.5,
+            (x[1] + inverse_transpose[1]) * 0.5,
+            (x[2] + inverse_transpose[2]) * 0.5,
+        ];
+        let change: f64 = (0..3).map(|j| (next[j] - x[j]).norm_linf()).fold(0.0, f64::max);
+        x = next;
+        if change < 1e-15 {
+            break;
+        }
+    }
+    return x;
+}
+
+// Chordal L2 mean of rotations: the arithmetic mean of the matrices projected back onto SO(3). Returns `None` for an
+// empty input or when the mean matrix has a non-positive determinant, i.e. the frames are too spread out for the mean
+// to be defined.
+pub fn mean_rotation(frames: &[Frame]) -> Option<Frame> {
+    if frames.is_empty() {
+        return None;
+    }
+    let n = frames.len() as f64;
+    let mean: Frame = [0, 1, 2].map(|j| frames.iter().map(|f| f[j]).sum::<Vector3>() / n);
+    if determinant(&mean) <= 0.0 {
+        return None;
+    }
+    return Some(polar_factor(&mean));
+}
+
+// Relabels the axes of each frame after the first by the signed permutation that best matches the frame before it,
+// i.e. maximizes `Σₖ ⟨prev[k], next[k]⟩`. Removes the sign flips and axis swaps that appear when frames along a curve
+// are orthonormalized one sample at a time. Single sign flips are undone as well, so a frame may change handedness.
+pub fn smooth_frames(frames: &mut [Frame]) {
+    const PERMUTATIONS: [[usize; 3]; 6] = [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]];
+    for k in 1..frames.len() {
+        let (before, rest) = frames.split_at_mut(k);
+        let (previous, current) = (&before[k - 1], &mut rest[0]);
+        let mut best = (f64::NEG_INFINITY, PERMUTATIONS[0], [1.0; 3]);
+        for permutation in PERMUTATIONS {
+            // For a fixed permutation each axis picks its own sign.
+            let dots = [0, 1, 2].map(|j| Vector3::dot_product(&previous[j], &current[permutation[j]]));
+            let score: f64 = dots.iter().map(|d| d.abs()).sum();
+            if score > best.0 {
+                best = (score, permutation, dots.map(|d| if d < 0.0 { -1.0 } else { 1.0 }));
+            }
+        }
+        let (_, permutation, signs) = best;
+        *current = [0, 1, 2].map(|j| current[permutation[j]] * signs[j]);
+    }
+}
+
+// Rotation-minimizing frames `[tangent, normal, binormal]` along the polyline through `points`, by the double
+// reflection method of Wang et al.: each frame is carried to the next point by a reflection in the bisector plane of
+// the segment followed by one that aligns the tangents, so the frames do not twist about the curve. Tangents are
+// central differences, and the first normal is the complement of the first tangent. Repeated points keep the
+// previous frame. Returns no frames for fewer than two points or when all points coincide.
+pub fn rotation_minimizing_frames(points: &[Vector3]) -> Vec<Frame> {
+    let n = points.len();
+    let mut tangents: Vec<Vector3> = Vec::with_capacity(n);
+    for i in 0..n {
+        let difference = points[(i + 1).min(n - 1)] - points[i.saturating_sub(1)];
+        let length = difference.length();
+        tangents.push(if length > 0.0 { difference / length } else { Vector3::zero() });
+    }
+    // Points without a tangent of their own inherit the nearest one before them, or after them at the start.
+    let Some(first) = tangents.iter().position(|t| t.length() > 0.0) else {
+        return Vec::new();
+    };
+    for i in 0..n {
+        if tangents[i].length() == 0.0 {
+            tangents[i] = tangents[if i < first { first } else { i - 1 }];
+        }
+    }
+
+    let mut normal = complement(&tangents[..1]).unwrap();
+    let mut frames = Vec::with_capacity(n);
+    frames.push([tangents[0], normal, tangents[0].cross(&normal)]);
+    for i in 0..n - 1 {
+        let segment = points[i + 1] - points[i];
+        let mut reflected_normal = normal;
+        let mut reflected_tangent = tangents[i];
+        let c1 = Vector3::dot_product(&segment, &segment);
+        if c1 > 0.0 {
+            reflected_normal.axpy(-2.0 / c1 * Vector3::dot_product(&segment, &normal), &segment);
+            reflected_tangent.axpy(-2.0 / c1 * Vector3::dot_product(&segment, &tangents[i]), &segment);
+        }
+        let alignment = tangents[i + 1] - reflected_tangent;
+        let c2 = Vector3::dot_product(&alignment, &alignment);
+        normal = reflected_normal;
+        if c2 > 0.0 {
+            normal.axpy(-2.0 / c2 * Vector3::dot_product(&alignment, &reflected_normal), &alignment);
+        }
+        frames.push([tangents[i + 1], normal, tangents[i + 1].cross(&normal)]);
+    }
+    return frames;
+}
+
+#[cfg(test)]
+mod frames_test {
+    use crate::frames::{mean_rotation, rotation_minimizing_frames, smooth_frames, Frame};
+    use crate::{Vector, Vector3};
+
+    fn rotation_z(angle: f64) -> Frame {
+        let (s, c) = angle.sin_cos();
+        return [Vector3::new([c, s, 0.0]), Vector3::new([-s, c, 0.0]), Vector3::E2];
+    }
+
+    #[test]
+    fn mean_of_symmetric_rotations() {
+        let mean = mean_rotation(&[rotation_z(0.3), rotation_z(-0.3)]).unwrap();
+        for (axis, expected) in mean.iter().zip([Vector3::E0, Vector3::E1, Vector3::E2]) {
+            assert!((*axis - expected).length() < 1e-12);
+        }
+
+        // For rotations about a common axis the chordal mean is the rotation by the circular mean angle.
+        let angles = [0.1_f64, 0.5, 0.6];
+        let mean = mean_rotation(&angles.map(rotation_z)).unwrap();
+        let expected = rotation_z(angles.iter().map(|a| a.sin()).sum::<f64>().atan2(angles.iter().map(|a| a.cos()).sum()));
+        for j in 0..3 {
+            assert!((mean[j] - expected[j]).length() < 1e-12);
+        }
+        assert!(mean_rotation(&[]).is_none());
+    }
+
+    #[test]
+    fn smoothing_removes_flips_and_swaps() {
+        let mut frames = [0.0, 0.1, 0.2, 0.3].map(rotation_z);
+        let expected = frames;
+        frames[1][0] = frames[1][0] * -1.0;
+        frames[2].swap(0, 1);
+        frames[3] = [frames[3][2], frames[3][0] * -1.0, frames[3][1] * -1.0];
+        smooth_frames(&mut frames);
+        for (frame, expected) in frames.iter().zip(&expected) {
+            for j in 0..3 {
+                assert!((frame[j] - expected[j]).length() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn rotation_minimizing_frames_on_a_helix() {
+        let points: Vec<Vector3> =
+            (0..200).map(|i| 0.05 * i as f64).map(|s| Vector3::new([s.cos(), s.sin(), 0.5 * s])).collect();
+        let frames = rotation_minimizing_frames(&points);
+        assert_eq!(frames.len(), points.len());
+        for pair in frames.windows(2) {
+            for frame in pair {
+                for i in 0..3 {
+                    for j in 0..3 {
+                        let expected = if i == j { 1.0 } else { 0.0 };
+                        assert!((Vector3::dot_product(&frame[i], &frame[j]) - expected).abs() < 1e-12);
+                    }
+                }
+            }
+            // Without twist the normal only turns within the plane of the tangents, to first order in the step.
+            let twist = Vector3::dot_product(&(pair[1][1] - pair[0][1]), &pair[0][2]);
+            assert!(twist.abs() < 1e-3);
+        }
+
+        // Along a straight line the frame does not change.
+        let line = [0.0, 1.0, 1.0, 3.0].map(|x| Vector3::new([x, 0.0, 0.0]));
+        let frames = rotation_minimizing_frames(&line);
+        assert!(frames.iter().all(|f| f == &frames[0]));
+        assert!(rotation_minimizing_frames(&line[..1]).is_empty());
+    }
+}
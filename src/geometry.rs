@@ -0,0 +1,215 @@
+use std::ops::Deref;
+
+use crate::basis::extend_orthonormal;
+use crate::eigen::symmetric_eigen;
+use crate::{apply_complement_projector, apply_projector, Error, Matrix, OrthonormalBasis, Vector};
+
+// Affine subspace `origin + span(basis)`, e.g. a line or plane through a point cloud.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AffineSubspace<V: Vector> {
+    origin: V,
+    basis: OrthonormalBasis<V>,
+}
+
+impl<V: Vector> AffineSubspace<V> {
+    pub fn new(origin: V, basis: OrthonormalBasis<V>) -> Self {
+        return Self { origin, basis };
+    }
+
+    pub fn origin(&self) -> &V {
+        return &self.origin;
+    }
+
+    pub fn basis(&self) -> &OrthonormalBasis<V> {
+        return &self.basis;
+    }
+
+    pub fn dim(&self) -> usize {
+        return self.basis.len();
+    }
+
+    // Closest point of the subspace to `p`.
+    pub fn project_point(&self, p: &V) -> V {
+        return self.origin.clone() + apply_projector(&self.basis, &(p.clone() - self.origin.clone()));
+    }
+
+    pub fn distance_to_point(&self, p: &V) -> f64 {
+        return apply_complement_projector(&self.basis, &(p.clone() - self.origin.clone())).length();
+    }
+
+    // The single point where `line` meets the subspace, or `None` if it is parallel to it, lies in it, or (from three
+    // dimensions on for two lines) passes by it.
+    pub fn intersect_line(&self, line: &Line<V>) -> Option<V> {
+        let direction = &line.basis[0];
+        let offset = line.origin.clone() - self.origin.clone();
+        let r0 = apply_complement_projector(&self.basis, &offset);
+        let rd = apply_complement_projector(&self.basis, direction);
+        let rd_squared = V::dot_product(&rd, &rd);
+        if rd_squared < INTERSECTION_TOLERANCE * INTERSECTION_TOLERANCE {
+            return None;
+        }
+        let t = -V::dot_product(&r0, &rd) / rd_squared;
+        let mut point = line.origin.clone();
+        point.axpy(t, direction);
+        if self.distance_to_point(&point) > INTERSECTION_TOLERANCE * (1.0 + offset.length()) {
+            return None;
+        }
+        return Some(point);
+    }
+}
+
+// Relative tolerance for deciding parallelism and whether an intersection candidate lies on both objects.
+const INTERSECTION_TOLERANCE: f64 = 1e-10;
+
+// Line through two points, stored as one point and a unit direction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line<V: Vector> {
+    subspace: AffineSubspace<V>,
+}
+
+impl<V: Vector> Line<V> {
+    // `None` if the points coincide.
+    pub fn from_points(a: &V, b: &V) -> Option<Self> {
+        let mut directions = vec![];
+        extend_orthonormal(&mut directions, std::iter::once(b.clone() - a.clone()));
+        if directions.len() != 1 {
+            return None;
+        }
+        let basis = OrthonormalBasis::try_from_orthonormal(directions, 1e-12).ok()?;
+        return Some(Self { subspace: AffineSubspace::new(a.clone(), basis) });
+    }
+
+    pub fn direction(&self) -> &V {
+        return &self.subspace.basis[0];
+    }
+
+    pub fn intersect(&self, other: &Line<V>) -> Option<V> {
+        return self.subspace.intersect_line(other);
+    }
+}
+
+impl<V: Vector> Deref for Line<V> {
+    type Target = AffineSubspace<V>;
+
+    fn deref(&self) -> &Self::Target {
+        return &self.subspace;
+    }
+}
+
+// Plane through three points, stored as one point and an orthonormal pair of in-plane directions obtained by
+// Gram-Schmidt of the two edge vectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plane<V: Vector> {
+    subspace: AffineSubspace<V>,
+}
+
+impl<V: Vector> Plane<V> {
+    // `None` if the points are collinear.
+    pub fn from_points(a: &V, b: &V, c: &V) -> Option<Self> {
+        let mut directions = vec![];
+        extend_orthonormal(&mut directions, [b.clone() - a.clone(), c.clone() - a.clone()].into_iter());
+        if directions.len() != 2 {
+            return None;
+        }
+        let basis = OrthonormalBasis::try_from_orthonormal(directions, 1e-12).ok()?;
+        return Some(Self { subspace: AffineSubspace::new(a.clone(), basis) });
+    }
+
+    pub fn intersect(&self, line: &Line<V>) -> Option<V> {
+        return self.subspace.intersect_line(line);
+    }
+}
+
+impl<V: Vector> Deref for Plane<V> {
+    type Target = AffineSubspace<V>;
+
+    fn deref(&self) -> &Self::Target {
+        return &self.subspace;
+    }
+}
+
+// Total least squares fit of a `k`-dimensional affine subspace to `points`: passes through their centroid and is
+// spanned by the `k` principal directions of the centered points, which minimizes the sum of squared orthogonal
+// distances. `k` is capped at `V::DIM`; `points` must not be empty. Fails if the eigensolver does not converge, which
+// happens for non-finite points.
+pub fn best_fit_subspace<V: Vector>(points: &[V], k: usize) -> Result<AffineSubspace<V>, Error> {
+    assert!(!points.is_empty(), "cannot fit a subspace to no points");
+    let centroid = points.iter().cloned().sum::<V>() / points.len() as f64;
+    let mut scatter = Matrix::zeros(V::DIM, V::DIM);
+    for p in points {
+        let d = p.clone() - centroid.clone();
+        for j in 0..V::DIM {
+            for i in 0..V::DIM {
+                scatter[(i, j)] += d[i] * d[j];
+            }
+        }
+    }
+    // Eigenvalues come in ascending order, so the principal directions are the last columns.
+    let (_, directions) = symmetric_eigen(&scatter)?;
+    let vectors = (V::DIM - k.min(V::DIM)..V::DIM).rev()
+        .map(|m| {
+            let mut v = V::zero();
+            v.get_components_mut().copy_from_slice(directions.column(m));
+            return v;
+        })
+        .collect();
+    return Ok(AffineSubspace::new(centroid, OrthonormalBasis::gram_schmidt(vectors)));
+}
+
+#[cfg(test)]
+mod geometry_test {
+    use crate::{best_fit_subspace, Line, Plane, Vector, Vector3};
+
+    #[test]
+    fn fits_plane_through_noisy_points() {
+        let points: Vec<Vector3> = (0..20).map(|i| {
+            let (x, y) = ((i % 5) as f64, (i / 5) as f64);
+            let noise = if i % 2 == 0 { 1e-3 } else { -1e-3 };
+            return Vector3::new([x, y, 0.5 * x - y + 2.0 + noise]);
+        }).collect();
+        let plane = best_fit_subspace(&points, 2).unwrap();
+        assert_eq!(plane.dim(), 2);
+        assert!(points.iter().all(|p| plane.distance_to_point(p) < 2e-3));
+
+        let off_plane = Vector3::new([1.0, 1.0, 10.0]);
+        let projected = plane.project_point(&off_plane);
+        assert!(plane.distance_to_point(&projected) < 1e-12);
+        assert!(plane.distance_to_point(&off_plane) > 1.0);
+
+        let line = best_fit_subspace(&points, 1).unwrap();
+        assert!(line.distance_to_point(line.origin()) < 1e-12);
+    }
+
+    #[test]
+    fn fits_tiny_point_clouds() {
+        let points: Vec<Vector3> = (0..10).map(|i| {
+            let t = i as f64 - 4.5;
+            return Vector3::new([t, t + if i % 2 == 0 { 1e-3 } else { -1e-3 }, 0.0]) * 1e-9;
+        }).collect();
+        let line = best_fit_subspace(&points, 1).unwrap();
+        // Along `(1, 1, 0)`, not along an axis.
+        let along = *line.origin() + Vector3::new([1.0, 1.0, 0.0]) * 1e-9;
+        assert!(line.distance_to_point(&along) < 1e-12, "{}", line.distance_to_point(&along));
+    }
+
+    #[test]
+    fn line_and_plane() {
+        let line_through = |a: [f64; 3], b: [f64; 3]| Line::from_points(&Vector3::new(a), &Vector3::new(b)).unwrap();
+        let plane = Plane::from_points(&Vector3::E2, &Vector3::new([1.0, 0.0, 1.0]), &Vector3::new([0.0, 1.0, 1.0]));
+        let plane = plane.unwrap();
+        assert!(Plane::from_points(&Vector3::E0, &(Vector3::E0 * 2.0), &(Vector3::E0 * 3.0)).is_none());
+        let p = Vector3::new([5.0, -2.0, 4.0]);
+        assert!((plane.distance_to_point(&p) - 3.0).abs() < 1e-12);
+        assert!((plane.project_point(&p) - Vector3::new([5.0, -2.0, 1.0])).length() < 1e-12);
+
+        let line = line_through([1.0, 1.0, 0.0], [2.0, 3.0, 2.0]);
+        let hit = plane.intersect(&line).unwrap();
+        assert!((hit - Vector3::new([1.5, 2.0, 1.0])).length() < 1e-12);
+        assert!((line.direction().length() - 1.0).abs() < 1e-12);
+
+        assert!(plane.intersect(&line_through([0.0, 0.0, 3.0], [1.0, 1.0, 3.0])).is_none());
+        assert!(line.intersect(&line_through([0.0, 0.0, 5.0], [0.0, 1.0, 5.0])).is_none());
+        let crossing = line.intersect(&line_through([1.5, 0.0, 1.0], [1.5, 1.0, 1.0])).unwrap();
+        assert!((crossing - Vector3::new([1.5, 2.0, 1.0])).length() < 1e-12);
+    }
+}
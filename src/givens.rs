@@ -0,0 +1,41 @@
+use crate::Vector;
+
+// Computes `(c, s, r)` such that `[c s; -s c] * [a; b] = [r; 0]`.
+pub fn givens(a: f64, b: f64) -> (f64, f64, f64) {
+    if b == 0.0 {
+        return (1.0, 0.0, a);
+    }
+    if a == 0.0 {
+        return (0.0, b.signum(), b.abs());
+    }
+    let r = a.hypot(b);
+    return (a / r, b / r, r);
+}
+
+// Rotates every component pair `(x[i], y[i])` by the rotation computed with `givens`.
+pub fn apply_givens<V: Vector>(c: f64, s: f64, x: &mut V, y: &mut V) {
+    for i in 0..V::DIM {
+        let xi = x[i];
+        let yi = y[i];
+        x[i] = c * xi + s * yi;
+        y[i] = -s * xi + c * yi;
+    }
+}
+
+#[cfg(test)]
+mod givens_test {
+    use crate::{apply_givens, givens, Vector3};
+
+    #[test]
+    fn zeroes_second_component() {
+        let (c, s, r) = givens(3.0, 4.0);
+        assert_eq!(r, 5.0);
+        assert!((-s * 3.0 + c * 4.0).abs() < 1e-15);
+
+        let mut x = Vector3::new([3.0, 1.0, 0.0]);
+        let mut y = Vector3::new([4.0, 2.0, 1.0]);
+        apply_givens(c, s, &mut x, &mut y);
+        assert_eq!(x[0], 5.0);
+        assert!(y[0].abs() < 1e-15);
+    }
+}
@@ -0,0 +1,80 @@
+use crate::householder::{apply_reflector, reflector};
+use crate::{HouseholderProduct, Matrix};
+
+// Reduces a square matrix to upper Hessenberg form by an orthogonal similarity, `A = Q H Qᵀ`, with one Householder
+// reflector per column applied from both sides. `H` has the eigenvalues of `A` and zeros below the first
+// subdiagonal, which makes each QR iteration step on it O(n²) instead of O(n³).
+pub fn hessenberg(a: &Matrix) -> (Matrix, Matrix) {
+    let (h, q) = hessenberg_implicit(a);
+    return (h, q.to_matrix());
+}
+
+// Like `hessenberg`, with `Q` kept as the product of its reflectors.
+pub fn hessenberg_implicit(a: &Matrix) -> (Matrix, HouseholderProduct) {
+    let n = a.rows();
+    assert_eq!(n, a.cols(), "hessenberg needs a square matrix");
+    let mut h = a.clone();
+    let mut q = HouseholderProduct::identity(n);
+    let mut row = vec![0.0; n];
+    for k in 0..n.saturating_sub(2) {
+        let (tau, beta) = reflector(&mut h.column_mut(k)[k + 1..]);
+        let v = h.column(k)[k + 1..].to_vec();
+        h.column_mut(k)[k + 1] = beta;
+        h.column_mut(k)[k + 2..].iter_mut().for_each(|c| *c = 0.0);
+        // `H ← P H P` with `P = I - tau v vᵀ` acting on the components `k + 1..`.
+        for j in k + 1..n {
+            apply_reflector(&v, tau, &mut h.column_mut(j)[k + 1..]);
+        }
+        for i in 0..n {
+            for (j, value) in row.iter_mut().enumerate().skip(k + 1) {
+                *value = h[(i, j)];
+            }
+            apply_reflector(&v, tau, &mut row[k + 1..]);
+            for (j, value) in row.iter().enumerate().skip(k + 1) {
+                h[(i, j)] = *value;
+            }
+        }
+        q.push(k + 1, v, tau);
+    }
+    return (h, q);
+}
+
+#[cfg(test)]
+mod hessenberg_test {
+    use crate::{hessenberg, Matrix};
+
+    #[test]
+    fn orthogonal_similarity() {
+        let a = Matrix::from_rows(&[
+            vec![4.0, 1.0, -2.0, 2.0],
+            vec![1.0, 2.0, 0.0, 1.0],
+            vec![-2.0, 0.0, 3.0, -2.0],
+            vec![2.0, 1.0, -2.0, -1.0],
+        ]);
+        let (h, q) = hessenberg(&a);
+        for j in 0..4 {
+            for i in j + 2..4 {
+                assert_eq!(h[(i, j)], 0.0);
+            }
+        }
+        let qt = q.transpose();
+        let identity = &qt * &q;
+        let reconstructed = &(&q * &h) * &qt;
+        for j in 0..4 {
+            for i in 0..4 {
+                assert!((identity[(i, j)] - if i == j { 1.0 } else { 0.0 }).abs() < 1e-14);
+                assert!((reconstructed[(i, j)] - a[(i, j)]).abs() < 1e-13);
+            }
+        }
+        // Similarity keeps the trace.
+        assert!(((0..4).map(|i| h[(i, i)]).sum::<f64>() - 8.0).abs() < 1e-13);
+    }
+
+    #[test]
+    fn small_matrices_are_already_hessenberg() {
+        let a = Matrix::from_rows(&[vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let (h, q) = hessenberg(&a);
+        assert_eq!(h, a);
+        assert_eq!(q, Matrix::identity(2));
+    }
+}
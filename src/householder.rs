@@ -0,0 +1,176 @@
+use crate::{Matrix, Vector};
+
+// Computes `(v, tau)` with `v[0] == 1` such that `H = I - tau * v * vᵀ` maps `x` onto a multiple of the first unit
+// vector. The sign of that multiple is chosen opposite to `x[0]` to avoid cancellation, as in LAPACK's `dlarfg`.
+pub fn householder_vector<V: Vector>(x: &V) -> (V, f64) {
+    let mut v = x.clone();
+    let (tau, _) = reflector(v.get_components_mut());
+    return (v, tau);
+}
+
+// Overwrites `x` with the reflector `v` and returns `(tau, beta)`, where `beta` is the first component of `H * x`.
+pub(crate) fn reflector(x: &mut [f64]) -> (f64, f64) {
+    let alpha = x[0];
+    let tail_norm_squared: f64 = x[1..].iter().map(|c| c * c).sum();
+    if tail_norm_squared == 0.0 {
+        x[0] = 1.0;
+        return (0.0, alpha);
+    }
+    let beta = -alpha.signum() * alpha.hypot(tail_norm_squared.sqrt());
+    let tau = (beta - alpha) / beta;
+    x[1..].iter_mut().for_each(|c| *c /= alpha - beta);
+    x[0] = 1.0;
+    return (tau, beta);
+}
+
+// Applies `I - tau * v * vᵀ` to the slice `target`.
+pub(crate) fn apply_reflector(v: &[f64], tau: f64, target: &mut [f64]) {
+    let dot: f64 = v.iter().zip(target.iter()).map(|(a, b)| a * b).sum();
+    for (t, vi) in target.iter_mut().zip(v) {
+        *t -= tau * dot * vi;
+    }
+}
+
+// Applies `H = I - tau * v * vᵀ` to `target` in place.
+pub fn apply_householder<V: Vector>(v: &V, tau: f64, target: &mut V) {
+    let dot = V::dot_product(v, target);
+    target.sub(&v.clone().scale(tau * dot));
+}
+
+// Orthogonal `n x n` matrix `Q = H_0 H_1 ... H_(k-1)` kept as a product of Householder reflectors. Applying it costs
+// O(kn) and never forms the dense matrix. Reflector `i` is `I - tau_i * v_i * v_iᵀ` acting on the trailing
+// components starting at its offset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HouseholderProduct {
+    dim: usize,
+    reflectors: Vec<(usize, Vec<f64>, f64)>,
+}
+
+impl HouseholderProduct {
+    // The identity, a product of no reflectors.
+    pub fn identity(dim: usize) -> Self {
+        return Self { dim, reflectors: vec![] };
+    }
+
+    // Product of the reflections through the hyperplanes orthogonal to the `normals`, each of length `dim`. Panics for
+    // a normal that is zero or not finite, which defines no hyperplane. Only the direction matters, so every normal is
+    // rescaled by its largest component first, which keeps `tau = 2 / ‖n‖²` finite for tiny and huge normals alike.
+    pub fn from_normals(dim: usize, normals: Vec<Vec<f64>>) -> Self {
+        let mut product = Self::identity(dim);
+        for mut normal in normals {
+            assert_eq!(normal.len(), dim, "normals must have length dim");
+            let scale = normal.iter().fold(0.0, |m: f64, c| m.max(c.abs()));
+            assert!(scale > 0.0 && scale.is_finite(), "normals must be non-zero and finite");
+            normal.iter_mut().for_each(|c| *c /= scale);
+            let tau = 2.0 / normal.iter().map(|c| c * c).sum::<f64>();
+            product.reflectors.push((0, normal, tau));
+        }
+        return product;
+    }
+
+    // Product of `k` reflections through uniformly random hyperplanes.
+    #[cfg(feature = "rand")]
+    pub fn random<R: rand::Rng + ?Sized>(dim: usize, k: usize, rng: &mut R) -> Self {
+        let normals = (0..k).map(|_| (0..dim).map(|_| crate::projection::gaussian(rng)).collect()).collect();
+        return Self::from_normals(dim, normals);
+    }
+
+    pub(crate) fn push(&mut self, offset: usize, v: Vec<f64>, tau: f64) {
+        self.reflectors.push((offset, v, tau));
+    }
+
+    pub fn dim(&self) -> usize {
+        return self.dim;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.reflectors.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.reflectors.is_empty();
+    }
+
+    // Overwrites `v` with `Q v`.
+    pub fn apply(&self, v: &mut [f64]) {
+        assert_eq!(v.len(), self.dim, "vector length must match the dimension");
+        for (offset, reflector, tau) in self.reflectors.iter().rev() {
+            apply_reflector(reflector, *tau, &mut v[*offset..]);
+        }
+    }
+
+    // Overwrites `v` with `Qᵀ v`.
+    pub fn apply_transpose(&self, v: &mut [f64]) {
+        assert_eq!(v.len(), self.dim, "vector length must match the dimension");
+        for (offset, reflector, tau) in &self.reflectors {
+            apply_reflector(reflector, *tau, &mut v[*offset..]);
+        }
+    }
+
+    pub fn to_matrix(&self) -> Matrix {
+        let mut q = Matrix::identity(self.dim);
+        for j in 0..self.dim {
+            self.apply(q.column_mut(j));
+        }
+        return q;
+    }
+}
+
+#[cfg(test)]
+mod householder_test {
+    use crate::{apply_householder, householder_vector, HouseholderProduct, Matrix, Vector, Vector4};
+
+    #[test]
+    fn annihilates_tail() {
+        let x = Vector4::new([1.0, 2.0, 2.0, 4.0]);
+        let (v, tau) = householder_vector(&x);
+        let mut y = x;
+        apply_householder(&v, tau, &mut y);
+        assert!((y[0] + 5.0).abs() < 1e-12);
+        for i in 1..4 {
+            assert!(y[i].abs() < 1e-12);
+        }
+
+        // Reflections are involutions.
+        apply_householder(&v, tau, &mut y);
+        assert!((y - x).length() < 1e-12);
+    }
+
+    #[test]
+    fn already_reduced() {
+        let (v, tau) = householder_vector(&Vector4::new([3.0, 0.0, 0.0, 0.0]));
+        assert_eq!(v, Vector4::E0);
+        assert_eq!(tau, 0.0);
+    }
+
+    #[test]
+    fn product_is_orthogonal() {
+        let q = HouseholderProduct::from_normals(3, vec![vec![1.0, 1.0, 0.0], vec![0.0, 2.0, -1.0]]);
+        let dense = q.to_matrix();
+        let error = &(&dense.transpose() * &dense) - &Matrix::identity(3);
+        assert!((0..3).all(|j| error.column(j).iter().all(|e| e.abs() < 1e-12)));
+
+        let mut v = vec![1.0, 2.0, 3.0];
+        q.apply(&mut v);
+        assert!((0..3).all(|i| (v[i] - (dense[(i, 0)] + 2.0 * dense[(i, 1)] + 3.0 * dense[(i, 2)])).abs() < 1e-12));
+        q.apply_transpose(&mut v);
+        assert!((v[0] - 1.0).abs() < 1e-12 && (v[1] - 2.0).abs() < 1e-12 && (v[2] - 3.0).abs() < 1e-12);
+    }
+
+    // `e0` reflected through the hyperplane orthogonal to `(1, 0, 1)` is `-e2`, whatever the length of the normal.
+    #[test]
+    fn normals_of_any_scale() {
+        for scale in [1e-200, 1.0, 1e200] {
+            let q = HouseholderProduct::from_normals(3, vec![vec![scale, 0.0, scale]]);
+            let mut v = vec![1.0, 0.0, 0.0];
+            q.apply(&mut v);
+            assert!(v[0].abs() < 1e-15 && v[1] == 0.0 && (v[2] + 1.0).abs() < 1e-15, "{:e}: {:?}", scale, v);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero and finite")]
+    fn rejects_zero_normals() {
+        HouseholderProduct::from_normals(3, vec![vec![1.0, 0.0, 0.0], vec![0.0; 3]]);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::eigen::{start_vector, symmetric_eigen};
+use crate::{lowdin_orthonormalize, Error, Matrix, Vector};
+
+const MAX_ITERATIONS: usize = 1_000;
+
+// Symmetric FastICA with the `tanh` contrast. `samples` are observations of `V::DIM` mixed channels; the routine
+// centers and whitens them, then runs the fixed-point update for `components` unmixing vectors at once, decorrelating
+// them after every step with `lowdin_orthonormalize`. Returns the sample mean and the unmixing vectors in the original
+// coordinates, so that source `i` of an observation `x` is `dot(unmixing[i], x - mean)`. The sample covariance must
+// be non-singular. Fails if an eigen-decomposition does not converge, which happens for non-finite samples.
+pub fn fastica<V: Vector>(samples: &[V], components: usize, tol: f64) -> Result<(V, Vec<V>), Error> {
+    assert!(components <= V::DIM, "cannot extract more components than channels");
+    let count = samples.len() as f64;
+    let mean = samples.iter().cloned().sum::<V>() / count;
+    let centered: Vec<V> = samples.iter().map(|x| x.clone() - mean.clone()).collect();
+
+    // Symmetric (ZCA) whitening `K = E D^(-1/2) Eᵀ` of the sample covariance `E D Eᵀ`.
+    let mut covariance = Matrix::zeros(V::DIM, V::DIM);
+    for x in &centered {
+        for j in 0..V::DIM {
+            for i in 0..V::DIM {
+                covariance[(i, j)] += x[i] * x[j] / count;
+            }
+        }
+    }
+    let (variances, axes) = symmetric_eigen(&covariance)?;
+    let whiten = |x: &V| {
+        let mut z = V::zero();
+        for (m, variance) in variances.iter().enumerate() {
+            let coefficient = (0..V::DIM).map(|i| axes[(i, m)] * x[i]).sum::<f64>() / variance.sqrt();
+            for i in 0..V::DIM {
+                z[i] += coefficient * axes[(i, m)];
+            }
+        }
+        return z;
+    };
+    let whitened: Vec<V> = centered.iter().map(whiten).collect();
+
+    let mut unmixing: Vec<V> = (0..components).map(start_vector).collect();
+    lowdin_orthonormalize(&mut unmixing)?;
+    for _ in 0..MAX_ITERATIONS {
+        let mut updated: Vec<V> = unmixing.iter().map(|w| {
+            let mut next = V::zero();
+            let mut derivative = 0.0;
+            for z in &whitened {
+                let g = V::dot_product(w, z).tanh();
+                next.axpy(g / count, z);
+                derivative += (1.0 - g * g) / count;
+            }
+            next.axpy(-derivative, w);
+            return next;
+        }).collect();
+        lowdin_orthonormalize(&mut updated)?;
+        let change = updated.iter().zip(&unmixing)
+            .map(|(a, b)| 1.0 - V::dot_product(a, b).abs())
+            .fold(0.0, f64::max);
+        unmixing = updated;
+        if change < tol {
+            break;
+        }
+    }
+
+    // K is symmetric, so `dot(w, K x) = dot(K w, x)`.
+    return Ok((mean, unmixing.iter().map(whiten).collect()));
+}
+
+#[cfg(test)]
+mod ica_test {
+    use crate::{fastica, Vector, Vector2};
+
+    #[test]
+    fn separates_mixed_signals() {
+        let sources: Vec<[f64; 2]> = (0..2000).map(|t| {
+            let t = t as f64;
+            return [(t * 0.05).sin(), if (t * 0.013).fract() < 0.5 { 1.0 } else { -1.0 }];
+        }).collect();
+        let mixed: Vec<Vector2> = sources.iter()
+            .map(|s| Vector2::new([s[0] + 0.5 * s[1] + 3.0, 0.3 * s[0] + s[1] - 1.0]))
+            .collect();
+
+        let (mean, unmixing) = fastica(&mixed, 2, 1e-10).unwrap();
+        for w in &unmixing {
+            let recovered: Vec<f64> = mixed.iter()
+                .map(|x| Vector2::dot_product(w, &(*x - mean)))
+                .collect();
+            let best = (0..2).map(|k| {
+                let source: Vec<f64> = sources.iter().map(|s| s[k]).collect();
+                let source_mean = source.iter().sum::<f64>() / source.len() as f64;
+                let (mut cross, mut rr, mut ss) = (0.0, 0.0, 0.0);
+                for (r, s) in recovered.iter().zip(&source) {
+                    cross += r * (s - source_mean);
+                    rr += r * r;
+                    ss += (s - source_mean) * (s - source_mean);
+                }
+                return (cross / (rr * ss).sqrt()).abs();
+            }).fold(0.0, f64::max);
+            assert!(best > 0.99);
+        }
+    }
+}
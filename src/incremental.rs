@@ -0,0 +1,213 @@
+use crate::{validate_finite, Error, OrthonormalBasis, Vector};
+
+// Residual norm, relative to the input norm, below which a pushed vector counts as numerically zero.
+const ZERO_TOLERANCE: f64 = 1e-10;
+
+// Maximum deviation from orthonormality accepted when resuming from a checkpoint.
+const ORTHONORMALITY_TOLERANCE: f64 = 1e-10;
+
+// Orthonormalizes vectors one at a time while keeping the coefficients, so that after pushing `a_0, ..., a_k` the
+// stored basis `q_0, ..., q_k` and the columns of the upper triangular `R` satisfy `a_j = sum_i R[j][i] * q_i`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncrementalGramSchmidt<V: Vector> {
+    basis: Vec<V>,
+    r: Vec<Vec<f64>>,
+    reorthogonalize: bool,
+}
+
+// Plain-data snapshot of an `IncrementalGramSchmidt`, independent of the concrete vector type.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    pub basis: Vec<Vec<f64>>,
+    pub r: Vec<Vec<f64>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reorthogonalize: bool,
+}
+
+impl<V: Vector> IncrementalGramSchmidt<V> {
+    pub fn new() -> Self {
+        return Self { basis: vec![], r: vec![], reorthogonalize: false };
+    }
+
+    // Like `new`, but every `push` projects twice. The second pass removes what rounding left of the components along
+    // the basis, so the basis stays orthonormal to working precision even for nearly dependent inputs, at twice the
+    // cost per push.
+    pub fn with_reorthogonalization() -> Self {
+        return Self { basis: vec![], r: vec![], reorthogonalize: true };
+    }
+
+    // Returns the new column of `R`. A vector that is zero or (numerically) in the span of the basis is rejected with
+    // `ZeroVector` and leaves the state unchanged.
+    pub fn push(&mut self, mut v: V) -> Result<&[f64], Error> {
+        let scale = v.length();
+        let mut column = Vec::with_capacity(self.basis.len() + 1);
+        for q in &self.basis {
+            let dot = V::dot_product(q, &v);
+            Vector::sub(&mut v, &q.clone().scale(dot));
+            column.push(dot);
+        }
+        if self.reorthogonalize {
+            for (q, coefficient) in self.basis.iter().zip(column.iter_mut()) {
+                let dot = V::dot_product(q, &v);
+                Vector::sub(&mut v, &q.clone().scale(dot));
+                *coefficient += dot;
+            }
+        }
+        let norm = v.length();
+        if !(norm > ZERO_TOLERANCE * scale && norm > 0.0) {
+            return Err(Error::ZeroVector { vector: self.basis.len() });
+        }
+        column.push(norm);
+        self.basis.push(v / norm);
+        self.r.push(column);
+        return Ok(&self.r[self.r.len() - 1]);
+    }
+
+    pub fn basis(&self) -> &[V] {
+        return &self.basis;
+    }
+
+    pub fn r(&self) -> &[Vec<f64>] {
+        return &self.r;
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        let basis = self.basis.iter()
+            .map(|q| (0..V::DIM).map(|i| q[i]).collect())
+            .collect();
+        return Checkpoint { basis, r: self.r.clone(), reorthogonalize: self.reorthogonalize };
+    }
+
+    // Fails with `DimensionMismatch` if the snapshot does not fit the vector type or is not triangular, with
+    // `NonFinite` for a NaN or infinite entry in the basis or in `R`, and with `NotOrthonormal` if the basis is off
+    // by more than a small tolerance. A state built without reorthogonalization from nearly dependent vectors can fail
+    // the last check; `with_reorthogonalization` avoids that.
+    pub fn resume(checkpoint: Checkpoint) -> Result<Self, Error> {
+        if checkpoint.basis.len() != checkpoint.r.len() {
+            return Err(Error::DimensionMismatch { expected: checkpoint.basis.len(), found: checkpoint.r.len() });
+        }
+        let mut basis = Vec::with_capacity(checkpoint.basis.len());
+        for (j, (components, column)) in checkpoint.basis.iter().zip(&checkpoint.r).enumerate() {
+            if components.len() != V::DIM {
+                return Err(Error::DimensionMismatch { expected: V::DIM, found: components.len() });
+            }
+            if column.len() != j + 1 {
+                return Err(Error::DimensionMismatch { expected: j + 1, found: column.len() });
+            }
+            if let Some(component) = column.iter().position(|x| !x.is_finite()) {
+                return Err(Error::NonFinite { vector: j, component });
+            }
+            let mut q = V::zero();
+            q.get_components_mut().copy_from_slice(components);
+            basis.push(q);
+        }
+        validate_finite(&basis)?;
+        let basis = OrthonormalBasis::try_from_orthonormal(basis, ORTHONORMALITY_TOLERANCE)?.into_vectors();
+        return Ok(Self { basis, r: checkpoint.r, reorthogonalize: checkpoint.reorthogonalize });
+    }
+}
+
+impl<V: Vector> Default for IncrementalGramSchmidt<V> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+#[cfg(test)]
+mod incremental_test {
+    use crate::{Error, IncrementalGramSchmidt, Vector, Vector3};
+
+    #[test]
+    fn push_builds_qr() {
+        let mut gs = IncrementalGramSchmidt::new();
+        assert_eq!(gs.push(Vector3::new([3.0, 4.0, 0.0])), Ok(&[5.0][..]));
+        assert_eq!(gs.push(Vector3::new([3.0, 4.0, 2.0])), Ok(&[5.0, 2.0][..]));
+        assert_eq!(gs.basis(), &[Vector3::new([0.6, 0.8, 0.0]), Vector3::E2]);
+    }
+
+    #[test]
+    fn push_rejects_dependent_vectors() {
+        let mut gs = IncrementalGramSchmidt::new();
+        assert_eq!(gs.push(Vector3::zero()), Err(Error::ZeroVector { vector: 0 }));
+        gs.push(Vector3::new([1.0, 1.0, 0.0])).unwrap();
+        gs.push(Vector3::new([1.0, -1.0, 0.0])).unwrap();
+        let before = gs.clone();
+        assert_eq!(gs.push(Vector3::new([3.0, 0.1, 0.0])), Err(Error::ZeroVector { vector: 2 }));
+        assert_eq!(gs, before);
+    }
+
+    #[test]
+    fn checkpoint_and_resume() {
+        let mut gs = IncrementalGramSchmidt::new();
+        gs.push(Vector3::new([1.0, 1.0, 0.0])).unwrap();
+        gs.push(Vector3::new([0.0, 1.0, 1.0])).unwrap();
+
+        let mut resumed = IncrementalGramSchmidt::<Vector3>::resume(gs.checkpoint()).unwrap();
+        assert_eq!(resumed, gs);
+        resumed.push(Vector3::new([1.0, 0.0, 1.0])).unwrap();
+        gs.push(Vector3::new([1.0, 0.0, 1.0])).unwrap();
+        assert_eq!(resumed, gs);
+
+        let mut truncated = gs.checkpoint();
+        truncated.basis[0].pop();
+        assert_eq!(IncrementalGramSchmidt::<Vector3>::resume(truncated),
+                   Err(Error::DimensionMismatch { expected: 3, found: 2 }));
+        assert!((resumed.basis()[2].length() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn resume_rejects_corrupt_checkpoints() {
+        let mut gs = IncrementalGramSchmidt::new();
+        gs.push(Vector3::new([1.0, 1.0, 0.0])).unwrap();
+        gs.push(Vector3::new([0.0, 1.0, 1.0])).unwrap();
+        let resume = IncrementalGramSchmidt::<Vector3>::resume;
+
+        let mut nan_basis = gs.checkpoint();
+        nan_basis.basis[1][2] = f64::NAN;
+        assert_eq!(resume(nan_basis), Err(Error::NonFinite { vector: 1, component: 2 }));
+        let mut infinite_r = gs.checkpoint();
+        infinite_r.r[1][0] = f64::INFINITY;
+        assert_eq!(resume(infinite_r), Err(Error::NonFinite { vector: 1, component: 0 }));
+        let mut skewed = gs.checkpoint();
+        skewed.basis[1][0] += 1e-6;
+        assert_eq!(resume(skewed), Err(Error::NotOrthonormal { vector: 0 }));
+        let mut long = gs.checkpoint();
+        long.basis[0].iter_mut().for_each(|c| *c *= 2.0);
+        assert_eq!(resume(long), Err(Error::NotOrthonormal { vector: 0 }));
+    }
+
+    // Nearly parallel inputs: a single projection pass leaves a visible component along the earlier vectors, the
+    // second pass removes it.
+    #[test]
+    fn reorthogonalization_restores_orthogonality() {
+        let inputs = [
+            Vector3::new([1.0, 1e-9, 0.0]),
+            Vector3::new([1.0, 0.0, 1e-9]),
+            Vector3::new([1.0, 1e-9, 1e-9]),
+        ];
+        let mut single = IncrementalGramSchmidt::new();
+        let mut twice = IncrementalGramSchmidt::with_reorthogonalization();
+        for v in &inputs {
+            single.push(*v).unwrap();
+            twice.push(*v).unwrap();
+        }
+        let worst = |gs: &IncrementalGramSchmidt<Vector3>| {
+            let q = gs.basis();
+            return (0..3)
+                .flat_map(|i| (0..i).map(move |j| Vector3::dot_product(&q[i], &q[j]).abs()))
+                .fold(0.0, f64::max);
+        };
+        assert!(worst(&single) > 1e-10);
+        assert!(worst(&twice) < 1e-15);
+        assert_eq!(IncrementalGramSchmidt::<Vector3>::resume(single.checkpoint()),
+                   Err(Error::NotOrthonormal { vector: 0 }));
+        assert_eq!(IncrementalGramSchmidt::<Vector3>::resume(twice.checkpoint()).as_ref(), Ok(&twice));
+
+        // Both factorizations reproduce the inputs.
+        for (j, v) in inputs.iter().enumerate() {
+            let reconstructed: Vector3 = twice.r()[j].iter().zip(twice.basis()).map(|(c, q)| *q * *c).sum();
+            assert!((reconstructed - *v).norm_linf() < 1e-15);
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use std::ops::{Add, Div, Mul, Sub};
+
+use crate::{Error, Vector};
+
+// Closed interval `[lo, hi]`. Every operation rounds its result outward by one ulp, so the exact real result of the
+// operation on any members of the operands is guaranteed to be contained in the result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+impl Interval {
+    pub const fn point(value: f64) -> Self {
+        return Self { lo: value, hi: value };
+    }
+
+    pub fn contains(&self, value: f64) -> bool {
+        return self.lo <= value && value <= self.hi;
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        return self.lo.abs().max(self.hi.abs());
+    }
+
+    pub fn width(&self) -> f64 {
+        return self.hi - self.lo;
+    }
+
+    // Square root of the non-negative part. `sqrt` is correctly rounded, so one ulp outward is enough.
+    pub fn sqrt(self) -> Self {
+        return Self::outward(self.lo.max(0.0).sqrt(), self.hi.max(0.0).sqrt());
+    }
+
+    fn outward(lo: f64, hi: f64) -> Self {
+        return Self { lo: lo.next_down(), hi: hi.next_up() };
+    }
+}
+
+impl Add for Interval {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        return Self::outward(self.lo + rhs.lo, self.hi + rhs.hi);
+    }
+}
+
+impl Sub for Interval {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        return Self::outward(self.lo - rhs.hi, self.hi - rhs.lo);
+    }
+}
+
+impl Mul for Interval {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let products = [self.lo * rhs.lo, self.lo * rhs.hi, self.hi * rhs.lo, self.hi * rhs.hi];
+        let lo = products.iter().copied().fold(f64::INFINITY, f64::min);
+        let hi = products.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        return Self::outward(lo, hi);
+    }
+}
+
+// A divisor containing zero gives the whole real line.
+impl Div for Interval {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        if rhs.contains(0.0) {
+            return Self { lo: f64::NEG_INFINITY, hi: f64::INFINITY };
+        }
+        let quotients = [self.lo / rhs.lo, self.lo / rhs.hi, self.hi / rhs.lo, self.hi / rhs.hi];
+        let lo = quotients.iter().copied().fold(f64::INFINITY, f64::min);
+        let hi = quotients.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        return Self::outward(lo, hi);
+    }
+}
+
+// Enclosure of the exact inner product of the stored (already rounded) vectors.
+pub fn interval_dot<V: Vector>(a: &V, b: &V) -> Interval {
+    let mut sum = Interval::point(0.0);
+    for i in 0..V::DIM {
+        sum = sum + Interval::point(a[i]) * Interval::point(b[i]);
+    }
+    return sum;
+}
+
+// Rigorous upper bounds on how far the exact Gram matrix of a computed basis is from the identity.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrthogonalityCertificate {
+    pub max_off_diagonal: f64,
+    pub max_norm_deviation: f64,
+}
+
+// Modified Gram-Schmidt carried out in interval arithmetic. Component `i` of vector `j` of the result encloses
+// component `i` of the `j`-th vector of the exact (real arithmetic) orthonormalization of `vectors`, so any basis
+// computed in floating point can be checked against it. Fails with `ZeroVector` if the enclosure of a residual norm
+// reaches zero, i.e. the vectors are dependent or too ill-conditioned to separate in interval arithmetic.
+pub fn gram_schmidt_interval<V: Vector>(vectors: &[V]) -> Result<Vec<Vec<Interval>>, Error> {
+    let mut basis: Vec<Vec<Interval>> = Vec::with_capacity(vectors.len());
+    for (index, v) in vectors.iter().enumerate() {
+        let mut residual: Vec<Interval> = (0..V::DIM).map(|i| Interval::point(v[i])).collect();
+        for q in &basis {
+            let dot = enclosure_dot(q, &residual);
+            residual.iter_mut().zip(q).for_each(|(r, qi)| *r = *r - dot * *qi);
+        }
+        let norm = enclosure_dot(&residual, &residual).sqrt();
+        if norm.lo <= 0.0 {
+            return Err(Error::ZeroVector { vector: index });
+        }
+        basis.push(residual.into_iter().map(|r| r / norm).collect());
+    }
+    return Ok(basis);
+}
+
+fn enclosure_dot(a: &[Interval], b: &[Interval]) -> Interval {
+    return a.iter().zip(b).fold(Interval::point(0.0), |sum, (x, y)| sum + *x * *y);
+}
+
+pub fn certify_orthonormality<V: Vector>(basis: &[V]) -> OrthogonalityCertificate {
+    let mut certificate = OrthogonalityCertificate { max_off_diagonal: 0.0, max_norm_deviation: 0.0 };
+    for (i, a) in basis.iter().enumerate() {
+        let deviation = interval_dot(a, a) - Interval::point(1.0);
+        certificate.max_norm_deviation = certificate.max_norm_deviation.max(deviation.magnitude());
+        for b in &basis[i + 1..] {
+            certificate.max_off_diagonal = certificate.max_off_diagonal.max(interval_dot(a, b).magnitude());
+        }
+    }
+    return certificate;
+}
+
+#[cfg(test)]
+mod interval_test {
+    use crate::interval::{certify_orthonormality, gram_schmidt_interval, interval_dot, Interval};
+    use crate::{Error, Vector3, Vector4};
+
+    #[test]
+    fn encloses_exact_result() {
+        let third = Interval::point(1.0 / 3.0);
+        let sum = third + third + third;
+        assert!(sum.contains(1.0));
+        assert!(sum.hi - sum.lo < 1e-15);
+
+        let dot = interval_dot(&Vector3::new([0.1, 0.2, 0.3]), &Vector3::new([0.3, 0.2, 0.1]));
+        assert!(dot.contains(0.1));
+    }
+
+    #[test]
+    fn certifies_gram_schmidt_output() {
+        let mut basis = vec![
+            Vector3::new([1.0, 2.0, 3.0]),
+            Vector3::new([-1.0, 0.5, 2.0]),
+            Vector3::new([0.3, -2.0, 1.0]),
+        ];
+        Vector3::gram_schmidt(&mut basis);
+        let certificate = certify_orthonormality(&basis);
+        assert!(certificate.max_off_diagonal < 1e-14);
+        assert!(certificate.max_norm_deviation < 1e-14);
+
+        let skewed = certify_orthonormality(&[Vector3::E0, Vector3::new([0.1, 1.0, 0.0])]);
+        assert!(skewed.max_off_diagonal >= 0.1);
+    }
+
+    #[test]
+    fn interval_gram_schmidt_encloses_exact_basis() {
+        let input = [
+            Vector4::new([1.0, 1.0, 1.0, 1.0]),
+            Vector4::new([0.0, 1.0, 0.0, 1.0]),
+            Vector4::new([0.0, 0.0, 1.0, 1.0]),
+            Vector4::new([0.0, 0.0, 0.0, 1.0]),
+        ];
+        let exact = [
+            [0.5, 0.5, 0.5, 0.5],
+            [-0.5, 0.5, -0.5, 0.5],
+            [-0.5, -0.5, 0.5, 0.5],
+            [0.5, -0.5, -0.5, 0.5],
+        ];
+        let enclosures = gram_schmidt_interval(&input).unwrap();
+        let mut computed = input.to_vec();
+        Vector4::gram_schmidt(&mut computed);
+        for j in 0..4 {
+            for i in 0..4 {
+                let enclosure = enclosures[j][i];
+                assert!(enclosure.contains(exact[j][i]));
+                assert!(enclosure.width() < 1e-12);
+                assert!(enclosure.contains(computed[j][i]));
+            }
+        }
+
+        let dependent = [Vector3::E0, Vector3::new([2.0, 0.0, 0.0])];
+        assert_eq!(gram_schmidt_interval(&dependent), Err(Error::ZeroVector { vector: 1 }));
+    }
+}
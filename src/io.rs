@@ -0,0 +1,273 @@
+// Reading and writing bases and factorizations: memory-mapped matrices (`mmap` feature), JSON documents (`json`) and
+// compact binary encodings (`bincode`).
+#[cfg(feature = "mmap")]
+use std::fs::{File, OpenOptions};
+#[cfg(feature = "mmap")]
+use std::path::Path;
+
+#[cfg(feature = "mmap")]
+use memmap2::MmapMut;
+
+#[cfg(feature = "bincode")]
+pub use crate::binary::{decode_qr, decode_subspace, encode_qr, encode_subspace};
+#[cfg(feature = "json")]
+pub use crate::json::{BasisDocument, FactorizationDocument};
+
+#[cfg(feature = "mmap")]
+const F64_SIZE: usize = std::mem::size_of::<f64>();
+
+// A column-major `rows x cols` matrix of little-endian f64 values stored in a memory-mapped file. Columns are
+// copied in and out explicitly, so only the columns currently being worked on have to be resident.
+//
+// Constructing one is unsafe because the mapping aliases the file: while the matrix is alive, the file must not be
+// truncated or written by anyone else (another process, another mapping or plain file I/O), or reads and writes
+// through the matrix are undefined behaviour.
+#[cfg(feature = "mmap")]
+pub struct MmapMatrix {
+    mmap: MmapMut,
+    rows: usize,
+    cols: usize,
+}
+
+#[cfg(feature = "mmap")]
+impl MmapMatrix {
+    // Creates (or truncates) the file at `path` with room for a zero `rows x cols` matrix. Fails with `InvalidInput`
+    // for an empty or overflowing size.
+    //
+    // Safety: the file must not be truncated or written by anyone else while the matrix is alive.
+    #[allow(clippy::missing_safety_doc)]
+    pub unsafe fn create<P: AsRef<Path>>(path: P, rows: usize, cols: usize) -> std::io::Result<Self> {
+        let len = byte_len(rows, cols)?;
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(true).open(path)?;
+        file.set_len(len)?;
+        // SAFETY: forwarded to the caller.
+        return unsafe { Self::map(&file, rows, cols) };
+    }
+
+    // Maps an existing file, which must hold exactly a `rows x cols` matrix. Fails with `InvalidInput` for an empty
+    // or overflowing size and with `InvalidData` if the file length does not match.
+    //
+    // Safety: the file must not be truncated or written by anyone else while the matrix is alive.
+    #[allow(clippy::missing_safety_doc)]
+    pub unsafe fn open<P: AsRef<Path>>(path: P, rows: usize, cols: usize) -> std::io::Result<Self> {
+        let len = byte_len(rows, cols)?;
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        if file.metadata()?.len() != len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("file size does not match a {}x{} f64 matrix", rows, cols),
+            ));
+        }
+        // SAFETY: forwarded to the caller.
+        return unsafe { Self::map(&file, rows, cols) };
+    }
+
+    unsafe fn map(file: &File, rows: usize, cols: usize) -> std::io::Result<Self> {
+        // SAFETY: the callers of `create` and `open` guarantee that nobody else truncates or writes the file while
+        // the mapping exists.
+        let mmap = unsafe { MmapMut::map_mut(file)? };
+        return Ok(Self { mmap, rows, cols });
+    }
+
+    pub fn rows(&self) -> usize {
+        return self.rows;
+    }
+
+    pub fn cols(&self) -> usize {
+        return self.cols;
+    }
+
+    // Copies column `col` into `out`. Panics unless `col < cols` and `out` holds exactly `rows` values.
+    pub fn read_column(&self, col: usize, out: &mut [f64]) {
+        self.check_column(col, out.len());
+        let start = col * self.rows * F64_SIZE;
+        let bytes = &self.mmap[start..start + self.rows * F64_SIZE];
+        for (value, chunk) in out.iter_mut().zip(bytes.chunks_exact(F64_SIZE)) {
+            *value = f64::from_le_bytes(chunk.try_into().unwrap());
+        }
+    }
+
+    // Overwrites column `col` with `values`. Panics unless `col < cols` and `values` holds exactly `rows` values.
+    pub fn write_column(&mut self, col: usize, values: &[f64]) {
+        self.check_column(col, values.len());
+        let start = col * self.rows * F64_SIZE;
+        let bytes = &mut self.mmap[start..start + self.rows * F64_SIZE];
+        for (value, chunk) in values.iter().zip(bytes.chunks_exact_mut(F64_SIZE)) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    fn check_column(&self, col: usize, len: usize) {
+        assert!(col < self.cols, "column {} out of range for a matrix with {} columns", col, self.cols);
+        assert_eq!(len, self.rows, "a column of this matrix has {} values, the buffer holds {}", self.rows, len);
+    }
+
+    // The columns viewed in place as fixed-size vectors, without copying. Needs `V::DIM == rows`; the mapping is page
+    // aligned, so any vector alignment is satisfied. Only on little-endian targets, where the file layout is the
+    // in-memory one.
+    #[cfg(all(feature = "bytemuck", target_endian = "little"))]
+    pub fn columns_as<V: crate::Vector + bytemuck::Pod>(&self) -> &[V] {
+        assert_eq!(V::DIM, self.rows, "vector dimension must match the row count");
+        return bytemuck::cast_slice(&self.mmap[..]);
+    }
+
+    #[cfg(all(feature = "bytemuck", target_endian = "little"))]
+    pub fn columns_as_mut<V: crate::Vector + bytemuck::Pod>(&mut self) -> &mut [V] {
+        assert_eq!(V::DIM, self.rows, "vector dimension must match the row count");
+        return bytemuck::cast_slice_mut(&mut self.mmap[..]);
+    }
+
+    pub fn flush(&self) -> std::io::Result<()> {
+        return self.mmap.flush();
+    }
+
+    // Orthonormalizes the columns in place, `panel_width` columns at a time. Every panel is orthogonalized against
+    // the already finished columns, which are streamed in one by one, and then among itself, so at most
+    // `panel_width + 1` columns are held in memory.
+    pub fn gram_schmidt(&mut self, panel_width: usize) -> std::io::Result<()> {
+        let panel_width = panel_width.max(1);
+        let mut previous = vec![0.0; self.rows];
+        for start in (0..self.cols).step_by(panel_width) {
+            let end = (start + panel_width).min(self.cols);
+            let mut panel: Vec<Vec<f64>> = (start..end).map(|col| {
+                let mut column = vec![0.0; self.rows];
+                self.read_column(col, &mut column);
+                return column;
+            }).collect();
+
+            for col in 0..start {
+                self.read_column(col, &mut previous);
+                for column in panel.iter_mut() {
+                    subtract_projection(column, &previous);
+                }
+            }
+            for j in 0..panel.len() {
+                let (done, rest) = panel.split_at_mut(j);
+                let column = &mut rest[0];
+                for q in done.iter() {
+                    subtract_projection(column, q);
+                }
+                let norm = dot(column, column).sqrt();
+                column.iter_mut().for_each(|c| *c /= norm);
+            }
+
+            for (offset, column) in panel.iter().enumerate() {
+                self.write_column(start + offset, column);
+            }
+        }
+        return self.flush();
+    }
+}
+
+// File length in bytes of a `rows x cols` matrix. Empty matrices are rejected, since an empty file cannot be mapped
+// portably.
+#[cfg(feature = "mmap")]
+fn byte_len(rows: usize, cols: usize) -> std::io::Result<u64> {
+    let len = rows.checked_mul(cols)
+        .and_then(|n| n.checked_mul(F64_SIZE))
+        .and_then(|n| u64::try_from(n).ok())
+        .ok_or_else(|| std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("a {}x{} f64 matrix does not fit in memory", rows, cols),
+        ))?;
+    if len == 0 {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "matrix must not be empty"));
+    }
+    return Ok(len);
+}
+
+#[cfg(feature = "mmap")]
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    return a.iter().zip(b).map(|(x, y)| x * y).sum();
+}
+
+#[cfg(feature = "mmap")]
+fn subtract_projection(column: &mut [f64], q: &[f64]) {
+    let d = dot(column, q);
+    for (c, qi) in column.iter_mut().zip(q) {
+        *c -= d * qi;
+    }
+}
+
+#[cfg(all(test, feature = "mmap"))]
+mod mmap_matrix_test {
+    use crate::io::MmapMatrix;
+
+    // Miri cannot emulate file mappings.
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    fn orthonormalizes_on_disk() {
+        let path = std::env::temp_dir().join(format!("gram_schmidt_mmap_{}.bin", std::process::id()));
+        let columns = [
+            [1.0, 1.0, 1.0, 1.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0, 1.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ];
+        // SAFETY: the file is private to this test.
+        let mut matrix = unsafe { MmapMatrix::create(&path, 4, 4) }.unwrap();
+        for (j, column) in columns.iter().enumerate() {
+            matrix.write_column(j, column);
+        }
+        matrix.gram_schmidt(3).unwrap();
+        drop(matrix);
+
+        // SAFETY: as above.
+        let matrix = unsafe { MmapMatrix::open(&path, 4, 4) }.unwrap();
+        let expected = [
+            [0.5, 0.5, 0.5, 0.5],
+            [-0.5, 0.5, -0.5, 0.5],
+            [-0.5, -0.5, 0.5, 0.5],
+            [0.5, -0.5, -0.5, 0.5],
+        ];
+        let mut column = [0.0; 4];
+        for (j, expected) in expected.iter().enumerate() {
+            matrix.read_column(j, &mut column);
+            for i in 0..4 {
+                assert!((column[i] - expected[i]).abs() < 1e-12);
+            }
+        }
+        // SAFETY: as above.
+        unsafe {
+            assert_eq!(MmapMatrix::open(&path, 4, 5).err().unwrap().kind(), std::io::ErrorKind::InvalidData);
+            assert_eq!(MmapMatrix::open(&path, 0, 4).err().unwrap().kind(), std::io::ErrorKind::InvalidInput);
+            assert_eq!(MmapMatrix::open(&path, usize::MAX, 2).err().unwrap().kind(), std::io::ErrorKind::InvalidInput);
+        }
+        #[cfg(all(feature = "bytemuck", target_endian = "little"))]
+        {
+            // SAFETY: as above.
+            let mut matrix = unsafe { MmapMatrix::open(&path, 4, 4) }.unwrap();
+            let columns = matrix.columns_as_mut::<crate::AlignedVector4>();
+            assert_eq!(columns[1].as_slice(), expected[1]);
+            columns[3] = crate::AlignedVector4::E0;
+            matrix.read_column(3, &mut column);
+            assert_eq!(column, [1.0, 0.0, 0.0, 0.0]);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    fn scratch_matrix(name: &str) -> (std::path::PathBuf, MmapMatrix) {
+        let path = std::env::temp_dir().join(format!("gram_schmidt_mmap_{}_{}.bin", name, std::process::id()));
+        // SAFETY: the file is private to this test.
+        let matrix = unsafe { MmapMatrix::create(&path, 3, 2) }.unwrap();
+        return (path, matrix);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    #[should_panic(expected = "column 2 out of range")]
+    fn rejects_columns_past_the_end() {
+        let (path, matrix) = scratch_matrix("past_the_end");
+        std::fs::remove_file(path).unwrap();
+        matrix.read_column(2, &mut [0.0; 3]);
+    }
+
+    #[cfg_attr(miri, ignore)]
+    #[test]
+    #[should_panic(expected = "has 3 values, the buffer holds 2")]
+    fn rejects_short_buffers() {
+        let (path, mut matrix) = scratch_matrix("short_buffer");
+        std::fs::remove_file(path).unwrap();
+        matrix.write_column(1, &[1.0, 2.0]);
+    }
+}
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{try_gram_schmidt_storage, Error, Matrix, Vector};
+
+// JSON form of a basis: `{ "dim": n, "vectors": [[...], ...] }`, every vector holding `dim` components.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BasisDocument {
+    pub dim: usize,
+    pub vectors: Vec<Vec<f64>>,
+}
+
+// JSON form of a QR factorization: the columns of `Q` as a basis document and `R` as a list of rows.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FactorizationDocument {
+    pub q: BasisDocument,
+    pub r: Vec<Vec<f64>>,
+}
+
+fn parse<'a, T: Deserialize<'a>>(json: &'a str) -> Result<T, Error> {
+    return serde_json::from_str(json).map_err(|e| Error::InvalidJson { line: e.line(), column: e.column() });
+}
+
+impl BasisDocument {
+    pub fn from_vectors<V: Vector>(vectors: &[V]) -> Self {
+        let vectors = vectors.iter().map(|v| (0..V::DIM).map(|i| v[i]).collect()).collect();
+        return Self { dim: V::DIM, vectors };
+    }
+
+    // Fails unless `dim` matches `V::DIM` and every vector has `dim` components.
+    pub fn to_vectors<V: Vector>(&self) -> Result<Vec<V>, Error> {
+        self.validate()?;
+        if self.dim != V::DIM {
+            return Err(Error::DimensionMismatch { expected: V::DIM, found: self.dim });
+        }
+        return Ok(self.vectors.iter().map(|components| {
+            let mut v = V::zero();
+            v.get_components_mut().copy_from_slice(components);
+            return v;
+        }).collect());
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let document: Self = parse(json)?;
+        document.validate()?;
+        return Ok(document);
+    }
+
+    // Like `from_json`, but parses while reading, so the text never has to be in memory as a whole.
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<Self, Error> {
+        let document: Self = serde_json::from_reader(reader)
+            .map_err(|e| Error::InvalidJson { line: e.line(), column: e.column() })?;
+        document.validate()?;
+        return Ok(document);
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string(self).unwrap();
+    }
+
+    // Orthonormalizes the vectors in place; works for any `dim` chosen at runtime.
+    pub fn gram_schmidt(&mut self) -> Result<(), Error> {
+        self.validate()?;
+        return try_gram_schmidt_storage(&mut self.vectors);
+    }
+
+    fn validate(&self) -> Result<(), Error> {
+        if let Some(v) = self.vectors.iter().find(|v| v.len() != self.dim) {
+            return Err(Error::DimensionMismatch { expected: self.dim, found: v.len() });
+        }
+        return Ok(());
+    }
+}
+
+impl FactorizationDocument {
+    pub fn from_qr(q: &Matrix, r: &Matrix) -> Self {
+        let vectors = (0..q.cols()).map(|j| q.column(j).to_vec()).collect();
+        let r = (0..r.rows()).map(|i| (0..r.cols()).map(|j| r[(i, j)]).collect()).collect();
+        return Self { q: BasisDocument { dim: q.rows(), vectors }, r };
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let document: Self = parse(json)?;
+        document.q.validate()?;
+        return Ok(document);
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string(self).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod json_test {
+    use crate::json::{BasisDocument, FactorizationDocument};
+    use crate::{householder_qr, Error, Matrix, Vector3};
+
+    #[test]
+    fn round_trips_bases_and_factorizations() {
+        let mut document = BasisDocument::from_json(r#"{ "dim": 3, "vectors": [[3, 4, 0], [1, 1, 1]] }"#).unwrap();
+        document.gram_schmidt().unwrap();
+        assert_eq!(document.vectors[0], vec![0.6, 0.8, 0.0]);
+        let vectors: Vec<Vector3> = document.to_vectors().unwrap();
+        assert_eq!(BasisDocument::from_vectors(&vectors), document);
+        assert_eq!(BasisDocument::from_json(&document.to_json()), Ok(document));
+
+        assert_eq!(BasisDocument::from_json(r#"{"dim": 2, "vectors": [[1, 2, 3]]}"#),
+                   Err(Error::DimensionMismatch { expected: 2, found: 3 }));
+        assert_eq!(BasisDocument::from_json("{\n  \"dim\": }"), Err(Error::InvalidJson { line: 2, column: 10 }));
+        assert_eq!(BasisDocument::from_reader(r#"{"dim": 1, "vectors": [[2]]}"#.as_bytes()),
+                   Ok(BasisDocument { dim: 1, vectors: vec![vec![2.0]] }));
+        assert_eq!(BasisDocument::from_reader("{\n  \"dim\": }".as_bytes()), Err(Error::InvalidJson { line: 2, column: 10 }));
+
+        let (q, r) = householder_qr(&Matrix::from_rows(&[vec![1.0, 2.0], vec![0.0, 1.0], vec![1.0, 0.0]]));
+        let factorization = FactorizationDocument::from_qr(&q, &r);
+        assert_eq!(factorization.r[1][0], 0.0);
+        assert_eq!(FactorizationDocument::from_json(&factorization.to_json()), Ok(factorization));
+    }
+}
@@ -0,0 +1,123 @@
+use crate::eigen::symmetric_eigen;
+use crate::warm_start::cholesky_upper;
+use crate::{givens, Error, Matrix};
+
+// Decorrelates the covariance `cov` through the symmetric eigen-decomposition: returns an orthogonal `Q` and the
+// diagonal `D = Qᵀ cov Q`, so `y = Qᵀ x` has independent components with variances on the diagonal of `D`, in
+// ascending order. Fails if the eigensolver does not converge, which happens for non-finite entries.
+pub fn decorrelate(cov: &Matrix) -> Result<(Matrix, Matrix), Error> {
+    let (variances, q) = symmetric_eigen(cov)?;
+    let mut diagonal = Matrix::zeros(variances.len(), variances.len());
+    for (i, variance) in variances.into_iter().enumerate() {
+        diagonal[(i, i)] = variance;
+    }
+    return Ok((q, diagonal));
+}
+
+// Lower triangular square root `S` with `S Sᵀ = cov`, the factor propagated by square-root Kalman filters. It is the
+// transposed `R` of a QR factorization of any `A` with `Aᵀ A = cov`, computed here by Cholesky. `None` if `cov` is not
+// numerically positive definite.
+pub fn sqrt_factor(cov: &Matrix) -> Option<Matrix> {
+    let rows: Vec<Vec<f64>> = (0..cov.rows()).map(|i| (0..cov.cols()).map(|j| cov[(i, j)]).collect()).collect();
+    let r = cholesky_upper(&rows)?;
+    return Some(Matrix::from_columns(&r));
+}
+
+// Square-root Kalman update for one scalar measurement `z = h x + v` with noise variance `r`. Triangularizes the
+// pre-array `[√r, h S; 0, S]` with Givens rotations into `[√α, 0; K̄, S⁺]`, where `α = r + h P hᵀ` is the innovation
+// variance. Returns the updated lower triangular factor `S⁺` with `S⁺ S⁺ᵀ = P - P hᵀ h P / α` and the gain
+// `K = K̄ / √α`, so the state is updated with `x + K (z - h x)`. The covariance itself is never formed.
+pub fn qr_measurement_update(sqrt_cov: &Matrix, h_row: &[f64], r: f64) -> (Matrix, Vec<f64>) {
+    let n = sqrt_cov.rows();
+    assert_eq!(h_row.len(), n, "the measurement row must match the state dimension");
+    assert!(r > 0.0, "the measurement noise variance must be positive");
+    let mut pre = Matrix::zeros(n + 1, n + 1);
+    pre[(0, 0)] = r.sqrt();
+    for j in 0..n {
+        pre[(0, j + 1)] = (0..n).map(|k| h_row[k] * sqrt_cov[(k, j)]).sum();
+        for i in 0..n {
+            pre[(i + 1, j + 1)] = sqrt_cov[(i, j)];
+        }
+    }
+    // Rotating the last columns first keeps the lower right block triangular.
+    for j in (1..=n).rev() {
+        let (c, s, _) = givens(pre[(0, 0)], pre[(0, j)]);
+        for i in 0..=n {
+            let (a, b) = (pre[(i, 0)], pre[(i, j)]);
+            pre[(i, 0)] = c * a + s * b;
+            pre[(i, j)] = -s * a + c * b;
+        }
+    }
+    let innovation = pre[(0, 0)];
+    let gain = (1..=n).map(|i| pre[(i, 0)] / innovation).collect();
+    let columns: Vec<Vec<f64>> = (1..=n).map(|j| pre.column(j)[1..].to_vec()).collect();
+    return (Matrix::from_columns(&columns), gain);
+}
+
+#[cfg(test)]
+mod kalman_test {
+    use crate::{decorrelate, qr_measurement_update, sqrt_factor, Matrix};
+
+    fn covariance() -> Matrix {
+        return Matrix::from_rows(&[vec![4.0, 1.2, -0.6], vec![1.2, 2.0, 0.3], vec![-0.6, 0.3, 1.0]]);
+    }
+
+    fn assert_close(a: &Matrix, b: &Matrix) {
+        for j in 0..a.cols() {
+            for i in 0..a.rows() {
+                assert!((a[(i, j)] - b[(i, j)]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn decorrelation_diagonalizes() {
+        let (q, d) = decorrelate(&covariance()).unwrap();
+        assert_close(&(&q.transpose() * &q), &Matrix::identity(3));
+        assert_close(&(&(&q * &d) * &q.transpose()), &covariance());
+        assert!(d[(0, 0)] <= d[(1, 1)] && d[(1, 1)] <= d[(2, 2)]);
+    }
+
+    #[test]
+    fn decorrelation_of_small_covariances() {
+        // Position variances around 1e-16 are common; the transform must not depend on the scale.
+        let mut cov = covariance();
+        (0..3).for_each(|j| cov.column_mut(j).iter_mut().for_each(|x| *x *= 1e-16));
+        let (q, d) = decorrelate(&cov).unwrap();
+        assert_close(&(&q.transpose() * &q), &Matrix::identity(3));
+        let rotated = &(&q.transpose() * &cov) * &q;
+        for j in 0..3 {
+            for i in 0..3 {
+                assert!((rotated[(i, j)] - d[(i, j)]).abs() < 1e-12 * 1e-16);
+            }
+        }
+        assert!(d[(0, 0)] > 0.0 && d[(0, 0)] < d[(1, 1)] && d[(1, 1)] < d[(2, 2)]);
+    }
+
+    #[test]
+    fn square_root_factor() {
+        let s = sqrt_factor(&covariance()).unwrap();
+        assert_close(&(&s * &s.transpose()), &covariance());
+        assert!((0..3).all(|j| (0..j).all(|i| s[(i, j)] == 0.0)));
+        assert!(sqrt_factor(&Matrix::from_rows(&[vec![1.0, 2.0], vec![2.0, 1.0]])).is_none());
+    }
+
+    #[test]
+    fn measurement_update_matches_covariance_form() {
+        let p = covariance();
+        let (h, r) = ([1.0, -0.5, 2.0], 0.3);
+        let (updated, gain) = qr_measurement_update(&sqrt_factor(&p).unwrap(), &h, r);
+
+        let ph: Vec<f64> = (0..3).map(|i| (0..3).map(|k| p[(i, k)] * h[k]).sum()).collect();
+        let alpha = r + (0..3).map(|i| h[i] * ph[i]).sum::<f64>();
+        let mut expected = p.clone();
+        for j in 0..3 {
+            assert!((gain[j] - ph[j] / alpha).abs() < 1e-12);
+            for i in 0..3 {
+                expected[(i, j)] -= ph[i] * ph[j] / alpha;
+            }
+        }
+        assert_close(&(&updated * &updated.transpose()), &expected);
+        assert!((0..3).all(|j| (0..j).all(|i| updated[(i, j)] == 0.0)));
+    }
+}
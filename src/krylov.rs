@@ -0,0 +1,103 @@
+use crate::basis::extend_orthonormal;
+use crate::{Matrix, Vector};
+
+// Relative size below which a new Krylov direction is treated as already contained in the basis.
+pub(crate) const DEFLATION_TOLERANCE: f64 = 1e-10;
+
+// Block Arnoldi process. Starting from the orthonormalized `start` block, every step applies `op` to the vectors of
+// the newest block and orthonormalizes the results against the whole basis and among themselves (with one
+// reorthogonalization pass). Directions that turn out to be linearly dependent are deflated, so the block size can
+// shrink and the process stops early once the Krylov space is invariant.
+//
+// Returns the basis `Q` and the block upper Hessenberg `H` with `basis.len()` rows and one column per application
+// of `op`, such that `op(Q[j]) = sum_i H[(i, j)] * Q[i]` for every vector `Q[j]` that `op` was applied to.
+pub fn block_arnoldi<V: Vector, F: Fn(&V) -> V>(op: F, start: &[V], steps: usize) -> (Vec<V>, Matrix) {
+    let mut basis = vec![];
+    extend_orthonormal(&mut basis, start.iter().cloned());
+    let mut columns: Vec<Vec<f64>> = vec![];
+    let mut block = 0..basis.len();
+    for _ in 0..steps {
+        if block.is_empty() {
+            break;
+        }
+        let next_block_start = basis.len();
+        for index in block {
+            let mut w = op(&basis[index]);
+            let scale = w.length();
+            let mut column = vec![0.0; basis.len()];
+            for _ in 0..2 {
+                for (i, q) in basis.iter().enumerate() {
+                    let dot = V::dot_product(q, &w);
+                    w.axpy(-dot, q);
+                    column[i] += dot;
+                }
+            }
+            #[cfg(feature = "metrics")]
+            crate::telemetry::reorthogonalization_passes("block_arnoldi", 1);
+            let norm = w.length();
+            if norm > DEFLATION_TOLERANCE * scale {
+                basis.push(w / norm);
+                column.push(norm);
+            }
+            columns.push(column);
+        }
+        block = next_block_start..basis.len();
+    }
+
+    let mut h = Matrix::zeros(basis.len(), columns.len());
+    for (j, column) in columns.iter().enumerate() {
+        for (i, value) in column.iter().enumerate() {
+            h[(i, j)] = *value;
+        }
+    }
+    return (basis, h);
+}
+
+// Single-vector Arnoldi process, see `block_arnoldi`. `arnoldi_distributed` runs the same process on vectors spread over
+// several processes.
+pub fn arnoldi<V: Vector, F: Fn(&V) -> V>(op: F, start: &V, steps: usize) -> (Vec<V>, Matrix) {
+    return block_arnoldi(op, std::slice::from_ref(start), steps);
+}
+
+#[cfg(test)]
+mod krylov_test {
+    use crate::{arnoldi, block_arnoldi, Vector, Vector4, Matrix};
+
+    fn op(v: &Vector4) -> Vector4 {
+        return Vector4::new([2.0 * v[0] + v[1], v[0] + 3.0 * v[1], 4.0 * v[2] - v[3], 5.0 * v[3]]);
+    }
+
+    fn assert_arnoldi_relation(basis: &[Vector4], h: &Matrix) {
+        for j in 0..h.cols() {
+            let mut reconstructed = Vector4::empty();
+            for (i, q) in basis.iter().enumerate() {
+                reconstructed.axpy(h[(i, j)], q);
+            }
+            assert!((op(&basis[j]) - reconstructed).length() < 1e-12);
+        }
+        for (i, a) in basis.iter().enumerate() {
+            for (j, b) in basis.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((Vector4::dot_product(a, b) - expected).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn block_relation_holds() {
+        let start = [Vector4::new([1.0, 0.0, 1.0, 0.0]), Vector4::new([0.0, 1.0, 0.0, 1.0])];
+        let (basis, h) = block_arnoldi(op, &start, 1);
+        assert_eq!((basis.len(), h.rows(), h.cols()), (4, 4, 2));
+        assert_arnoldi_relation(&basis, &h);
+        // The lower block of H is upper triangular.
+        assert!(h[(3, 0)].abs() < 1e-12);
+    }
+
+    #[test]
+    fn stops_on_invariant_subspace() {
+        let (basis, h) = arnoldi(op, &Vector4::new([1.0, 1.0, 0.0, 0.0]), 10);
+        assert_eq!(basis.len(), 2);
+        assert_eq!(h.cols(), 2);
+        assert_arnoldi_relation(&basis, &h);
+    }
+}
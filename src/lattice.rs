@@ -0,0 +1,399 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::Error;
+
+// Exact Gram-Schmidt data of an integer lattice basis `b_0, ..., b_{n-1}`, without normalization, in the integral
+// representation LLL and BKZ work with: the Gram determinants `d_0 = 1, d_{k+1} = d_k ‖b*_k‖²` and the integers
+// `λ_{k,j} = d_{j+1} μ_{k,j}` for `j < k`, so that `b_k = b*_k + Σ_{j<k} μ_{k,j} b*_j`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatticeGramSchmidt {
+    d: Vec<BigInt>,
+    lambda: Vec<Vec<BigInt>>,
+}
+
+impl LatticeGramSchmidt {
+    // Fails if the vectors do not all have the same length or are linearly dependent. Uses only exact integer
+    // divisions (Cohen, Algorithm 2.6.7).
+    pub fn new(basis: &[Vec<BigInt>]) -> Result<Self, Error> {
+        if let Some(first) = basis.first() {
+            for v in basis {
+                if v.len() != first.len() {
+                    return Err(Error::DimensionMismatch { expected: first.len(), found: v.len() });
+                }
+            }
+        }
+        let mut d = vec![BigInt::one()];
+        let mut lambda: Vec<Vec<BigInt>> = Vec::with_capacity(basis.len());
+        for k in 0..basis.len() {
+            let mut row = Vec::with_capacity(k);
+            for j in 0..=k {
+                let mut u = dot(&basis[k], &basis[j]);
+                for i in 0..j {
+                    let lambda_j = if j < k { &lambda[j][i] } else { &row[i] };
+                    u = (&d[i + 1] * u - &row[i] * lambda_j) / &d[i];
+                }
+                if j < k {
+                    row.push(u);
+                } else if u.is_zero() {
+                    return Err(Error::ZeroVector { vector: k });
+                } else {
+                    d.push(u);
+                }
+            }
+            lambda.push(row);
+        }
+        return Ok(Self { d, lambda });
+    }
+
+    pub fn len(&self) -> usize {
+        return self.lambda.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.lambda.is_empty();
+    }
+
+    // `μ_{k,j} = <b_k, b*_j> / ‖b*_j‖²` for `j < k`.
+    pub fn mu(&self, k: usize, j: usize) -> BigRational {
+        assert!(j < k, "mu is only defined below the diagonal");
+        return BigRational::new(self.lambda[k][j].clone(), self.d[j + 1].clone());
+    }
+
+    // `‖b*_k‖²`.
+    pub fn squared_norm(&self, k: usize) -> BigRational {
+        return BigRational::new(self.d[k + 1].clone(), self.d[k].clone());
+    }
+
+    // `d_0 = 1, ..., d_n`, where `d_k` is the determinant of the Gram matrix of the first `k` vectors.
+    pub fn gram_determinants(&self) -> &[BigInt] {
+        return &self.d;
+    }
+
+    // Row `k` holds `λ_{k,0}, ..., λ_{k,k-1}`.
+    pub fn lambda(&self) -> &[Vec<BigInt>] {
+        return &self.lambda;
+    }
+}
+
+// Subtracts `q b_j` from `b_k` for `j < k`, updating the Gram-Schmidt data in place (the `d_i` do not change).
+fn subtract_multiple(basis: &mut [Vec<BigInt>], gs: &mut LatticeGramSchmidt, k: usize, j: usize, q: &BigInt) {
+    let (head, tail) = basis.split_at_mut(k);
+    for (x, y) in tail[0].iter_mut().zip(&head[j]) {
+        *x -= q * y;
+    }
+    for i in 0..j {
+        let update = q * &gs.lambda[j][i];
+        gs.lambda[k][i] -= update;
+    }
+    gs.lambda[k][j] -= q * &gs.d[j + 1];
+}
+
+// Makes `|μ_{k,j}| ≤ 1/2` for `j < k` by subtracting integer multiples of the earlier vectors.
+fn size_reduce_vector(basis: &mut [Vec<BigInt>], gs: &mut LatticeGramSchmidt, k: usize) {
+    for j in (0..k).rev() {
+        let q = gs.mu(k, j).round().to_integer();
+        if !q.is_zero() {
+            subtract_multiple(basis, gs, k, j, &q);
+        }
+    }
+}
+
+// Makes every `|μ_{k,j}| ≤ 1/2`, which keeps the lattice and the Gram-Schmidt vectors `b*_k` unchanged.
+pub fn size_reduce(basis: &mut [Vec<BigInt>]) -> Result<LatticeGramSchmidt, Error> {
+    let mut gs = LatticeGramSchmidt::new(basis)?;
+    for k in 1..basis.len() {
+        size_reduce_vector(basis, &mut gs, k);
+    }
+    return Ok(gs);
+}
+
+// LLL reduction with parameter `1/4 < delta ≤ 1` (typically 0.99): size reduces and swaps neighbours until
+// `‖b*_k‖² ≥ (δ - μ_{k,k-1}²) ‖b*_{k-1}‖²` for every `k`. All decisions are made in exact arithmetic; the
+// Gram-Schmidt data are recomputed after every swap, which is simple but only meant for small dimensions.
+pub fn lll(basis: &mut [Vec<BigInt>], delta: f64) -> Result<LatticeGramSchmidt, Error> {
+    assert!(delta > 0.25 && delta <= 1.0, "delta must be in (1/4, 1]");
+    let delta = BigRational::from_float(delta).unwrap();
+    let mut gs = LatticeGramSchmidt::new(basis)?;
+    let mut k = 1;
+    while k < basis.len() {
+        size_reduce_vector(basis, &mut gs, k);
+        let mu = gs.mu(k, k - 1);
+        if gs.squared_norm(k) >= (&delta - &mu * &mu) * gs.squared_norm(k - 1) {
+            k += 1;
+        } else {
+            basis.swap(k, k - 1);
+            gs = LatticeGramSchmidt::new(basis)?;
+            k = (k - 1).max(1);
+        }
+    }
+    return Ok(gs);
+}
+
+// Integer coefficients `x` of a shortest nonzero vector of the projected block lattice `π_start(L(b_start, ...,
+// b_{end-1}))`, i.e. minimizing `‖π_start(Σ x_i b_{start+i})‖²`, found by enumeration. The pruning uses the
+// Gram-Schmidt data in `f64`, which is accurate for the moderately sized entries this is meant for. The cost grows
+// exponentially with the block size.
+pub fn shortest_in_block(gs: &LatticeGramSchmidt, start: usize, end: usize) -> Vec<BigInt> {
+    assert!(start < end && end <= gs.len(), "block must be a non-empty range of the basis");
+    let n = end - start;
+    let norms: Vec<f64> = (start..end).map(|k| gs.squared_norm(k).to_f64().unwrap()).collect();
+    let mu: Vec<Vec<f64>> = (0..n)
+        .map(|j| (0..j).map(|i| gs.mu(start + j, start + i).to_f64().unwrap()).collect())
+        .collect();
+
+    let mut best = vec![0i64; n];
+    best[0] = 1;
+    // Only strictly shorter vectors replace `b_start`, with some slack for the rounding of the pruning bound.
+    let mut radius = norms[0] * (1.0 - 1e-9);
+    let mut x = vec![0i64; n];
+    enumerate(&norms, &mu, n, 0.0, &mut x, &mut radius, &mut best);
+    return best.into_iter().map(BigInt::from).collect();
+}
+
+// Depth-first search over the coefficients `x_{level-1}, ..., x_0`, with `partial` the squared projected length
+// contributed by the coefficients already fixed.
+fn enumerate(norms: &[f64], mu: &[Vec<f64>], level: usize, partial: f64, x: &mut [i64], radius: &mut f64,
+             best: &mut [i64]) {
+    if level == 0 {
+        if partial > 0.0 && partial < *radius {
+            *radius = partial;
+            best.copy_from_slice(x);
+        }
+        return;
+    }
+    let i = level - 1;
+    let center: f64 = -(level..x.len()).map(|j| x[j] as f64 * mu[j][i]).sum::<f64>();
+    let spread = ((*radius - partial) / norms[i]).max(0.0).sqrt();
+    let (low, high) = ((center - spread).ceil() as i64, (center + spread).floor() as i64);
+    for value in low..=high {
+        let length = partial + (value as f64 - center).powi(2) * norms[i];
+        if length < *radius {
+            x[i] = value;
+            enumerate(norms, mu, i, length, x, radius, best);
+        }
+    }
+    x[i] = 0;
+}
+
+// Inserts `v = Σ x_i b_{start+i}`, a primitive vector of the block, at position `start` by a unimodular change of
+// the block basis, so the lattice is unchanged.
+fn insert_primitive(basis: &mut [Vec<BigInt>], start: usize, mut x: Vec<BigInt>) {
+    // Euclid on the coefficients: `x_i w_i + x_j w_j = (x_i - q x_j) w_i + x_j (w_j + q w_i)`.
+    loop {
+        let mut nonzero: Vec<usize> = (0..x.len()).filter(|&i| !x[i].is_zero()).collect();
+        if nonzero.len() <= 1 {
+            break;
+        }
+        nonzero.sort_by(|&a, &b| x[b].abs().cmp(&x[a].abs()));
+        let (i, j) = (nonzero[0], nonzero[nonzero.len() - 1]);
+        let q = &x[i] / &x[j];
+        x[i] = &x[i] - &q * &x[j];
+        let w_i = basis[start + i].clone();
+        for (a, b) in basis[start + j].iter_mut().zip(&w_i) {
+            *a += &q * b;
+        }
+    }
+    let k = (0..x.len()).find(|&i| !x[i].is_zero()).unwrap();
+    if x[k].is_negative() {
+        basis[start + k].iter_mut().for_each(|c| *c = -c.clone());
+    }
+    basis[start..=start + k].rotate_right(1);
+}
+
+// BKZ with blocks of `block_size` vectors: LLL, then tours replacing the first vector of every block by a shortest
+// vector of its projected block until a tour changes nothing. Returns the Gram-Schmidt data of the reduced basis.
+pub fn bkz(basis: &mut [Vec<BigInt>], block_size: usize, delta: f64) -> Result<LatticeGramSchmidt, Error> {
+    let mut gs = lll(basis, delta)?;
+    let n = basis.len();
+    loop {
+        let mut changed = false;
+        for start in 0..n.saturating_sub(1) {
+            let end = (start + block_size.max(2)).min(n);
+            let x = shortest_in_block(&gs, start, end);
+            if x[0].is_one() && x[1..].iter().all(|c| c.is_zero()) {
+                continue;
+            }
+            insert_primitive(basis, start, x);
+            gs = lll(basis, delta)?;
+            changed = true;
+        }
+        if !changed {
+            return Ok(gs);
+        }
+    }
+}
+
+// Babai's nearest-plane algorithm: a lattice vector close to `target`, within `½ (Σ ‖b*_k‖²)^½` of its projection
+// onto the span of `basis` (the covering bound of the Gram-Schmidt box). `gs` must be the Gram-Schmidt data of
+// `basis`, e.g. as returned by `lll`, whose `μ_{k,j}` and `‖b*_k‖²` are reused, so the result is exact. On an
+// LLL-reduced basis it is within `2^(n/2)` times the distance of the closest vector.
+pub fn babai_nearest_plane(basis: &[Vec<BigInt>], gs: &LatticeGramSchmidt, target: &[BigRational])
+                           -> Result<Vec<BigInt>, Error> {
+    assert_eq!(basis.len(), gs.len(), "gs must be the Gram-Schmidt data of basis");
+    let n = basis.len();
+    let dim = basis.first().map_or(target.len(), |b| b.len());
+    if target.len() != dim {
+        return Err(Error::DimensionMismatch { expected: dim, found: target.len() });
+    }
+    // Coordinates `τ_k = <t, b*_k> / ‖b*_k‖²` of the target along the Gram-Schmidt vectors, from
+    // `<t, b*_k> = <t, b_k> - Σ_{j<k} μ_{k,j} <t, b*_j>`.
+    let mut projections: Vec<BigRational> = Vec::with_capacity(n);
+    for (k, b) in basis.iter().enumerate() {
+        let mut projection: BigRational = target.iter().zip(b).map(|(t, c)| t * BigRational::from(c.clone())).sum();
+        for (j, previous) in projections.iter().enumerate() {
+            projection -= gs.mu(k, j) * previous;
+        }
+        projections.push(projection);
+    }
+    let mut tau: Vec<BigRational> = projections.iter().enumerate().map(|(k, p)| p / gs.squared_norm(k)).collect();
+
+    // Subtracting `x_k b_k` shifts the coordinates below `k` by `x_k μ_{k,j}`.
+    let mut x = vec![BigInt::zero(); n];
+    for k in (0..n).rev() {
+        x[k] = tau[k].round().to_integer();
+        let coefficient = BigRational::from(x[k].clone());
+        for (j, t) in tau[..k].iter_mut().enumerate() {
+            *t -= gs.mu(k, j) * &coefficient;
+        }
+    }
+    let mut closest = vec![BigInt::zero(); dim];
+    for (b, coefficient) in basis.iter().zip(&x) {
+        for (c, component) in closest.iter_mut().zip(b) {
+            *c += coefficient * component;
+        }
+    }
+    return Ok(closest);
+}
+
+fn dot(a: &[BigInt], b: &[BigInt]) -> BigInt {
+    return a.iter().zip(b).map(|(x, y)| x * y).sum();
+}
+
+#[cfg(test)]
+mod lattice_test {
+    use num_bigint::BigInt;
+    use num_rational::BigRational;
+    use num_traits::{Signed, Zero};
+
+    use crate::lattice::{babai_nearest_plane, bkz, lll, shortest_in_block, size_reduce, LatticeGramSchmidt};
+    use crate::Error;
+
+    fn lattice(rows: &[&[i64]]) -> Vec<Vec<BigInt>> {
+        return rows.iter().map(|row| row.iter().map(|&x| BigInt::from(x)).collect()).collect();
+    }
+
+    fn ratio(numerator: i64, denominator: i64) -> BigRational {
+        return BigRational::new(numerator.into(), denominator.into());
+    }
+
+    #[test]
+    fn exact_coefficients() {
+        let gs = LatticeGramSchmidt::new(&lattice(&[&[1, 1, 1], &[-1, 0, 2], &[3, 5, 6]])).unwrap();
+        assert_eq!(gs.mu(1, 0), ratio(1, 3));
+        assert_eq!(gs.mu(2, 0), ratio(14, 3));
+        assert_eq!(gs.mu(2, 1), ratio(13, 14));
+        assert_eq!(gs.squared_norm(0), ratio(3, 1));
+        assert_eq!(gs.squared_norm(1), ratio(14, 3));
+        assert_eq!(gs.squared_norm(2), ratio(9, 14));
+        // The last Gram determinant is the squared volume of the lattice.
+        assert_eq!(gs.gram_determinants()[3], BigInt::from(9));
+    }
+
+    #[test]
+    fn rejects_dependent_and_ragged_bases() {
+        let dependent = lattice(&[&[1, 2], &[2, 4]]);
+        assert_eq!(LatticeGramSchmidt::new(&dependent), Err(Error::ZeroVector { vector: 1 }));
+        let ragged = lattice(&[&[1, 2], &[2]]);
+        assert_eq!(LatticeGramSchmidt::new(&ragged), Err(Error::DimensionMismatch { expected: 2, found: 1 }));
+    }
+
+    #[test]
+    fn size_reduction_and_lll() {
+        let mut basis = lattice(&[&[1, 1, 1], &[-1, 0, 2], &[3, 5, 6]]);
+        let gs = size_reduce(&mut basis).unwrap();
+        for k in 1..3 {
+            for j in 0..k {
+                assert!(gs.mu(k, j).abs() <= ratio(1, 2));
+            }
+        }
+        assert_eq!(gs, LatticeGramSchmidt::new(&basis).unwrap());
+
+        let mut basis = lattice(&[&[1, 1, 1], &[-1, 0, 2], &[3, 5, 6]]);
+        let gs = lll(&mut basis, 0.75).unwrap();
+        assert_eq!(basis, lattice(&[&[0, 1, 0], &[1, 0, 1], &[-2, 0, 1]]));
+        assert_eq!(gs.gram_determinants()[3], BigInt::from(9));
+        for k in 1..3 {
+            let mu = gs.mu(k, k - 1);
+            assert!(gs.squared_norm(k) >= (ratio(3, 4) - &mu * &mu) * gs.squared_norm(k - 1));
+        }
+    }
+
+    #[test]
+    fn bkz_improves_on_lll() {
+        // Knapsack-style lattice: unit vectors with pseudo-random weights in the last column.
+        let mut state: u64 = 7;
+        let knapsack: Vec<Vec<BigInt>> = (0..8).map(|i| (0..8).map(|j| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            return BigInt::from(if j == 7 { (state >> 33) % 100000 } else { (i == j) as u64 });
+        }).collect()).collect();
+        let squared_length = |v: &[BigInt]| v.iter().map(|c| c * c).sum::<BigInt>();
+        let mut reduced = knapsack.clone();
+        lll(&mut reduced, 0.75).unwrap();
+
+        let mut basis = knapsack.clone();
+        let volume = LatticeGramSchmidt::new(&basis).unwrap().gram_determinants()[8].clone();
+        let gs = bkz(&mut basis, 4, 0.75).unwrap();
+        assert_eq!(gs.gram_determinants()[8], volume);
+        assert!(squared_length(&basis[0]) < squared_length(&reduced[0]));
+        // Every block's first vector is a shortest vector of its projected block.
+        let shortest = shortest_in_block(&gs, 0, 4);
+        assert_eq!(shortest, [1, 0, 0, 0].map(BigInt::from));
+    }
+
+    fn ratios(values: &[(i64, i64)]) -> Vec<BigRational> {
+        return values.iter().map(|&(n, d)| ratio(n, d)).collect();
+    }
+
+    #[test]
+    fn babai_exact_on_orthogonal_lattice() {
+        let basis = lattice(&[&[2, 0, 0], &[0, 3, 0], &[0, 0, 5]]);
+        let gs = LatticeGramSchmidt::new(&basis).unwrap();
+        let closest = babai_nearest_plane(&basis, &gs, &ratios(&[(29, 10), (-44, 10), (13, 1)])).unwrap();
+        assert_eq!(closest, [2, -3, 15].map(BigInt::from));
+        assert_eq!(babai_nearest_plane(&basis, &gs, &ratios(&[(1, 1)])),
+                   Err(Error::DimensionMismatch { expected: 3, found: 1 }));
+    }
+
+    #[test]
+    fn babai_within_covering_bound() {
+        let basis = lattice(&[&[2, 0], &[1, 4]]);
+        let gs = LatticeGramSchmidt::new(&basis).unwrap();
+        // `¼ Σ ‖b*_k‖²`, the squared covering bound.
+        let bound = ratio(4 + 16, 4);
+        for target in [[(3, 5), (9, 5)], [(-72, 5), (33, 5)], [(44, 5), (-51, 5)]] {
+            let target = ratios(&target);
+            let v = babai_nearest_plane(&basis, &gs, &target).unwrap();
+            // `v = x b_0 + y b_1` with integer coefficients.
+            let four = BigInt::from(4);
+            assert!((&v[1] % &four).is_zero() && ((&v[0] - &v[1] / &four) % BigInt::from(2)).is_zero());
+            let distance: BigRational = target.iter().zip(&v).map(|(t, c)| {
+                let d = t - BigRational::from(c.clone());
+                return &d * &d;
+            }).sum();
+            assert!(distance <= bound);
+        }
+    }
+
+    #[test]
+    fn babai_reuses_lll_output() {
+        let mut basis = lattice(&[&[1, 1, 1], &[-1, 0, 2], &[3, 5, 6]]);
+        let gs = lll(&mut basis, 0.75).unwrap();
+        // `2 b_0 - b_1 + 3 b_2` of the original basis, offset by less than half the shortest `‖b*_k‖`.
+        let target = ratios(&[(12, 1), (17, 1), (18, 1)]);
+        let offset = ratios(&[(1, 10), (-1, 10), (1, 5)]);
+        let target: Vec<BigRational> = target.iter().zip(&offset).map(|(t, o)| t + o).collect();
+        assert_eq!(babai_nearest_plane(&basis, &gs, &target).unwrap(), [12, 17, 18].map(BigInt::from));
+    }
+}
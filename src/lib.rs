@@ -1,248 +1,128 @@
 #![allow(clippy::needless_return)]
 
-use std::iter::Sum;
-use std::ops::{Add, Div, Index, IndexMut, Mul, Sub};
-use std::ptr;
-
-pub trait Vector where
-    Self: Sized
-    + Index<usize, Output=f64>
-    + IndexMut<usize>
-    + Clone
-    + Mul<f64, Output=Self>
-    + Add<Output=Self>
-    + Sub<Output=Self>
-    + Div<f64, Output=Self>
-    + Sum<Self> {
-    const DIM: usize;
-
-    fn get_component(&self, index: usize) -> f64;
-
-    fn get_components_mut(&mut self) -> &mut [f64];
-
-    fn dot_product(v1: &Self, v2: &Self) -> f64 {
-        let mut sum = 0.0;
-        for i in 0..Self::DIM {
-            sum += v1[i] * v2[i];
-        }
-        return sum;
-    }
-
-    fn scale_with_dot_prod(&mut self, v2: &Self) {
-        for i in 0..Self::DIM {
-            self[i] = self[i] * self[i] * v2[i];
-        }
-    }
-
-
-    fn length(&self) -> f64 {
-        return Self::dot_product(self, self).sqrt();
-    }
-
-    fn normalize(&mut self) {
-        let len = self.length();
-        self.get_components_mut().iter_mut().for_each(|c| *c /= len);
-    }
-
-    fn scale(self, lambda: f64) -> Self {
-        return self * lambda;
-    }
-
-    fn sub(&mut self, other: &Self) {
-        for i in 0..Self::DIM {
-            self[i] -= other[i];
-        }
-    }
-}
-
-macro_rules! vector {
-    ($name:ident, $dim:expr) => {
-        #[derive(Debug, PartialEq, Clone)]
-        pub struct $name {
-            pub components: [f64; $dim],
-        }
-
-        impl $name {
-            pub const DIM: usize = $dim;
-
-            pub fn new(components: [f64; Self::DIM]) -> Self {
-                return Self { components };
-            }
-
-            pub fn empty() -> Self {
-                return Self { components: [0.0; Self::DIM] };
-            }
-
-            pub fn scaled_copy(&self, lambda: f64) -> Self {
-                let mut components = [0.0; Self::DIM];
-                for i in 0..Self::DIM {
-                    components[i] = self.components[i] * lambda;
-                }
-                return Self { components };
-            }
-
-            #[inline(never)]
-    pub fn gram_schmidt(
-        basis: &mut Vec<Self>,
-    ) {
-        basis[0].normalize();
-        for index in 1..basis.len() {
-            let (first_half, second_half) = basis.split_at_mut(index);
-            let a = &mut second_half[0];
-            for b in first_half.iter() {
-                let dot = Vector::dot_product(a, &b);
-                a.sub(&b.scaled_copy(dot));
-            }
-            a.normalize();
-        }
-    }
-        }
-
-        impl Add for $name {
-            type Output = Self;
-
-            fn add(self, rhs: Self) -> Self::Output {
-                let mut components = [0.0; Self::DIM];
-                for i in 0..Self::DIM {
-                    components[i] = self.components[i] + rhs.components[i];
-                }
-                return Self { components };
-            }
-        }
-
-        impl Sub for $name {
-            type Output = Self;
-
-            fn sub(self, rhs: Self) -> Self::Output {
-                let mut components = [0.0; Self::DIM];
-                for i in 0..Self::DIM {
-                    components[i] = self.components[i] - rhs.components[i];
-                }
-                return Self { components };
-            }
-        }
-
-        impl Sum for $name {
-            fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
-                return iter.fold(
-                    Self::empty(),
-                    |a, b| a + b,
-                );
-            }
-        }
-
-        impl Mul<f64> for $name {
-            type Output = Self;
-
-            fn mul(self, rhs: f64) -> Self::Output {
-                let mut components = [0.0; Self::DIM];
-                for i in 0..Self::DIM {
-                    components[i] = self.components[i] * rhs;
-                }
-                return Self { components };
-            }
-        }
-
-        impl Div<f64> for $name {
-            type Output = Self;
-
-            fn div(self, rhs: f64) -> Self::Output {
-                let mut components = [0.0; Self::DIM];
-                for i in 0..Self::DIM {
-                    components[i] = self.components[i] / rhs;
-                }
-                return Self { components };
-            }
-        }
-
-        impl Index<usize> for $name {
-            type Output = f64;
-
-            fn index(&self, index: usize) -> &Self::Output {
-                return &self.components[index];
-            }
-        }
-
-        impl IndexMut<usize> for $name {
-            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-                return &mut self.components[index];
-            }
-        }
-
-        impl Vector for $name {
-
-            const DIM: usize = $dim;
-
-            fn get_component(&self, index: usize) -> f64 {
-                return self.components[index];
-            }
-
-            fn get_components_mut(&mut self) -> &mut [f64] {
-                return &mut self.components;
-            }
-        }
-    };
-}
-
-// Usage
-vector!(Vector4, 4);
-vector!(Vector3, 3);
-
-
-
-#[cfg(test)]
-mod vec3_test {
-    use crate::{Vector, Vector4};
-
-    #[test]
-    fn test_dot_product() {
-        let v1 = Vector4::new([1.0, 2.0, 3.0, 6.0]);
-        let v2 = Vector4::new([3.0, 4.0, 5.0, 7.0]);
-        assert_eq!(Vector4::dot_product(&v1, &v2), 68.0);
-    }
-
-    #[test]
-    fn scale_with_dot_prod() {
-        let mut v1 = Vector4::new([1.0, 2.0, 3.0, 6.0]);
-        let v2 = Vector4::new([3.0, 4.0, 5.0, 7.0]);
-        v1.scale_with_dot_prod(&v2);
-        assert_eq!(v1, Vector4::new([3.0, 16.0, 45.0, 252.0]));
-    }
-
-    #[test]
-    fn test_normalize() {
-        let mut v1 = Vector4::new([4.0, 4.0, 4.0, 4.0]);
-        v1.normalize();
-        assert_eq!(v1, Vector4::new([4.0 / 8.0, 4.0 / 8.0, 4.0 / 8.0, 4.0 / 8.0]));
-    }
-
-    #[test]
-    fn test_length() {
-        let v1 = Vector4::new([4.0, 4.0, 4.0, 4.0]);
-        let v2 = Vector4::new([3.0, 4.0, 5.0, 7.0]);
-        assert_eq!(v1.length(), 8.0);
-        assert_eq!(v2.length(), 99_f64.sqrt());
-    }
-}
-
-#[cfg(test)]
-mod grim_schmidt_test {
-    use crate::{Vector, Vector4};
-
-    #[test]
-    fn basic_test() {
-        let mut basis = vec![
-            Vector4::new([1.0, 1.0, 1.0, 1.0]),
-            Vector4::new([0.0, 1.0, 0.0, 1.0]),
-            Vector4::new([0.0, 0.0, 1.0, 1.0]),
-            Vector4::new([0.0, 0.0, 0.0, 1.0]),
-        ];
-        Vector4::gram_schmidt(&mut basis);
-        assert_eq!(vec![
-            Vector4::new([0.5, 0.5, 0.5, 0.5]),
-            Vector4::new([-0.5, 0.5, -0.5, 0.5]),
-            Vector4::new([-0.5, -0.5, 0.5, 0.5]),
-            Vector4::new([0.5, -0.5, -0.5, 0.5]),
-        ], basis);
-    }
-}
\ No newline at end of file
+pub use attitude::{attitude_from_accel_mag, NavigationFrame};
+pub use backward::{gram_schmidt_vjp, qr_vjp};
+pub use basis::{coordinates_of, from_coordinates, OrthonormalBasis};
+pub use bidiagonal::bidiagonalize;
+pub use blocking::{orthonormalize_cancellable, CancellationToken, OrthonormalizeOptions};
+#[cfg(feature = "tokio")]
+pub use blocking::orthonormalize_async;
+pub use cache::{content_key, BasisCache};
+pub use distributed::{
+    arnoldi_distributed, block_arnoldi_distributed, distributed_dot, gram_schmidt_distributed, Communicator, Local,
+};
+pub use dynamic::{dyn_vector, gram_schmidt_dyn, DynVector, SmallVector, SMALL_VECTOR_INLINE};
+pub use dual::{gram_schmidt_dual, gram_schmidt_jvp, Dual};
+pub use eigen::{lowdin_orthonormalize, power_iteration, rayleigh_ritz, subspace_iteration};
+pub use error::{validate_finite, Error, GramSchmidtError, Warning};
+pub use extend::{gram_schmidt_extend, gram_schmidt_resume};
+pub use fp_env::{check_fp_environment, set_strict_fp, strict_fp, FpEnvironment, RoundingMode};
+pub use frames::{mean_rotation, rotation_minimizing_frames, smooth_frames, Frame};
+pub use geometry::{best_fit_subspace, AffineSubspace, Line, Plane};
+pub use givens::{apply_givens, givens};
+pub use hessenberg::{hessenberg, hessenberg_implicit};
+pub use householder::{apply_householder, householder_vector, HouseholderProduct};
+pub use ica::fastica;
+pub use incremental::{Checkpoint, IncrementalGramSchmidt};
+pub use kalman::{decorrelate, qr_measurement_update, sqrt_factor};
+pub use krylov::{arnoldi, block_arnoldi};
+pub use lsqr::{lsqr, lsqr_pooled, lsqr_preconditioned};
+pub use matrix::{orthonormalize_rows, Matrix};
+pub use mixed::{dot_mixed, gram_schmidt_mixed, StorageScalar};
+pub use omp::omp;
+#[cfg(feature = "rayon")]
+pub use parallel::{par_gram_schmidt_batch, par_try_gram_schmidt_batch};
+pub use partial::gram_schmidt_partial;
+pub use permutation::{gram_schmidt_ordered, gram_schmidt_pivoted, OutputOrder, Permutation};
+pub use policy::{gram_schmidt_with_policy, ZeroVectorPolicy};
+pub use pool::{Pooled, VectorPool};
+pub use preconditioner::{IncompleteOrthogonalization, Jacobi, Preconditioner};
+pub use projector::{apply_complement_projector, apply_projector, null_space_projector};
+pub use qr::{householder_qr, householder_qr_implicit, try_householder_qr, try_tsqr, tsqr, ImplicitQ};
+pub use quaternion::{gram_schmidt_quaternion, quaternion_inner, Quaternion};
+pub use report::{debug_report, debug_report_json, BasisReport};
+pub use scaling::equilibrate_columns;
+pub use schur::schur;
+pub use stiefel::{cayley, expm_skew, interpolate_bases, project_to_tangent, retract_qr};
+pub use storage::{
+    gram_schmidt_storage, storage_axpy, storage_dot, storage_normalize, try_gram_schmidt_storage, try_storage_axpy,
+    try_storage_dot, Storage,
+};
+pub use triangular::{perturbation_bound, UpperTriangular};
+pub(crate) use vector::{divide, fuse_multiply_add, multiply_add, square_root};
+pub use vector::{
+    AlignedVector16, AlignedVector4, AlignedVector8, HashableVector, Vector, Vector10, Vector11, Vector12, Vector13,
+    Vector14, Vector15, Vector16, Vector2, Vector3, Vector4, Vector5, Vector6, Vector7, Vector8, Vector9,
+};
+pub use warm_start::gram_schmidt_warm_start;
+pub use workspace::{gram_schmidt_with_workspace, GsWorkspace};
+
+mod attitude;
+mod backward;
+mod basis;
+mod bidiagonal;
+#[cfg(feature = "bincode")]
+pub mod binary;
+mod blocking;
+mod cache;
+pub mod conformance;
+mod distributed;
+mod dual;
+mod dynamic;
+mod eigen;
+mod error;
+mod extend;
+#[cfg(feature = "fixed")]
+pub mod fixed_point;
+mod fp_env;
+mod frames;
+mod geometry;
+mod givens;
+mod hessenberg;
+mod householder;
+mod ica;
+mod incremental;
+#[cfg(feature = "interval")]
+pub mod interval;
+pub mod io;
+#[cfg(feature = "json")]
+pub mod json;
+mod kalman;
+mod krylov;
+#[cfg(feature = "lattice")]
+pub mod lattice;
+mod lsqr;
+mod matrix;
+mod mixed;
+mod omp;
+pub mod ortho;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod partial;
+mod permutation;
+#[cfg(feature = "bytemuck")]
+mod pod;
+mod policy;
+mod pool;
+mod preconditioner;
+pub mod prelude;
+#[cfg(feature = "rand")]
+pub mod projection;
+mod projector;
+pub mod qr;
+mod quaternion;
+mod report;
+#[cfg(feature = "complex")]
+pub mod roots;
+mod scaling;
+mod schur;
+#[cfg(feature = "soft-float")]
+pub mod soft_float;
+mod stiefel;
+mod storage;
+#[cfg(feature = "metrics")]
+pub mod telemetry;
+mod triangular;
+pub mod vector;
+mod warm_start;
+mod workspace;
@@ -0,0 +1,316 @@
+use std::cell::Cell;
+
+use crate::{storage_axpy, storage_dot, Pooled, Preconditioner, Storage, VectorPool};
+
+// Iteration limit, as a multiple of the larger dimension. Without reorthogonalization the Golub-Kahan vectors lose
+// orthogonality and LSQR can need more than the `min(m, n)` steps of exact arithmetic.
+const ITERATIONS_PER_DIMENSION: usize = 4;
+
+// Paige and Saunders' LSQR for `min ‖A x - b‖`, touching `A` only through `op(x) = A x` and `op_transpose(u) = Aᵀ u`,
+// so `AᵀA` is never formed. Runs Golub-Kahan bidiagonalization from `b` and solves the projected bidiagonal problem
+// with Givens rotations. Stops once `‖r‖ <= tol ‖b‖` (consistent systems) or `‖Aᵀr‖ <= tol ‖A‖ ‖r‖` (least squares),
+// with `‖A‖` estimated from the bidiagonal entries. With `reorthogonalize` every new Golub-Kahan vector is also
+// orthogonalized against all previous ones, which costs memory and time linear in the iteration count but keeps the
+// convergence of exact arithmetic. Works on any storage, so the dimensions may be chosen at runtime (`Vec<f64>`,
+// `SmallVector`); the solution takes its length from `op_transpose`. Returns the solution and the number of
+// iterations. See `lsqr_pooled` for a variant that does not allocate in every iteration.
+pub fn lsqr<U: Storage + Clone + 'static, V: Storage + Clone + 'static, A: Fn(&V) -> U, T: Fn(&U) -> V>(
+    op: A,
+    op_transpose: T,
+    rhs: &U,
+    tol: f64,
+    reorthogonalize: bool,
+) -> (V, usize) {
+    let initial = op_transpose(rhs);
+    let (mut u_template, mut v_template) = (rhs.clone(), initial.clone());
+    u_template.as_mut_slice().fill(0.0);
+    v_template.as_mut_slice().fill(0.0);
+    let u_pool = VectorPool::new(move || u_template.clone());
+    let v_pool = VectorPool::new(move || v_template.clone());
+    // The first product `lsqr_pooled` asks for is `Aᵀ b`, computed above to size the pools.
+    let initial = Cell::new(Some(initial));
+    return lsqr_pooled(
+        |v, out| *out = op(v),
+        |u, out| *out = initial.take().unwrap_or_else(|| op_transpose(u)),
+        rhs,
+        tol,
+        reorthogonalize,
+        &u_pool,
+        &v_pool,
+    );
+}
+
+// `lsqr` with operators that write `A x` and `Aᵀ u` into a zeroed `out`, and all vectors of the iteration drawn from
+// `u_pool` (length of `b`) and `v_pool` (length of `x`). Each iteration returns the vectors it replaces to the
+// pools, so after the first few iterations nothing is allocated, and pools reused across solves keep the
+// reorthogonalization vectors as well. Only the returned solution leaves the pool.
+pub fn lsqr_pooled<U: Storage, V: Storage, A: Fn(&V, &mut U), T: Fn(&U, &mut V)>(
+    op: A,
+    op_transpose: T,
+    rhs: &U,
+    tol: f64,
+    reorthogonalize: bool,
+    u_pool: &VectorPool<U>,
+    v_pool: &VectorPool<V>,
+) -> (V, usize) {
+    let mut v = v_pool.acquire();
+    op_transpose(rhs, &mut v);
+    let mut x = v_pool.acquire();
+    let rhs_norm = norm(rhs);
+    if rhs_norm == 0.0 {
+        return (x.into_inner(), 0);
+    }
+    let mut u = copy(u_pool, rhs);
+    scale(&mut *u, 1.0 / rhs_norm);
+    scale(&mut *v, 1.0 / rhs_norm);
+    let mut alpha = norm(&*v);
+    if alpha == 0.0 {
+        // `Aᵀ b = 0`, so `x = 0` is already a least-squares solution.
+        return (x.into_inner(), 0);
+    }
+    scale(&mut *v, 1.0 / alpha);
+    let (mut us, mut vs) = (vec![], vec![]);
+    if reorthogonalize {
+        us.push(copy(u_pool, &*u));
+        vs.push(copy(v_pool, &*v));
+    }
+    let mut w = copy(v_pool, &*v);
+    let (mut phi_bar, mut rho_bar) = (rhs_norm, alpha);
+    let mut op_norm_squared = alpha * alpha;
+
+    let limit = ITERATIONS_PER_DIMENSION * u.as_slice().len().max(v.as_slice().len());
+    for iteration in 1..=limit {
+        // Next bidiagonalization step: `β u = A v - α u`, `α v = Aᵀ u - β v`.
+        let mut next_u = u_pool.acquire();
+        op(&v, &mut next_u);
+        storage_axpy(-alpha, &*u, &mut *next_u);
+        orthogonalize(&mut *next_u, &us);
+        let beta = norm(&*next_u);
+        if beta > 0.0 {
+            u = next_u;
+            scale(&mut *u, 1.0 / beta);
+            let mut next_v = v_pool.acquire();
+            op_transpose(&u, &mut next_v);
+            storage_axpy(-beta, &*v, &mut *next_v);
+            orthogonalize(&mut *next_v, &vs);
+            alpha = norm(&*next_v);
+            if alpha > 0.0 {
+                v = next_v;
+                scale(&mut *v, 1.0 / alpha);
+            }
+            if reorthogonalize {
+                us.push(copy(u_pool, &*u));
+                vs.push(copy(v_pool, &*v));
+            }
+        } else {
+            alpha = 0.0;
+        }
+        op_norm_squared += alpha * alpha + beta * beta;
+
+        // Rotate the new subdiagonal `β` away and update the solution along `w`.
+        let rho = rho_bar.hypot(beta);
+        let (c, s) = (rho_bar / rho, beta / rho);
+        let theta = s * alpha;
+        rho_bar = -c * alpha;
+        let phi = c * phi_bar;
+        phi_bar *= s;
+        storage_axpy(phi / rho, &*w, &mut *x);
+        // `w ← v - (θ / ρ) w`
+        scale(&mut *w, -theta / rho);
+        storage_axpy(1.0, &*v, &mut *w);
+
+        let residual = phi_bar;
+        let normal_residual = phi_bar * alpha * c.abs();
+        if residual <= tol * rhs_norm
+            || normal_residual <= tol * op_norm_squared.sqrt() * residual
+            || alpha == 0.0
+            || beta == 0.0
+        {
+            return (x.into_inner(), iteration);
+        }
+    }
+    return (x.into_inner(), limit);
+}
+
+// `lsqr` with a right preconditioner: solves `min ‖A M⁻¹ y - b‖` and returns `x = M⁻¹ y`. The stopping test applies
+// to the preconditioned operator.
+pub fn lsqr_preconditioned<
+    U: Storage + Clone + 'static,
+    V: Storage + Clone + 'static,
+    A: Fn(&V) -> U,
+    T: Fn(&U) -> V,
+    P: Preconditioner<V>,
+>(
+    op: A,
+    op_transpose: T,
+    preconditioner: &P,
+    rhs: &U,
+    tol: f64,
+    reorthogonalize: bool,
+) -> (V, usize) {
+    let (y, iterations) = lsqr(
+        |y| op(&preconditioner.apply(y)),
+        |u| preconditioner.apply_transpose(&op_transpose(u)),
+        rhs,
+        tol,
+        reorthogonalize,
+    );
+    return (preconditioner.apply(&y), iterations);
+}
+
+fn norm<S: Storage>(v: &S) -> f64 {
+    return storage_dot(v, v).sqrt();
+}
+
+fn copy<'a, S: Storage>(pool: &'a VectorPool<S>, source: &S) -> Pooled<'a, S> {
+    let mut v = pool.acquire();
+    v.as_mut_slice().copy_from_slice(source.as_slice());
+    return v;
+}
+
+fn scale<S: Storage>(v: &mut S, factor: f64) {
+    v.as_mut_slice().iter_mut().for_each(|c| *c *= factor);
+}
+
+fn orthogonalize<W: Storage>(w: &mut W, basis: &[Pooled<'_, W>]) {
+    #[cfg(feature = "metrics")]
+    crate::telemetry::reorthogonalization_passes("lsqr", !basis.is_empty() as u64);
+    for q in basis {
+        let dot = storage_dot(&**q, w);
+        storage_axpy(-dot, &**q, w);
+    }
+}
+
+#[cfg(test)]
+mod lsqr_test {
+    use crate::{
+        householder_qr, lsqr, lsqr_pooled, lsqr_preconditioned, DynVector, IncompleteOrthogonalization, Jacobi, Matrix, SmallVector,
+        Storage, UpperTriangular, Vector, Vector3, Vector5, VectorPool,
+    };
+
+    fn system() -> (Matrix, Vector5) {
+        let a = Matrix::from_rows(&[
+            vec![1.0, 2.0, 0.5],
+            vec![0.0, 1.0, -1.0],
+            vec![3.0, -1.0, 2.0],
+            vec![1.0, 1.0, 1.0],
+            vec![-2.0, 0.0, 4.0],
+        ]);
+        return (a, Vector5::new([1.0, -2.0, 0.5, 3.0, 1.0]));
+    }
+
+    fn apply(a: &Matrix, x: &Vector3) -> Vector5 {
+        return Vector5::from_fn(|i| (0..3).map(|j| a[(i, j)] * x[j]).sum());
+    }
+
+    fn apply_transpose(a: &Matrix, u: &Vector5) -> Vector3 {
+        return Vector3::from_fn(|j| (0..5).map(|i| a[(i, j)] * u[i]).sum());
+    }
+
+    #[test]
+    fn matches_qr_least_squares() {
+        let (a, b) = system();
+        let (q, r) = householder_qr(&a);
+        let qtb: Vec<f64> = (0..3).map(|j| (0..5).map(|i| q[(i, j)] * b[i]).sum()).collect();
+        let expected = UpperTriangular::new(r).solve(&qtb);
+        for reorthogonalize in [false, true] {
+            let (x, iterations) = lsqr(|x| apply(&a, x), |u| apply_transpose(&a, u), &b, 1e-12, reorthogonalize);
+            assert!(iterations <= 12);
+            for i in 0..3 {
+                assert!((x[i] - expected[i]).abs() < 1e-10, "{} != {}", x[i], expected[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn zero_rhs_and_reorthogonalized_termination() {
+        let (a, b) = system();
+        let (x, iterations) = lsqr(|x| apply(&a, x), |u| apply_transpose(&a, u), &Vector5::zero(), 1e-12, false);
+        assert_eq!((x, iterations), (Vector3::zero(), 0));
+        // With full reorthogonalization the process ends after at most `n` steps, like in exact arithmetic.
+        let (_, iterations) = lsqr(|x| apply(&a, x), |u| apply_transpose(&a, u), &b, 1e-14, true);
+        assert!(iterations <= 3, "{}", iterations);
+    }
+
+    #[test]
+    fn preconditioning_reduces_iterations() {
+        let (mut a, b) = system();
+        // Badly scaled columns.
+        for (j, scale) in [1e-4, 1.0, 1e4].iter().enumerate() {
+            a.column_mut(j).iter_mut().for_each(|x| *x *= scale);
+        }
+        let columns: Vec<Vector5> = (0..3).map(|j| Vector5::from_fn(|i| a[(i, j)])).collect();
+        let solve = |x: &Vector3| apply(&a, x);
+        let solve_transpose = |u: &Vector5| apply_transpose(&a, u);
+        let (_, plain_iterations) = lsqr(solve, solve_transpose, &b, 1e-12, false);
+
+        let jacobi = Jacobi::from_columns(&columns);
+        let (x, iterations) = lsqr_preconditioned(solve, solve_transpose, &jacobi, &b, 1e-12, false);
+        assert!(iterations <= 6 && iterations < plain_iterations, "{} vs {}", iterations, plain_iterations);
+
+        // The exact `R` turns `A R⁻¹` into a matrix with orthonormal columns: one step suffices.
+        let exact = IncompleteOrthogonalization::new(&columns, 0.0).unwrap();
+        let (y, iterations) = lsqr_preconditioned(solve, solve_transpose, &exact, &b, 1e-12, false);
+        assert_eq!(iterations, 1);
+        assert!((y - x).length() < 1e-6 * x.length());
+    }
+
+    #[test]
+    fn runtime_dimensions() {
+        let (a, b) = system();
+        let (expected, _) = lsqr(|x| apply(&a, x), |u| apply_transpose(&a, u), &b, 1e-12, false);
+        let apply_dyn = |x: &[f64]| (0..5).map(|i| (0..3).map(|j| a[(i, j)] * x[j]).sum()).collect::<Vec<f64>>();
+        let apply_transpose_dyn = |u: &[f64]| (0..3).map(|j| (0..5).map(|i| a[(i, j)] * u[i]).sum()).collect();
+        let (x, _) = lsqr(|x: &Vec<f64>| apply_dyn(x), |u: &Vec<f64>| apply_transpose_dyn(u), &b.components.to_vec(),
+                          1e-12, true);
+        assert_eq!(x.len(), 3);
+        assert!(x.iter().zip(expected.as_slice()).all(|(x, e)| (x - e).abs() < 1e-10));
+
+        // Boxed vectors whose type is only known at runtime.
+        let boxed = |v: Vec<f64>| -> Box<dyn DynVector> { Box::new(v) };
+        let (x, _) = lsqr::<Box<dyn DynVector>, Box<dyn DynVector>, _, _>(
+            |x| boxed(apply_dyn(x.as_slice())),
+            |u| boxed(apply_transpose_dyn(u.as_slice())),
+            &boxed(b.components.to_vec()),
+            1e-12,
+            false,
+        );
+        assert_eq!(x.dim(), 3);
+        assert!(x.components().iter().zip(expected.as_slice()).all(|(x, e)| (x - e).abs() < 1e-10));
+
+        // Inline small vectors, preconditioned by the exact `R`.
+        let columns: Vec<Vector5> = (0..3).map(|j| Vector5::from_fn(|i| a[(i, j)])).collect();
+        let exact = IncompleteOrthogonalization::new(&columns, 0.0).unwrap();
+        let (x, iterations) = lsqr_preconditioned(
+            |x: &SmallVector| SmallVector::from_slice(&apply_dyn(x.as_slice())),
+            |u: &SmallVector| SmallVector::from_slice(&apply_transpose_dyn(u.as_slice())),
+            &exact,
+            &SmallVector::from_slice(&b.components),
+            1e-12,
+            false,
+        );
+        assert_eq!(iterations, 1);
+        assert!(x.as_slice().iter().zip(expected.as_slice()).all(|(x, e)| (x - e).abs() < 1e-10));
+    }
+
+    #[test]
+    fn pooled_solves_stop_allocating() {
+        let (a, b) = system();
+        let (expected, expected_iterations) = lsqr(|x| apply(&a, x), |u| apply_transpose(&a, u), &b, 1e-12, true);
+        let op = |x: &Vec<f64>, out: &mut Vec<f64>| {
+            (0..5).for_each(|i| out[i] = (0..3).map(|j| a[(i, j)] * x[j]).sum());
+        };
+        let op_transpose = |u: &Vec<f64>, out: &mut Vec<f64>| {
+            (0..3).for_each(|j| out[j] = (0..5).map(|i| a[(i, j)] * u[i]).sum());
+        };
+        let (u_pool, v_pool) = (VectorPool::with_dim(5), VectorPool::with_dim(3));
+        let rhs = b.components.to_vec();
+        let (x, iterations) = lsqr_pooled(op, op_transpose, &rhs, 1e-12, true, &u_pool, &v_pool);
+        assert_eq!((x.as_slice(), iterations), (expected.as_slice(), expected_iterations));
+        let allocations = (u_pool.allocations(), v_pool.allocations());
+        // A second solve on the same pools only allocates the solution it hands out.
+        let (y, _) = lsqr_pooled(op, op_transpose, &rhs, 1e-12, true, &u_pool, &v_pool);
+        assert_eq!(x, y);
+        assert_eq!((u_pool.allocations(), v_pool.allocations()), (allocations.0, allocations.1 + 1));
+    }
+}
@@ -1,15 +1,75 @@
-use gram_schmidt::{Vector, Vector4};
+#![allow(clippy::needless_return)]
+
+use gram_schmidt::{gram_schmidt_with_workspace, GsWorkspace, Vector4};
+
+#[cfg(feature = "json")]
+mod batch;
+#[cfg(feature = "server")]
+mod server;
+mod stress;
+
+// With the `json` feature, `gram-schmidt --json` reads a basis document from stdin and writes the orthonormalized
+// basis to stdout, and `gram-schmidt batch ...` factors a directory of such documents (see `batch::run`). With the
+// `server` feature, `gram-schmidt --serve <address>` runs an HTTP service instead. Without arguments the binary runs
+// the fixed benchmark loop. `gram-schmidt stress ...` compares methods on generated matrices (see `stress::run`).
+#[cfg(feature = "json")]
+fn run_json() -> Result<(), Box<dyn std::error::Error>> {
+    let input = std::io::read_to_string(std::io::stdin())?;
+    let mut document = gram_schmidt::json::BasisDocument::from_json(&input)?;
+    document.gram_schmidt()?;
+    println!("{}", document.to_json());
+    return Ok(());
+}
 
 fn main() {
+    if std::env::args().nth(1).as_deref() == Some("stress") {
+        let args: Vec<String> = std::env::args().skip(2).collect();
+        if let Err(error) = stress::run(&args) {
+            eprintln!("error: {}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    #[cfg(feature = "json")]
+    if std::env::args().nth(1).as_deref() == Some("--json") {
+        if let Err(error) = run_json() {
+            eprintln!("error: {}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    #[cfg(feature = "json")]
+    if std::env::args().nth(1).as_deref() == Some("batch") {
+        let args: Vec<String> = std::env::args().skip(2).collect();
+        match batch::run(&args) {
+            Ok(0) => return,
+            Ok(failures) => eprintln!("{} file(s) failed", failures),
+            Err(error) => eprintln!("error: {}", error),
+        }
+        std::process::exit(1);
+    }
+
+    #[cfg(feature = "server")]
+    if std::env::args().nth(1).as_deref() == Some("--serve") {
+        let address = std::env::args().nth(2).unwrap_or_else(|| "127.0.0.1:8080".to_string());
+        if let Err(error) = server::serve(&address) {
+            eprintln!("error: {}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
 
-    const iterations: usize = 1000000;
-    for _ in 0..iterations {
-        let mut basis = [
-            Vector4::new([1.0, 1.0, 1.0, 1.0]),
-            Vector4::new([0.0, 1.0, 0.0, 1.0]),
-            Vector4::new([0.0, 0.0, 1.0, 1.0]),
-            Vector4::new([0.0, 0.0, 0.0, 1.0]),
-        ].to_vec();
-        Vector4::gram_schmidt(&mut basis);
+    const ITERATIONS: usize = 1000000;
+    let basis = [
+        Vector4::new([1.0, 1.0, 1.0, 1.0]),
+        Vector4::new([0.0, 1.0, 0.0, 1.0]),
+        Vector4::new([0.0, 0.0, 1.0, 1.0]),
+        Vector4::new([0.0, 0.0, 0.0, 1.0]),
+    ];
+    let mut ws = GsWorkspace::with_capacity(basis.len());
+    for _ in 0..ITERATIONS {
+        gram_schmidt_with_workspace(&mut ws, &basis);
     }
 }
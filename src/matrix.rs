@@ -0,0 +1,233 @@
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+use crate::Error;
+
+// Dense, column-major matrix with dimensions chosen at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>,
+}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        return Self { rows, cols, data: vec![0.0; rows * cols] };
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = 1.0;
+        }
+        return m;
+    }
+
+    // All columns must have the same length.
+    pub fn from_columns(columns: &[Vec<f64>]) -> Self {
+        let rows = columns.first().map_or(0, |c| c.len());
+        assert!(columns.iter().all(|c| c.len() == rows), "columns must have equal length");
+        return Self { rows, cols: columns.len(), data: columns.concat() };
+    }
+
+    // Like `from_columns`, but reports columns of unequal length instead of panicking.
+    pub fn try_from_columns(columns: &[Vec<f64>]) -> Result<Self, Error> {
+        let rows = columns.first().map_or(0, |c| c.len());
+        if let Some(column) = columns.iter().find(|c| c.len() != rows) {
+            return Err(Error::DimensionMismatch { expected: rows, found: column.len() });
+        }
+        return Ok(Self::from_columns(columns));
+    }
+
+    // All rows must have the same length.
+    pub fn from_rows(rows: &[Vec<f64>]) -> Self {
+        return Self::from_columns(rows).transpose();
+    }
+
+    pub fn rows(&self) -> usize {
+        return self.rows;
+    }
+
+    pub fn cols(&self) -> usize {
+        return self.cols;
+    }
+
+    pub fn column(&self, col: usize) -> &[f64] {
+        return &self.data[col * self.rows..(col + 1) * self.rows];
+    }
+
+    pub fn column_mut(&mut self, col: usize) -> &mut [f64] {
+        return &mut self.data[col * self.rows..(col + 1) * self.rows];
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut t = Self::zeros(self.cols, self.rows);
+        for j in 0..self.cols {
+            for i in 0..self.rows {
+                t[(j, i)] = self[(i, j)];
+            }
+        }
+        return t;
+    }
+
+    // Fallible versions of the arithmetic operators, which panic on mismatched dimensions.
+    pub fn checked_add(&self, rhs: &Self) -> Result<Self, Error> {
+        self.check_same_shape(rhs)?;
+        return Ok(self + rhs);
+    }
+
+    pub fn checked_sub(&self, rhs: &Self) -> Result<Self, Error> {
+        self.check_same_shape(rhs)?;
+        return Ok(self - rhs);
+    }
+
+    pub fn checked_mul(&self, rhs: &Self) -> Result<Self, Error> {
+        if self.cols != rhs.rows {
+            return Err(Error::DimensionMismatch { expected: self.cols, found: rhs.rows });
+        }
+        return Ok(self * rhs);
+    }
+
+    fn check_same_shape(&self, rhs: &Self) -> Result<(), Error> {
+        if self.rows != rhs.rows {
+            return Err(Error::DimensionMismatch { expected: self.rows, found: rhs.rows });
+        }
+        if self.cols != rhs.cols {
+            return Err(Error::DimensionMismatch { expected: self.cols, found: rhs.cols });
+        }
+        return Ok(());
+    }
+
+    // Copies rows `start..end`.
+    pub fn row_block(&self, start: usize, end: usize) -> Self {
+        let mut block = Self::zeros(end - start, self.cols);
+        for j in 0..self.cols {
+            block.column_mut(j).copy_from_slice(&self.column(j)[start..end]);
+        }
+        return block;
+    }
+
+    // Stacks `top` above `bottom`.
+    pub fn vstack(top: &Self, bottom: &Self) -> Self {
+        assert_eq!(top.cols, bottom.cols, "stacked matrices must have the same number of columns");
+        let mut m = Self::zeros(top.rows + bottom.rows, top.cols);
+        for j in 0..top.cols {
+            let column = m.column_mut(j);
+            column[..top.rows].copy_from_slice(top.column(j));
+            column[top.rows..].copy_from_slice(bottom.column(j));
+        }
+        return m;
+    }
+}
+
+// Orthonormalizes the rows of `m` in place with modified Gram-Schmidt, yielding an orthonormal basis of the row space
+// when the rows are linearly independent. Works on the strided rows directly instead of transposing twice.
+pub fn orthonormalize_rows(m: &mut Matrix) {
+    let row_dot = |m: &Matrix, a: usize, b: usize| (0..m.cols).map(|j| m[(a, j)] * m[(b, j)]).sum::<f64>();
+    for i in 0..m.rows {
+        for p in 0..i {
+            let dot = row_dot(m, i, p);
+            for j in 0..m.cols {
+                m[(i, j)] -= dot * m[(p, j)];
+            }
+        }
+        let norm = row_dot(m, i, i).sqrt();
+        for j in 0..m.cols {
+            m[(i, j)] /= norm;
+        }
+    }
+}
+
+impl TryFrom<&[Vec<f64>]> for Matrix {
+    type Error = Error;
+
+    fn try_from(columns: &[Vec<f64>]) -> Result<Self, Self::Error> {
+        return Self::try_from_columns(columns);
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        return &self.data[col * self.rows + row];
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        return &mut self.data[col * self.rows + row];
+    }
+}
+
+impl Add for &Matrix {
+    type Output = Matrix;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        assert_eq!((self.rows, self.cols), (rhs.rows, rhs.cols), "dimensions must agree");
+        let data = self.data.iter().zip(&rhs.data).map(|(a, b)| a + b).collect();
+        return Matrix { rows: self.rows, cols: self.cols, data };
+    }
+}
+
+impl Sub for &Matrix {
+    type Output = Matrix;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        assert_eq!((self.rows, self.cols), (rhs.rows, rhs.cols), "dimensions must agree");
+        let data = self.data.iter().zip(&rhs.data).map(|(a, b)| a - b).collect();
+        return Matrix { rows: self.rows, cols: self.cols, data };
+    }
+}
+
+impl Mul for &Matrix {
+    type Output = Matrix;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.cols, rhs.rows, "inner dimensions must agree");
+        let mut product = Matrix::zeros(self.rows, rhs.cols);
+        for j in 0..rhs.cols {
+            for k in 0..self.cols {
+                let factor = rhs[(k, j)];
+                for i in 0..self.rows {
+                    product[(i, j)] += self[(i, k)] * factor;
+                }
+            }
+        }
+        return product;
+    }
+}
+
+#[cfg(test)]
+mod matrix_test {
+    use crate::{orthonormalize_rows, Error, Matrix};
+
+    #[test]
+    fn layout_and_product() {
+        let a = Matrix::from_rows(&[vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+        assert_eq!(a.rows(), 3);
+        assert_eq!(a.column(1), &[2.0, 4.0, 6.0]);
+        assert_eq!(&a.transpose() * &a, Matrix::from_rows(&[vec![35.0, 44.0], vec![44.0, 56.0]]));
+        assert_eq!(Matrix::vstack(&a.row_block(0, 1), &a.row_block(1, 3)), a);
+    }
+
+    #[test]
+    fn fallible_alternatives() {
+        let ragged: &[Vec<f64>] = &[vec![1.0, 2.0], vec![3.0]];
+        assert_eq!(Matrix::try_from(ragged), Err(Error::DimensionMismatch { expected: 2, found: 1 }));
+        let a = Matrix::identity(2);
+        let b = Matrix::zeros(3, 2);
+        assert_eq!(a.checked_mul(&b), Err(Error::DimensionMismatch { expected: 2, found: 3 }));
+        assert_eq!(b.checked_mul(&a), Ok(b.clone()));
+        assert!(a.checked_add(&b).is_err());
+    }
+
+    #[test]
+    fn row_space_basis() {
+        let mut m = Matrix::from_rows(&[vec![1.0, 1.0, 1.0, 1.0], vec![0.0, 1.0, 0.0, 1.0]]);
+        orthonormalize_rows(&mut m);
+        let gram = &m * &m.transpose();
+        assert!((0..2).all(|i| (0..2).all(|j| (gram[(i, j)] - if i == j { 1.0 } else { 0.0 }).abs() < 1e-12)));
+        assert_eq!(m.column(0), &[0.5, -0.5]);
+    }
+}
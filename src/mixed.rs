@@ -0,0 +1,109 @@
+// Scalar type used to store vector components in the mixed-precision routines. All arithmetic happens in f64; values
+// are only rounded to the storage type when written back.
+pub trait StorageScalar: Copy {
+    fn to_f64(self) -> f64;
+
+    fn from_f64(value: f64) -> Self;
+}
+
+impl StorageScalar for f32 {
+    fn to_f64(self) -> f64 {
+        return self as f64;
+    }
+
+    fn from_f64(value: f64) -> Self {
+        return value as f32;
+    }
+}
+
+impl StorageScalar for f64 {
+    fn to_f64(self) -> f64 {
+        return self;
+    }
+
+    fn from_f64(value: f64) -> Self {
+        return value;
+    }
+}
+
+#[cfg(feature = "half")]
+impl StorageScalar for half::f16 {
+    fn to_f64(self) -> f64 {
+        return half::f16::to_f64(self);
+    }
+
+    fn from_f64(value: f64) -> Self {
+        return half::f16::from_f64(value);
+    }
+}
+
+#[cfg(feature = "half")]
+impl StorageScalar for half::bf16 {
+    fn to_f64(self) -> f64 {
+        return half::bf16::to_f64(self);
+    }
+
+    fn from_f64(value: f64) -> Self {
+        return half::bf16::from_f64(value);
+    }
+}
+
+pub fn dot_mixed<S: StorageScalar>(a: &[S], b: &[S]) -> f64 {
+    return a.iter().zip(b).map(|(x, y)| x.to_f64() * y.to_f64()).sum();
+}
+
+// Orthonormalizes `columns` in place. Each column is widened to f64, orthogonalized against the already stored
+// columns and normalized, and only then rounded back, so the accumulated error is that of a single rounding per
+// component rather than one per operation.
+pub fn gram_schmidt_mixed<S: StorageScalar>(columns: &mut [Vec<S>]) {
+    let mut work = vec![];
+    for index in 0..columns.len() {
+        let (done, rest) = columns.split_at_mut(index);
+        let column = &mut rest[0];
+        work.clear();
+        work.extend(column.iter().map(|c| c.to_f64()));
+        for q in done.iter() {
+            let dot: f64 = q.iter().zip(&work).map(|(qi, w)| qi.to_f64() * w).sum();
+            for (w, qi) in work.iter_mut().zip(q) {
+                *w -= dot * qi.to_f64();
+            }
+        }
+        let norm = work.iter().map(|w| w * w).sum::<f64>().sqrt();
+        for (c, w) in column.iter_mut().zip(&work) {
+            *c = S::from_f64(w / norm);
+        }
+    }
+}
+
+#[cfg(test)]
+mod mixed_test {
+    use crate::{dot_mixed, gram_schmidt_mixed, StorageScalar};
+
+    fn check_orthonormal<S: StorageScalar>(tolerance: f64) {
+        let mut columns: Vec<Vec<S>> = [
+            [1.0, 1.0, 1.0, 1.0],
+            [0.0, 1.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0, 1.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ].iter().map(|c| c.iter().map(|v| S::from_f64(*v)).collect()).collect();
+        gram_schmidt_mixed(&mut columns);
+        for i in 0..4 {
+            for j in 0..4 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((dot_mixed(&columns[i], &columns[j]) - expected).abs() < tolerance);
+            }
+        }
+    }
+
+    #[test]
+    fn f32_storage() {
+        check_orthonormal::<f32>(1e-6);
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn half_storage() {
+        check_orthonormal::<half::f16>(1e-2);
+        check_orthonormal::<half::bf16>(1e-2);
+    }
+}
@@ -0,0 +1,67 @@
+use crate::{IncrementalGramSchmidt, Vector};
+
+// Orthogonal matching pursuit: greedily selects up to `k` atoms of `dictionary` to approximate `signal`. Each step
+// picks the atom best correlated with the current residual, appends it to an `IncrementalGramSchmidt` and
+// re-projects the signal onto the orthonormalized selection, so the residual stays orthogonal to every chosen atom.
+// Stops early when the residual vanishes or no remaining atom adds a new direction. Returns the selected atom indices
+// with their coefficients in the least-squares fit.
+pub fn omp<V: Vector>(dictionary: &[V], signal: &V, k: usize) -> Vec<(usize, f64)> {
+    let scale = signal.length();
+    let mut gs = IncrementalGramSchmidt::new();
+    let mut selected = vec![];
+    let mut projections = vec![];
+    let mut residual = signal.clone();
+    while selected.len() < k && residual.length() > 1e-12 * scale {
+        let best = dictionary.iter().enumerate()
+            .filter(|(index, atom)| !selected.contains(index) && atom.length() > 0.0)
+            .map(|(index, atom)| (index, V::dot_product(atom, &residual).abs() / atom.length()))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        let Some((index, correlation)) = best else {
+            break;
+        };
+        if correlation <= 1e-12 * scale {
+            break;
+        }
+        if gs.push(dictionary[index].clone()).is_err() {
+            break;
+        }
+        selected.push(index);
+        let q = &gs.basis()[gs.basis().len() - 1];
+        let projection = V::dot_product(q, &residual);
+        residual.axpy(-projection, q);
+        projections.push(projection);
+    }
+
+    // Back substitution `R x = Qᵀ signal`; `gs.r()[j][i]` is `R[i][j]`.
+    let r = gs.r();
+    let mut coefficients = projections;
+    for i in (0..coefficients.len()).rev() {
+        for j in i + 1..coefficients.len() {
+            coefficients[i] -= r[j][i] * coefficients[j];
+        }
+        coefficients[i] /= r[i][i];
+    }
+    return selected.into_iter().zip(coefficients).collect();
+}
+
+#[cfg(test)]
+mod omp_test {
+    use crate::{omp, Vector4};
+
+    #[test]
+    fn recovers_sparse_combination() {
+        let dictionary = [
+            Vector4::new([1.0, 0.0, 0.0, 0.0]),
+            Vector4::new([0.6, 0.8, 0.0, 0.0]),
+            Vector4::new([0.0, 0.0, 1.0, 0.0]),
+            Vector4::new([0.0, 0.6, 0.0, 0.8]),
+            Vector4::new([0.5, 0.5, 0.5, 0.5]),
+        ];
+        let signal = Vector4::new([3.0 * 0.6, 3.0 * 0.8 - 2.0 * 0.6, 0.0, -2.0 * 0.8]);
+        let mut fit = omp(&dictionary, &signal, 3);
+        fit.sort_by_key(|(index, _)| *index);
+        assert_eq!(fit.len(), 2);
+        assert_eq!((fit[0].0, fit[1].0), (1, 3));
+        assert!((fit[0].1 - 3.0).abs() < 1e-12 && (fit[1].1 + 2.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,11 @@
+// The orthogonalization routines grouped in one place. Everything here is also re-exported from the crate root.
+pub use crate::{
+    coordinates_of, from_coordinates, gram_schmidt_distributed, gram_schmidt_dual, gram_schmidt_dyn,
+    gram_schmidt_extend, gram_schmidt_mixed, gram_schmidt_ordered, gram_schmidt_partial, gram_schmidt_pivoted,
+    gram_schmidt_quaternion, gram_schmidt_resume, gram_schmidt_storage, gram_schmidt_warm_start,
+    gram_schmidt_with_policy, gram_schmidt_with_workspace, lowdin_orthonormalize, orthonormalize_rows,
+    try_gram_schmidt_storage, Checkpoint, GsWorkspace, IncrementalGramSchmidt, OrthonormalBasis, OutputOrder,
+    ZeroVectorPolicy,
+};
+#[cfg(feature = "rayon")]
+pub use crate::{par_gram_schmidt_batch, par_try_gram_schmidt_batch};
@@ -0,0 +1,57 @@
+use rayon::prelude::*;
+
+use crate::{Error, Vector};
+
+// Orthonormalizes many independent bases, distributing whole bases across rayon's thread pool. Every basis is processed
+// by the sequential `Vector::gram_schmidt` on one thread and written back in place, so the output is bitwise identical
+// to a sequential loop regardless of the number of threads or the scheduling. Meant for large numbers of small
+// problems, where parallelizing inside a single basis does not pay off.
+pub fn par_gram_schmidt_batch<V: Vector + Send, B: AsMut<[V]> + Send>(bases: &mut [B]) {
+    bases.par_iter_mut().for_each(|basis| V::gram_schmidt(basis.as_mut()));
+}
+
+// Like `par_gram_schmidt_batch` with `Vector::try_gram_schmidt`: returns one result per basis, in input order. Bases
+// that fail are left untouched.
+pub fn par_try_gram_schmidt_batch<V: Vector + Send, B: AsMut<[V]> + Send>(bases: &mut [B]) -> Vec<Result<(), Error>> {
+    return bases.par_iter_mut().map(|basis| V::try_gram_schmidt(basis.as_mut())).collect();
+}
+
+#[cfg(test)]
+mod parallel_test {
+    use crate::{par_gram_schmidt_batch, par_try_gram_schmidt_batch, Error, Vector3};
+
+    fn bases(count: usize) -> Vec<Vec<Vector3>> {
+        return (0..count).map(|k| {
+            let t = k as f64 * 0.01;
+            vec![
+                Vector3::new([1.0, t, 0.5]),
+                Vector3::new([t.sin(), 1.0, -t]),
+                Vector3::new([0.25, t.cos(), 1.0 + t]),
+            ]
+        }).collect();
+    }
+
+    #[test]
+    fn matches_sequential_bitwise() {
+        let mut parallel = bases(10_000);
+        let mut sequential = parallel.clone();
+        par_gram_schmidt_batch(&mut parallel);
+        for basis in sequential.iter_mut() {
+            Vector3::gram_schmidt(basis);
+        }
+        assert_eq!(parallel, sequential);
+
+        let mut arrays: Vec<[Vector3; 3]> = bases(100).into_iter().map(|b| b.try_into().unwrap()).collect();
+        par_gram_schmidt_batch(&mut arrays);
+        assert_eq!(arrays[99].as_slice(), sequential[99].as_slice());
+    }
+
+    #[test]
+    fn reports_failures_per_basis() {
+        let mut input = bases(3);
+        input[1][2][0] = f64::NAN;
+        let results = par_try_gram_schmidt_batch(&mut input);
+        assert_eq!(results, [Ok(()), Err(Error::NonFinite { vector: 2, component: 0 }), Ok(())]);
+        assert!(input[1][2][0].is_nan() && input[1][0][1] == 0.01);
+    }
+}
@@ -0,0 +1,53 @@
+use crate::Vector;
+
+// Schmidt orthogonalization restricted to a hierarchy: vector `i` is orthogonalized only against the vectors listed in
+// `parents[i]`, all of which must come before it, and then normalized. Vectors that are not related stay as they are
+// relative to each other, which keeps the sparsity of hierarchical and multilevel bases (e.g. in FEM) that a full
+// Gram-Schmidt would destroy. Each vector ends up orthogonal to its parents as long as the parents of one vector are
+// mutually orthogonal.
+pub fn gram_schmidt_partial<V: Vector>(vectors: &mut [V], parents: &[Vec<usize>]) {
+    assert_eq!(parents.len(), vectors.len(), "every vector needs a parent list");
+    for (index, parents) in parents.iter().enumerate() {
+        let (a, previous) = vectors[..=index].split_last_mut().unwrap();
+        for &parent in parents {
+            assert!(parent < index, "parent {} of vector {} does not come before it", parent, index);
+            let b = &previous[parent];
+            let dot = V::dot_product(a, b);
+            a.axpy(-dot, b);
+        }
+        a.normalize();
+    }
+}
+
+#[cfg(test)]
+mod partial_test {
+    use crate::{gram_schmidt_partial, Vector, Vector4};
+
+    #[test]
+    fn orthogonal_to_parents_only() {
+        let mut vectors = vec![
+            Vector4::new([1.0, 1.0, 0.0, 0.0]),
+            Vector4::new([1.0, 0.0, 1.0, 0.0]),
+            Vector4::new([1.0, 0.0, 0.0, 1.0]),
+            Vector4::new([0.0, 0.0, 1.0, 1.0]),
+        ];
+        // Two children of the root; the last vector only refines the second child.
+        let parents = vec![vec![], vec![0], vec![0], vec![2]];
+        gram_schmidt_partial(&mut vectors, &parents);
+        for (i, parents) in parents.iter().enumerate() {
+            assert!((vectors[i].length() - 1.0).abs() < 1e-12);
+            for &p in parents {
+                assert!(Vector4::dot_product(&vectors[i], &vectors[p]).abs() < 1e-12);
+            }
+        }
+        // Siblings are left coupled.
+        assert!(Vector4::dot_product(&vectors[1], &vectors[2]).abs() > 0.1);
+
+        // With every previous vector as a parent it is the full Gram-Schmidt.
+        let input = vec![Vector4::new([1.0, 1.0, 1.0, 1.0]), Vector4::new([0.0, 1.0, 0.0, 1.0])];
+        let (mut partial, mut full) = (input.clone(), input);
+        gram_schmidt_partial(&mut partial, &[vec![], vec![0]]);
+        Vector4::gram_schmidt(&mut full);
+        assert_eq!(partial, full);
+    }
+}
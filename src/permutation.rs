@@ -0,0 +1,226 @@
+use crate::{Error, Matrix, Vector};
+
+// Reordering of `n` items: applying it puts input item `indices()[i]` at output position `i`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Permutation {
+    indices: Vec<usize>,
+}
+
+impl Permutation {
+    pub fn identity(n: usize) -> Self {
+        return Self { indices: (0..n).collect() };
+    }
+
+    // Returns `None` unless `indices` contains every number in `0..indices.len()` exactly once.
+    pub fn new(indices: Vec<usize>) -> Option<Self> {
+        let mut seen = vec![false; indices.len()];
+        for &index in &indices {
+            if index >= seen.len() || seen[index] {
+                return None;
+            }
+            seen[index] = true;
+        }
+        return Some(Self { indices });
+    }
+
+    pub fn indices(&self) -> &[usize] {
+        return &self.indices;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.indices.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.indices.is_empty();
+    }
+
+    pub fn swap(&mut self, a: usize, b: usize) {
+        self.indices.swap(a, b);
+    }
+
+    pub fn apply<T: Clone>(&self, items: &[T]) -> Vec<T> {
+        assert_eq!(items.len(), self.len(), "permutation length must match");
+        return self.indices.iter().map(|&i| items[i].clone()).collect();
+    }
+
+    // Like `apply`, but reports a length mismatch instead of panicking.
+    pub fn try_apply<T: Clone>(&self, items: &[T]) -> Result<Vec<T>, Error> {
+        if items.len() != self.len() {
+            return Err(Error::DimensionMismatch { expected: self.len(), found: items.len() });
+        }
+        return Ok(self.apply(items));
+    }
+
+    pub fn inverse(&self) -> Self {
+        let mut indices = vec![0; self.len()];
+        for (position, &index) in self.indices.iter().enumerate() {
+            indices[index] = position;
+        }
+        return Self { indices };
+    }
+
+    // The permutation that applies `other` first and then `self`.
+    pub fn compose(&self, other: &Self) -> Self {
+        assert_eq!(self.len(), other.len(), "permutation lengths must match");
+        return Self { indices: self.indices.iter().map(|&i| other.indices[i]).collect() };
+    }
+
+    // The matrix `P` with `P x = apply(x)`.
+    pub fn to_matrix(&self) -> Matrix {
+        let mut p = Matrix::zeros(self.len(), self.len());
+        for (row, &col) in self.indices.iter().enumerate() {
+            p[(row, col)] = 1.0;
+        }
+        return p;
+    }
+}
+
+// Modified Gram-Schmidt with column pivoting: every step moves the remaining vector with the largest residual norm to
+// the front before normalizing it, which reveals the numerical rank. Afterwards `vectors[k]` belongs to input vector
+// `permutation.indices()[k]`. Returns the permutation and the rank, the number of leading vectors whose residual
+// exceeded `tolerance` times the largest input norm; the vectors past the rank are left as the small residuals.
+pub fn gram_schmidt_pivoted<V: Vector>(vectors: &mut [V], tolerance: f64) -> (Permutation, usize) {
+    #[cfg(feature = "metrics")]
+    let _timer = crate::telemetry::orthogonalization("gram_schmidt_pivoted");
+    let mut permutation = Permutation::identity(vectors.len());
+    let largest = vectors.iter().map(|v| v.length()).fold(0.0, f64::max);
+    for k in 0..vectors.len() {
+        let (pivot, norm) = (k..vectors.len())
+            .map(|i| (i, vectors[i].length()))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .unwrap();
+        if norm <= tolerance * largest {
+            #[cfg(feature = "metrics")]
+            crate::telemetry::rank_deficiencies("gram_schmidt_pivoted", (vectors.len() - k) as u64);
+            return (permutation, k);
+        }
+        vectors.swap(k, pivot);
+        permutation.swap(k, pivot);
+        vectors[k] = vectors[k].clone() / norm;
+        let (done, rest) = vectors.split_at_mut(k + 1);
+        for v in rest {
+            let dot = V::dot_product(&done[k], v);
+            v.axpy(-dot, &done[k]);
+        }
+    }
+    return (permutation, vectors.len());
+}
+
+// Order of the output of `gram_schmidt_ordered`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputOrder {
+    // The order in which pivoting picked the vectors, as in `gram_schmidt_pivoted`.
+    #[default]
+    Pivot,
+    // Every output vector at the position of the input vector it came from.
+    Input,
+    // Orthonormalized in input order without pivoting, then sorted by decreasing residual norm before normalization,
+    // i.e. by how much of each input was independent of the inputs before it. Ties keep the input order. (The pivoted
+    // residuals never increase, so sorting them would just give the pivot order.)
+    ResidualNorm,
+}
+
+// Orthonormalizes like `gram_schmidt_pivoted` and returns the output in `order`. The returned permutation maps the
+// output to the input as before, `vectors[k]` belonging to input vector `permutation.indices()[k]`, and the rank still
+// counts the vectors above the tolerance, which need no longer be the leading ones.
+pub fn gram_schmidt_ordered<V: Vector>(vectors: &mut [V], tolerance: f64, order: OutputOrder) -> (Permutation, usize) {
+    return match order {
+        OutputOrder::Pivot => gram_schmidt_pivoted(vectors, tolerance),
+        OutputOrder::Input => {
+            let (permutation, rank) = gram_schmidt_pivoted(vectors, tolerance);
+            let reordered = permutation.inverse().apply(vectors);
+            vectors.clone_from_slice(&reordered);
+            (Permutation::identity(vectors.len()), rank)
+        }
+        OutputOrder::ResidualNorm => {
+            let (residuals, rank) = unpivoted(vectors, tolerance);
+            let mut indices: Vec<usize> = (0..vectors.len()).collect();
+            indices.sort_by(|&a, &b| residuals[b].total_cmp(&residuals[a]));
+            let permutation = Permutation { indices };
+            let reordered = permutation.apply(vectors);
+            vectors.clone_from_slice(&reordered);
+            (permutation, rank)
+        }
+    };
+}
+
+// Modified Gram-Schmidt in input order that leaves vectors with a residual below `tolerance` times the largest input
+// norm as they are and does not project the later ones onto them. Returns the residual norms and the rank.
+fn unpivoted<V: Vector>(vectors: &mut [V], tolerance: f64) -> (Vec<f64>, usize) {
+    let largest = vectors.iter().map(|v| v.length()).fold(0.0, f64::max);
+    let mut accepted = vec![false; vectors.len()];
+    let mut residuals = Vec::with_capacity(vectors.len());
+    for k in 0..vectors.len() {
+        let (done, rest) = vectors.split_at_mut(k);
+        let v = &mut rest[0];
+        for (q, _) in done.iter().zip(&accepted).filter(|(_, accepted)| **accepted) {
+            let dot = V::dot_product(q, v);
+            v.axpy(-dot, q);
+        }
+        let norm = v.length();
+        residuals.push(norm);
+        if norm > tolerance * largest {
+            *v = v.clone() / norm;
+            accepted[k] = true;
+        }
+    }
+    return (residuals, accepted.iter().filter(|a| **a).count());
+}
+
+#[cfg(test)]
+mod permutation_test {
+    use crate::{gram_schmidt_ordered, gram_schmidt_pivoted, Matrix, OutputOrder, Permutation, Vector, Vector3};
+
+    #[test]
+    fn algebra() {
+        let p = Permutation::new(vec![2, 0, 1]).unwrap();
+        let q = Permutation::new(vec![1, 0, 2]).unwrap();
+        assert!(Permutation::new(vec![0, 0, 1]).is_none());
+        assert_eq!(p.apply(&['a', 'b', 'c']), vec!['c', 'a', 'b']);
+        assert_eq!(p.compose(&p.inverse()), Permutation::identity(3));
+        assert_eq!(p.compose(&q).apply(&['a', 'b', 'c']), p.apply(&q.apply(&['a', 'b', 'c'])));
+        let x = [1.0, 2.0, 3.0];
+        let px = &p.to_matrix() * &Matrix::from_columns(&[x.to_vec()]);
+        assert_eq!(px.column(0), p.apply(&x).as_slice());
+    }
+
+    #[test]
+    fn pivoting_reveals_rank() {
+        let inputs = vec![
+            Vector3::new([1.0, 0.0, 0.0]),
+            Vector3::new([0.0, 5.0, 0.0]),
+            Vector3::new([2.0, 10.0, 0.0]),
+        ];
+        let mut vectors = inputs.clone();
+        let (permutation, rank) = gram_schmidt_pivoted(&mut vectors, 1e-12);
+        assert_eq!(rank, 2);
+        assert_eq!(permutation.indices()[0], 2);
+        assert!((Vector3::dot_product(&vectors[0], &inputs[2]) - inputs[2].length()).abs() < 1e-12);
+        assert!(Vector3::dot_product(&vectors[0], &vectors[1]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn output_orders() {
+        let inputs = vec![
+            Vector3::new([1.0, 0.0, 0.0]),
+            Vector3::new([3.0, 0.0, 4.0]),
+            Vector3::new([0.0, 0.0, 2.0]),
+        ];
+        let mut pivot_order = inputs.clone();
+        let (pivot, _) = gram_schmidt_pivoted(&mut pivot_order, 1e-12);
+        assert_eq!(pivot.indices(), [1, 2, 0]);
+
+        let mut in_input_order = inputs.clone();
+        let (permutation, rank) = gram_schmidt_ordered(&mut in_input_order, 1e-12, OutputOrder::Input);
+        assert_eq!((permutation, rank), (Permutation::identity(3), 2));
+        assert_eq!(in_input_order, pivot.inverse().apply(&pivot_order));
+
+        // Unpivoted residuals 1, 4 and 0: the last input lies in the span of the first two.
+        let mut by_norm = inputs.clone();
+        let (permutation, rank) = gram_schmidt_ordered(&mut by_norm, 1e-12, OutputOrder::ResidualNorm);
+        assert_eq!((permutation.indices(), rank), (&[1, 0, 2][..], 2));
+        assert_eq!(by_norm[..2], [Vector3::E2, Vector3::E0]);
+        assert!(by_norm[2].length() < 1e-12);
+    }
+}
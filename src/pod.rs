@@ -0,0 +1,45 @@
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    AlignedVector16, AlignedVector4, AlignedVector8, Vector10, Vector11, Vector12, Vector13, Vector14, Vector15,
+    Vector16, Vector2, Vector3, Vector4, Vector5, Vector6, Vector7, Vector8, Vector9,
+};
+
+// The fixed-size vectors are `#[repr(C)]` wrappers around `[f64; DIM]` without padding (the aligned ones have a size
+// that is a multiple of their alignment), so any bit pattern is valid and they can be cast to and from bytes, e.g.
+// for GPU buffers or memory-mapped files.
+macro_rules! pod {
+    ($($name:ident),*) => {
+        $(
+            // SAFETY: all-zero components are a valid vector.
+            unsafe impl Zeroable for $name {}
+
+            // SAFETY: `#[repr(C)]` with a single `[f64; DIM]` field and no padding; every bit pattern is an `f64`.
+            unsafe impl Pod for $name {}
+        )*
+    };
+}
+
+pod!(
+    Vector2, Vector3, Vector4, Vector5, Vector6, Vector7, Vector8, Vector9, Vector10, Vector11, Vector12, Vector13,
+    Vector14, Vector15, Vector16, AlignedVector4, AlignedVector8, AlignedVector16
+);
+
+#[cfg(test)]
+mod pod_test {
+    use crate::{AlignedVector4, Vector, Vector3};
+
+    #[test]
+    fn casts_without_copying() {
+        let components = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let vectors: &[Vector3] = bytemuck::cast_slice(&components);
+        assert_eq!(vectors, [Vector3::new([1.0, 2.0, 3.0]), Vector3::new([4.0, 5.0, 6.0])]);
+        assert_eq!(vectors.as_ptr() as *const f64, components.as_ptr());
+
+        let aligned = [AlignedVector4::E1, AlignedVector4::zero()];
+        let bytes: &[u8] = bytemuck::cast_slice(&aligned);
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(bytemuck::cast_slice::<u8, f64>(bytes)[1], 1.0);
+        assert_eq!(<Vector3 as bytemuck::Zeroable>::zeroed(), Vector3::zero());
+    }
+}
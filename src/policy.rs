@@ -0,0 +1,107 @@
+use crate::{Error, Vector};
+
+// Residual norm, relative to the input norm, below which a vector counts as numerically zero.
+const ZERO_TOLERANCE: f64 = 1e-10;
+
+// What `gram_schmidt_with_policy` does with a vector that is zero or becomes numerically zero after the projections,
+// i.e. one that lies in the span of the vectors before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ZeroVectorPolicy {
+    // Stop and report `Error::ZeroVector`.
+    #[default]
+    Error,
+    // Drop the vector from the output.
+    Skip,
+    // Replace it by a unit vector orthogonal to all previous ones, so the output keeps one vector per input.
+    ReplaceWithComplement,
+}
+
+// Orthonormalizes `basis` like `Vector::gram_schmidt`, handling dependent vectors according to `policy`. With `Skip`
+// the output can be shorter than the input. On error the vectors before the offending one are already orthonormal.
+pub fn gram_schmidt_with_policy<V: Vector>(
+    basis: &mut Vec<V>,
+    policy: ZeroVectorPolicy,
+) -> Result<(), Error> {
+    #[cfg(feature = "metrics")]
+    let _timer = crate::telemetry::orthogonalization("gram_schmidt_with_policy");
+    let inputs = std::mem::take(basis);
+    for (index, mut v) in inputs.into_iter().enumerate() {
+        let scale = v.length();
+        for q in basis.iter() {
+            let dot = V::dot_product(q, &v);
+            v.axpy(-dot, q);
+        }
+        let norm = v.length();
+        if norm > ZERO_TOLERANCE * scale && norm > 0.0 {
+            basis.push(v / norm);
+            continue;
+        }
+        #[cfg(feature = "metrics")]
+        crate::telemetry::rank_deficiencies("gram_schmidt_with_policy", 1);
+        match policy {
+            ZeroVectorPolicy::Error => return Err(Error::ZeroVector { vector: index }),
+            ZeroVectorPolicy::Skip => {}
+            ZeroVectorPolicy::ReplaceWithComplement => match complement(basis) {
+                Some(q) => basis.push(q),
+                None => return Err(Error::ZeroVector { vector: index }),
+            },
+        }
+    }
+    return Ok(());
+}
+
+// The unit vector orthogonal to the orthonormal `basis` obtained from the coordinate axis with the largest residual,
+// or `None` if the basis already spans the space.
+pub(crate) fn complement<V: Vector>(basis: &[V]) -> Option<V> {
+    if basis.len() >= V::DIM {
+        return None;
+    }
+    let mut best = V::zero();
+    let mut best_norm = 0.0;
+    for axis in 0..V::DIM {
+        let mut v = V::unit(axis);
+        for _ in 0..2 {
+            for q in basis {
+                let dot = V::dot_product(q, &v);
+                v.axpy(-dot, q);
+            }
+        }
+        let norm = v.length();
+        if norm > best_norm {
+            best = v;
+            best_norm = norm;
+        }
+    }
+    return Some(best / best_norm);
+}
+
+#[cfg(test)]
+mod policy_test {
+    use crate::{gram_schmidt_with_policy, Error, Vector, Vector3, ZeroVectorPolicy};
+
+    fn inputs() -> Vec<Vector3> {
+        return vec![Vector3::new([1.0, 1.0, 0.0]), Vector3::new([2.0, 2.0, 0.0]), Vector3::new([0.0, 1.0, 0.0])];
+    }
+
+    #[test]
+    fn policies() {
+        let mut basis = inputs();
+        let result = gram_schmidt_with_policy(&mut basis, ZeroVectorPolicy::Error);
+        assert_eq!(result, Err(Error::ZeroVector { vector: 1 }));
+        assert_eq!(basis.len(), 1);
+
+        let mut basis = inputs();
+        gram_schmidt_with_policy(&mut basis, ZeroVectorPolicy::Skip).unwrap();
+        assert_eq!(basis.len(), 2);
+
+        let mut basis = inputs();
+        gram_schmidt_with_policy(&mut basis, ZeroVectorPolicy::ReplaceWithComplement).unwrap();
+        assert_eq!(basis.len(), 3);
+        for i in 0..3 {
+            for j in 0..3 {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((Vector3::dot_product(&basis[i], &basis[j]) - expected).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::cell::{Cell, RefCell};
+use std::ops::{Deref, DerefMut};
+
+use crate::Storage;
+
+// Recycles temporary vectors of runtime dimension across solver iterations. `acquire` hands out a zeroed vector
+// behind a guard that returns it to the pool when dropped, so a loop that acquires the same number of temporaries
+// every iteration only allocates during the first one. `lsqr_pooled` draws all its vectors from such pools.
+pub struct VectorPool<S: Storage> {
+    free: RefCell<Vec<S>>,
+    make: Box<dyn Fn() -> S>,
+    allocations: Cell<usize>,
+}
+
+impl<S: Storage> VectorPool<S> {
+    // `make` creates a new vector when the pool is empty; all vectors must have the same length.
+    pub fn new<F: Fn() -> S + 'static>(make: F) -> Self {
+        return Self { free: RefCell::new(vec![]), make: Box::new(make), allocations: Cell::new(0) };
+    }
+
+    pub fn acquire(&self) -> Pooled<'_, S> {
+        let vector = match self.free.borrow_mut().pop() {
+            Some(mut vector) => {
+                vector.as_mut_slice().fill(0.0);
+                vector
+            }
+            None => {
+                self.allocations.set(self.allocations.get() + 1);
+                (self.make)()
+            }
+        };
+        return Pooled { pool: self, vector: Some(vector) };
+    }
+
+    // Returns the vector to the pool; the same as dropping the guard.
+    pub fn release(&self, guard: Pooled<'_, S>) {
+        drop(guard);
+    }
+
+    // Number of vectors `make` has created so far.
+    pub fn allocations(&self) -> usize {
+        return self.allocations.get();
+    }
+
+    // Number of vectors waiting to be reused.
+    pub fn available(&self) -> usize {
+        return self.free.borrow().len();
+    }
+}
+
+impl VectorPool<Vec<f64>> {
+    pub fn with_dim(dim: usize) -> Self {
+        return Self::new(move || vec![0.0; dim]);
+    }
+}
+
+// A vector borrowed from a `VectorPool`.
+pub struct Pooled<'a, S: Storage> {
+    pool: &'a VectorPool<S>,
+    vector: Option<S>,
+}
+
+impl<S: Storage> Pooled<'_, S> {
+    // Keeps the vector instead of returning it to the pool.
+    pub fn into_inner(mut self) -> S {
+        return self.vector.take().expect("vector is present until drop");
+    }
+}
+
+impl<S: Storage> Deref for Pooled<'_, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        return self.vector.as_ref().expect("vector is present until drop");
+    }
+}
+
+impl<S: Storage> DerefMut for Pooled<'_, S> {
+    fn deref_mut(&mut self) -> &mut S {
+        return self.vector.as_mut().expect("vector is present until drop");
+    }
+}
+
+impl<S: Storage> Drop for Pooled<'_, S> {
+    fn drop(&mut self) {
+        if let Some(vector) = self.vector.take() {
+            self.pool.free.borrow_mut().push(vector);
+        }
+    }
+}
+
+#[cfg(test)]
+mod pool_test {
+    use crate::{storage_axpy, VectorPool};
+
+    #[test]
+    fn recycles_zeroed_vectors() {
+        let pool = VectorPool::with_dim(3);
+        for iteration in 0..10 {
+            let mut a = pool.acquire();
+            let mut b = pool.acquire();
+            assert_eq!((a.as_slice(), b.as_slice()), (&[0.0; 3][..], &[0.0; 3][..]));
+            a.copy_from_slice(&[1.0, 2.0, iteration as f64]);
+            storage_axpy(2.0, &*a, &mut *b);
+            assert_eq!(b[1], 4.0);
+            pool.release(a);
+        }
+        assert_eq!(pool.allocations(), 2);
+        assert_eq!(pool.available(), 2);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{Error, Matrix, Storage, UpperTriangular, Vector};
+
+// Right preconditioner `M` for the iterative solvers: `apply(v) = M⁻¹ v` and `apply_transpose(v) = M⁻ᵀ v`. The
+// solvers work with `A M⁻¹` in place of `A` and map the result back, so a good `M` makes `A M⁻¹` well conditioned.
+pub trait Preconditioner<V> {
+    fn apply(&self, v: &V) -> V;
+
+    fn apply_transpose(&self, v: &V) -> V;
+}
+
+// Diagonal (Jacobi) preconditioner. For least-squares problems the diagonal is usually the column norms of `A`,
+// which equalizes the columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jacobi<V: Vector> {
+    inverse_diagonal: V,
+}
+
+impl<V: Vector> Jacobi<V> {
+    // Zero diagonal entries are treated as ones, leaving those components unscaled.
+    pub fn new(diagonal: &V) -> Self {
+        let mut inverse_diagonal = V::zero();
+        for i in 0..V::DIM {
+            inverse_diagonal[i] = if diagonal[i] == 0.0 { 1.0 } else { 1.0 / diagonal[i] };
+        }
+        return Self { inverse_diagonal };
+    }
+
+    // Scales by the norms of the columns of `A`, given as one vector per column.
+    pub fn from_columns<U: Vector>(columns: &[U]) -> Self {
+        assert_eq!(columns.len(), V::DIM, "need one column per component");
+        let mut norms = V::zero();
+        for (i, column) in columns.iter().enumerate() {
+            norms[i] = column.length();
+        }
+        return Self::new(&norms);
+    }
+}
+
+impl<V: Vector> Preconditioner<V> for Jacobi<V> {
+    fn apply(&self, v: &V) -> V {
+        let mut result = v.clone();
+        for i in 0..V::DIM {
+            result[i] *= self.inverse_diagonal[i];
+        }
+        return result;
+    }
+
+    fn apply_transpose(&self, v: &V) -> V {
+        return self.apply(v);
+    }
+}
+
+// Incomplete orthogonalization preconditioner: the `R` factor of a modified Gram-Schmidt run over the columns of `A`
+// in which every projection coefficient `|r_ij| < drop_tolerance * ‖a_j‖` is dropped, so the corresponding
+// projection is skipped. With a zero tolerance this is the exact `R` and `A R⁻¹` has orthonormal columns; larger
+// tolerances give a sparser, cheaper `R`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncompleteOrthogonalization {
+    r: UpperTriangular,
+}
+
+impl IncompleteOrthogonalization {
+    pub fn new<U: Vector>(columns: &[U], drop_tolerance: f64) -> Result<Self, Error> {
+        if columns.is_empty() {
+            return Err(Error::Empty);
+        }
+        let n = columns.len();
+        let mut r = Matrix::zeros(n, n);
+        let mut q: Vec<U> = Vec::with_capacity(n);
+        for (j, column) in columns.iter().enumerate() {
+            let mut v = column.clone();
+            let scale = v.length();
+            for (i, qi) in q.iter().enumerate() {
+                let dot = U::dot_product(qi, &v);
+                if dot.abs() >= drop_tolerance * scale {
+                    v.axpy(-dot, qi);
+                    r[(i, j)] = dot;
+                }
+            }
+            let norm = v.length();
+            if norm == 0.0 {
+                return Err(Error::ZeroVector { vector: j });
+            }
+            r[(j, j)] = norm;
+            q.push(v / norm);
+        }
+        return Ok(Self { r: UpperTriangular::new(r) });
+    }
+
+    pub fn r(&self) -> &UpperTriangular {
+        return &self.r;
+    }
+}
+
+// Applies to any storage of the right length, including vectors whose dimension is chosen at runtime.
+impl<V: Storage + Clone> Preconditioner<V> for IncompleteOrthogonalization {
+    fn apply(&self, v: &V) -> V {
+        let mut result = v.clone();
+        result.as_mut_slice().copy_from_slice(&self.r.solve(v.as_slice()));
+        return result;
+    }
+
+    fn apply_transpose(&self, v: &V) -> V {
+        let mut result = v.clone();
+        result.as_mut_slice().copy_from_slice(&self.r.solve_transpose(v.as_slice()));
+        return result;
+    }
+}
+
+#[cfg(test)]
+mod preconditioner_test {
+    use crate::{IncompleteOrthogonalization, Jacobi, Preconditioner, Vector, Vector3};
+
+    #[test]
+    fn jacobi_scales_by_inverse_diagonal() {
+        let jacobi = Jacobi::new(&Vector3::new([2.0, 0.0, -4.0]));
+        let v = Vector3::new([1.0, 1.0, 1.0]);
+        assert_eq!(jacobi.apply(&v), Vector3::new([0.5, 1.0, -0.25]));
+        assert_eq!(jacobi.apply_transpose(&v), jacobi.apply(&v));
+    }
+
+    #[test]
+    fn incomplete_orthogonalization_drops_small_coefficients() {
+        let columns = [Vector3::new([1.0, 0.0, 0.0]), Vector3::new([1e-3, 1.0, 0.0]), Vector3::new([1.0, 1.0, 1.0])];
+        let exact = IncompleteOrthogonalization::new(&columns, 0.0).unwrap();
+        let incomplete = IncompleteOrthogonalization::new(&columns, 1e-2).unwrap();
+        assert_eq!(exact.r()[(0, 1)], 1e-3);
+        assert_eq!(incomplete.r()[(0, 1)], 0.0);
+        assert_eq!(incomplete.r()[(0, 2)], 1.0);
+        // `R Rᵀ` undoes `R⁻ᵀ R⁻¹`.
+        let v = Vector3::new([1.0, 2.0, 3.0]);
+        let back: Vector3 = exact.apply_transpose(&exact.apply(&v));
+        let r = exact.r();
+        let rrt = |i: usize, j: usize| (0..3).map(|k| r[(i, k)] * r[(j, k)]).sum::<f64>();
+        let restored = Vector3::from_fn(|i| (0..3).map(|j| rrt(i, j) * back[j]).sum());
+        assert!((restored - v).length() < 1e-12);
+    }
+}
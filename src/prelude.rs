@@ -0,0 +1,20 @@
+// `use gram_schmidt::prelude::*;` brings in what most callers need: the `Vector` trait, the concrete vectors, the
+// reusable and incremental orthogonalizers, the error types and the subspace types.
+pub use crate::{
+    AffineSubspace, Error, GramSchmidtError, GsWorkspace, IncrementalGramSchmidt, Line, Matrix, OrthonormalBasis,
+    Plane, Vector, Vector10, Vector11, Vector12, Vector13, Vector14, Vector15, Vector16, Vector2, Vector3, Vector4,
+    Vector5, Vector6, Vector7, Vector8, Vector9, Warning, ZeroVectorPolicy,
+};
+
+#[cfg(test)]
+mod prelude_test {
+    use crate::prelude::*;
+
+    #[test]
+    fn prelude_is_enough_to_orthonormalize() {
+        let basis = OrthonormalBasis::gram_schmidt(vec![Vector3::new([2.0, 0.0, 0.0]), Vector3::new([1.0, 1.0, 0.0])]);
+        assert_eq!(basis.vectors(), [Vector3::E0, Vector3::E1]);
+        let mut invalid = vec![Vector3::new([f64::NAN, 0.0, 0.0])];
+        assert!(matches!(Vector3::try_gram_schmidt(&mut invalid), Err(Error::NonFinite { .. })));
+    }
+}
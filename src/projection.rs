@@ -0,0 +1,62 @@
+use rand::Rng;
+
+use crate::{gram_schmidt_storage, Matrix};
+
+// Projection onto a uniformly random `to_dim`-dimensional subspace of `R^from_dim`. The rows of the
+// `to_dim x from_dim` matrix are orthonormal, so unlike a plain Gaussian sketch it never stretches a vector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RandomProjection {
+    matrix: Matrix,
+}
+
+impl RandomProjection {
+    pub fn matrix(&self) -> &Matrix {
+        return &self.matrix;
+    }
+
+    pub fn apply(&self, v: &[f64]) -> Vec<f64> {
+        assert_eq!(v.len(), self.matrix.cols(), "vector length must match the source dimension");
+        return (0..self.matrix.rows())
+            .map(|i| (0..v.len()).map(|j| self.matrix[(i, j)] * v[j]).sum())
+            .collect();
+    }
+}
+
+// Standard normal sample by the Box-Muller transform.
+pub(crate) fn gaussian<R: Rng + ?Sized>(rng: &mut R) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    return (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos();
+}
+
+// Gram-Schmidt of `to_dim` Gaussian rows, which is distributed uniformly over the semi-orthogonal matrices.
+pub fn random_orthogonal_projection<R: Rng + ?Sized>(from_dim: usize, to_dim: usize, rng: &mut R) -> RandomProjection {
+    assert!(to_dim <= from_dim, "target dimension must not exceed the source dimension");
+    let mut rows: Vec<Vec<f64>> = (0..to_dim).map(|_| (0..from_dim).map(|_| gaussian(rng)).collect()).collect();
+    gram_schmidt_storage(&mut rows);
+    return RandomProjection { matrix: Matrix::from_rows(&rows) };
+}
+
+#[cfg(test)]
+mod projection_test {
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::projection::random_orthogonal_projection;
+    use crate::Matrix;
+
+    #[test]
+    fn rows_are_orthonormal() {
+        let projection = random_orthogonal_projection(8, 3, &mut StdRng::seed_from_u64(7));
+        let p = projection.matrix();
+        let gram = p * &p.transpose();
+        let error = &gram - &Matrix::identity(3);
+        assert!((0..3).all(|i| (0..3).all(|j| error[(i, j)].abs() < 1e-12)));
+
+        let v = [1.0, -2.0, 0.5, 3.0, 0.0, 1.0, -1.0, 2.0];
+        let projected = projection.apply(&v);
+        assert_eq!(projected.len(), 3);
+        let length = |x: &[f64]| x.iter().map(|c| c * c).sum::<f64>().sqrt();
+        assert!(length(&projected) <= length(&v));
+    }
+}
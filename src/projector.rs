@@ -0,0 +1,66 @@
+use crate::basis::extend_orthonormal;
+use crate::{Matrix, Vector};
+
+// Dense `DIM x DIM` projector `I - QQᵀ` onto the directions `x` with `dot(c, x) = 0` for every constraint `c`, where
+// `Q` is an orthonormal basis of the constraints' span. Linearly dependent constraints are allowed.
+pub fn null_space_projector<V: Vector>(constraints: &[V]) -> Matrix {
+    let mut q = vec![];
+    extend_orthonormal(&mut q, constraints.iter().cloned());
+    let mut projector = Matrix::identity(V::DIM);
+    for b in &q {
+        for j in 0..V::DIM {
+            for i in 0..V::DIM {
+                projector[(i, j)] -= b[i] * b[j];
+            }
+        }
+    }
+    return projector;
+}
+
+// `QQᵀ v` for an orthonormal `basis` `Q`, using only `basis.len()` dot products and updates, so no `DIM x DIM`
+// matrix is formed.
+pub fn apply_projector<V: Vector>(basis: &[V], v: &V) -> V {
+    let mut projection = V::zero();
+    for q in basis {
+        projection.axpy(V::dot_product(q, v), q);
+    }
+    return projection;
+}
+
+// `(I - QQᵀ) v` for an orthonormal `basis` `Q`, the implicit form of `null_space_projector`. The projections are
+// subtracted one after the other as in modified Gram-Schmidt, which keeps the result closer to orthogonal to the
+// basis than subtracting `apply_projector(basis, v)`.
+pub fn apply_complement_projector<V: Vector>(basis: &[V], v: &V) -> V {
+    let mut residual = v.clone();
+    for q in basis {
+        let dot = V::dot_product(q, &residual);
+        residual.axpy(-dot, q);
+    }
+    return residual;
+}
+
+#[cfg(test)]
+mod projector_test {
+    use crate::{apply_complement_projector, apply_projector, null_space_projector, Vector, Vector3};
+
+    #[test]
+    fn projects_onto_constraint_null_space() {
+        let constraints = [Vector3::new([1.0, 1.0, 0.0]), Vector3::new([2.0, 2.0, 0.0])];
+        let p = null_space_projector(&constraints);
+        let gradient = [3.0, 1.0, 5.0];
+        let projected = Vector3::from_fn(|i| (0..3).map(|j| p[(i, j)] * gradient[j]).sum());
+        assert!(Vector3::dot_product(&projected, &constraints[0]).abs() < 1e-12);
+        assert!((projected - Vector3::new([1.0, -1.0, 5.0])).length() < 1e-12);
+    }
+
+    #[test]
+    fn implicit_application_matches_matrix() {
+        let basis = [Vector3::new([0.6, 0.8, 0.0]), Vector3::E2];
+        let v = Vector3::new([1.0, 2.0, 3.0]);
+        let p = null_space_projector(&basis);
+        let expected = Vector3::from_fn(|i| (0..3).map(|j| p[(i, j)] * v[j]).sum());
+        let complement = apply_complement_projector(&basis, &v);
+        assert!((complement - expected).length() < 1e-12);
+        assert!((apply_projector(&basis, &v) + complement - v).length() < 1e-12);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::householder::{apply_reflector, reflector};
+use crate::{Error, Matrix};
+
+// The factor types QR-based code works with, the sensitivity estimate for them and the other decompositions built
+// from the same reflectors, so `gram_schmidt::qr` is self-contained.
+pub use crate::{
+    bidiagonalize, hessenberg, hessenberg_implicit, perturbation_bound, schur, HouseholderProduct, UpperTriangular,
+};
+
+// Thin `Q` factor of a QR factorization kept in factored form as the Householder reflectors that produced it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImplicitQ {
+    reflectors: HouseholderProduct,
+    cols: usize,
+}
+
+impl ImplicitQ {
+    pub fn rows(&self) -> usize {
+        return self.reflectors.dim();
+    }
+
+    pub fn cols(&self) -> usize {
+        return self.cols;
+    }
+
+    // `Q x` for `x` of length `cols`.
+    pub fn apply_q(&self, x: &[f64]) -> Vec<f64> {
+        assert_eq!(x.len(), self.cols, "vector length must match the number of columns");
+        let mut y = vec![0.0; self.rows()];
+        y[..self.cols].copy_from_slice(x);
+        self.reflectors.apply(&mut y);
+        return y;
+    }
+
+    // `Qᵀ y` for `y` of length `rows`.
+    pub fn apply_q_transpose(&self, y: &[f64]) -> Vec<f64> {
+        let mut x = y.to_vec();
+        self.reflectors.apply_transpose(&mut x);
+        x.truncate(self.cols);
+        return x;
+    }
+
+    pub fn to_matrix(&self) -> Matrix {
+        let mut q = Matrix::zeros(self.rows(), self.cols);
+        for j in 0..self.cols {
+            q.column_mut(j)[j] = 1.0;
+            self.reflectors.apply(q.column_mut(j));
+        }
+        return q;
+    }
+}
+
+// Thin QR factorization of an `m x n` matrix with `m >= n` via Householder reflections, with `Q` left in factored
+// form. Returns `Q` and the `n x n` upper triangular `R`.
+pub fn householder_qr_implicit(a: &Matrix) -> (ImplicitQ, Matrix) {
+    let (m, n) = (a.rows(), a.cols());
+    assert!(m >= n, "householder_qr needs at least as many rows as columns");
+    let mut work = a.clone();
+    let mut reflectors = HouseholderProduct::identity(m);
+    let mut r = Matrix::zeros(n, n);
+    for k in 0..n {
+        let (tau, beta) = reflector(&mut work.column_mut(k)[k..]);
+        let v = work.column(k)[k..].to_vec();
+        for j in k + 1..n {
+            apply_reflector(&v, tau, &mut work.column_mut(j)[k..]);
+        }
+        reflectors.push(k, v, tau);
+        r[(k, k)] = beta;
+    }
+    for j in 0..n {
+        for i in 0..j {
+            r[(i, j)] = work[(i, j)];
+        }
+    }
+    return (ImplicitQ { reflectors, cols: n }, r);
+}
+
+// Thin QR factorization of an `m x n` matrix with `m >= n`. Returns the `m x n` matrix `Q` with orthonormal columns
+// and the `n x n` upper triangular `R`.
+pub fn householder_qr(a: &Matrix) -> (Matrix, Matrix) {
+    #[cfg(feature = "metrics")]
+    let _timer = crate::telemetry::orthogonalization("householder_qr");
+    let (q, r) = householder_qr_implicit(a);
+    return (q.to_matrix(), r);
+}
+
+// Like `householder_qr`, but reports a wide matrix instead of panicking.
+pub fn try_householder_qr(a: &Matrix) -> Result<(Matrix, Matrix), Error> {
+    if a.rows() < a.cols() {
+        return Err(Error::DimensionMismatch { expected: a.cols(), found: a.rows() });
+    }
+    return Ok(householder_qr(a));
+}
+
+// Thin QR with the sign convention of Gram-Schmidt: the diagonal of `R` is non-negative.
+pub(crate) fn positive_qr(a: &Matrix) -> (Matrix, Matrix) {
+    let (mut q, mut r) = householder_qr(a);
+    for k in 0..r.rows() {
+        if r[(k, k)] < 0.0 {
+            q.column_mut(k).iter_mut().for_each(|c| *c = -*c);
+            for j in k..r.cols() {
+                r[(k, j)] = -r[(k, j)];
+            }
+        }
+    }
+    return (q, r);
+}
+
+// Tall-skinny QR of the matrix formed by stacking `blocks` on top of each other. Every block is factored on its own
+// and the small `R` factors are combined pairwise in a reduction tree, so the full matrix is never assembled. Returns
+// the row blocks of `Q`, matching `blocks`, and the common `R`.
+pub fn tsqr(blocks: &[Matrix]) -> (Vec<Matrix>, Matrix) {
+    assert!(!blocks.is_empty(), "tsqr needs at least one block");
+    let mut nodes: Vec<(Vec<Matrix>, Matrix)> = blocks.iter()
+        .map(|block| {
+            let (q, r) = householder_qr(block);
+            return (vec![q], r);
+        })
+        .collect();
+
+    while nodes.len() > 1 {
+        let mut next = Vec::with_capacity(nodes.len().div_ceil(2));
+        let mut iter = nodes.into_iter();
+        while let Some((mut q_top, r_top)) = iter.next() {
+            let Some((q_bottom, r_bottom)) = iter.next() else {
+                next.push((q_top, r_top));
+                break;
+            };
+            let n = r_top.rows();
+            let (q, r) = householder_qr(&Matrix::vstack(&r_top, &r_bottom));
+            let (top, bottom) = (q.row_block(0, n), q.row_block(n, 2 * n));
+            q_top = q_top.iter().map(|block| block * &top).collect();
+            q_top.extend(q_bottom.iter().map(|block| block * &bottom));
+            next.push((q_top, r));
+        }
+        nodes = next;
+    }
+    return nodes.pop().unwrap();
+}
+
+// Like `tsqr`, but reports an empty input, blocks of different widths or wider than tall instead of panicking.
+pub fn try_tsqr(blocks: &[Matrix]) -> Result<(Vec<Matrix>, Matrix), Error> {
+    let first = blocks.first().ok_or(Error::Empty)?;
+    for block in blocks {
+        if block.cols() != first.cols() {
+            return Err(Error::DimensionMismatch { expected: first.cols(), found: block.cols() });
+        }
+        if block.rows() < block.cols() {
+            return Err(Error::DimensionMismatch { expected: block.cols(), found: block.rows() });
+        }
+    }
+    return Ok(tsqr(blocks));
+}
+
+#[cfg(test)]
+mod qr_test {
+    use crate::{householder_qr, householder_qr_implicit, try_householder_qr, try_tsqr, tsqr, Error, Matrix};
+
+    fn assert_close(a: &Matrix, b: &Matrix) {
+        assert_eq!((a.rows(), a.cols()), (b.rows(), b.cols()));
+        for j in 0..a.cols() {
+            for i in 0..a.rows() {
+                assert!((a[(i, j)] - b[(i, j)]).abs() < 1e-12, "{:?} != {:?}", a, b);
+            }
+        }
+    }
+
+    fn tall() -> Matrix {
+        return Matrix::from_rows(&[
+            vec![1.0, 2.0],
+            vec![3.0, 4.0],
+            vec![5.0, 6.0],
+            vec![7.0, 9.0],
+            vec![2.0, 1.0],
+            vec![0.0, 1.0],
+        ]);
+    }
+
+    #[test]
+    fn householder_reconstructs() {
+        let a = tall();
+        let (q, r) = householder_qr(&a);
+        assert_close(&(&q * &r), &a);
+        assert_close(&(&q.transpose() * &q), &Matrix::identity(2));
+        assert_eq!(r[(1, 0)], 0.0);
+    }
+
+    #[test]
+    fn implicit_q_matches_dense() {
+        let a = tall();
+        let (implicit, r) = householder_qr_implicit(&a);
+        let (q, _) = householder_qr(&a);
+        assert_close(&implicit.to_matrix(), &q);
+
+        let x = [2.0, -1.0];
+        let qx = implicit.apply_q(&x);
+        for i in 0..6 {
+            assert!((qx[i] - (2.0 * q[(i, 0)] - q[(i, 1)])).abs() < 1e-12);
+        }
+        let qt_a0 = implicit.apply_q_transpose(a.column(0));
+        assert!((qt_a0[0] - r[(0, 0)]).abs() < 1e-12 && qt_a0[1].abs() < 1e-12);
+    }
+
+    #[test]
+    fn tsqr_matches_direct_factorization() {
+        let a = tall();
+        let blocks = [a.row_block(0, 2), a.row_block(2, 4), a.row_block(4, 6)];
+        let (q_blocks, r) = tsqr(&blocks);
+        assert_eq!(q_blocks.len(), 3);
+
+        let q = Matrix::vstack(&Matrix::vstack(&q_blocks[0], &q_blocks[1]), &q_blocks[2]);
+        assert_close(&(&q * &r), &a);
+        assert_close(&(&q.transpose() * &q), &Matrix::identity(2));
+
+        assert_eq!(try_tsqr(&[]), Err(Error::Empty));
+        assert!(try_householder_qr(&a.transpose()).is_err());
+
+        let (_, direct_r) = householder_qr(&a);
+        for i in 0..2 {
+            for j in 0..2 {
+                assert!((r[(i, j)].abs() - direct_r[(i, j)].abs()).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::Error;
+
+// Residual norm, relative to the input norm, below which a column counts as numerically zero.
+const ZERO_TOLERANCE: f64 = 1e-10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Quaternion {
+    pub w: f64,
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Quaternion {
+    pub const fn new(w: f64, x: f64, y: f64, z: f64) -> Self {
+        return Self { w, x, y, z };
+    }
+
+    pub const fn real(w: f64) -> Self {
+        return Self { w, x: 0.0, y: 0.0, z: 0.0 };
+    }
+
+    pub fn conj(self) -> Self {
+        return Self { w: self.w, x: -self.x, y: -self.y, z: -self.z };
+    }
+
+    pub fn norm_squared(self) -> f64 {
+        return self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z;
+    }
+
+    pub fn scale(self, lambda: f64) -> Self {
+        return Self { w: self.w * lambda, x: self.x * lambda, y: self.y * lambda, z: self.z * lambda };
+    }
+}
+
+impl Add for Quaternion {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        return Self { w: self.w + rhs.w, x: self.x + rhs.x, y: self.y + rhs.y, z: self.z + rhs.z };
+    }
+}
+
+impl Sub for Quaternion {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        return Self { w: self.w - rhs.w, x: self.x - rhs.x, y: self.y - rhs.y, z: self.z - rhs.z };
+    }
+}
+
+// Hamilton product, which is not commutative.
+impl Mul for Quaternion {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        return Self {
+            w: self.w * rhs.w - self.x * rhs.x - self.y * rhs.y - self.z * rhs.z,
+            x: self.w * rhs.x + self.x * rhs.w + self.y * rhs.z - self.z * rhs.y,
+            y: self.w * rhs.y - self.x * rhs.z + self.y * rhs.w + self.z * rhs.x,
+            z: self.w * rhs.z + self.x * rhs.y - self.y * rhs.x + self.z * rhs.w,
+        };
+    }
+}
+
+// Quaternion-valued inner product `<u, v> = sum conj(u_i) * v_i` of the right module of quaternion vectors.
+pub fn quaternion_inner(u: &[Quaternion], v: &[Quaternion]) -> Quaternion {
+    return u.iter().zip(v).fold(Quaternion::default(), |sum, (a, b)| sum + a.conj() * *b);
+}
+
+// Modified Gram-Schmidt over quaternion vectors, with scalars acting from the right: the projection of `v` onto a
+// unit vector `q` is `q * <q, v>`, which makes `<q, v - q * <q, v>>` vanish despite the non-commutative product.
+// Fails with `ZeroVector` for a column that is (numerically) in the span of the ones before it; those are orthonormal
+// by then.
+pub fn gram_schmidt_quaternion(columns: &mut [Vec<Quaternion>]) -> Result<(), Error> {
+    for index in 0..columns.len() {
+        let (done, rest) = columns.split_at_mut(index);
+        let column = &mut rest[0];
+        let scale = quaternion_inner(column, column).w.sqrt();
+        for q in done.iter() {
+            let coefficient = quaternion_inner(q, column);
+            for (c, qi) in column.iter_mut().zip(q) {
+                *c = *c - *qi * coefficient;
+            }
+        }
+        let norm = quaternion_inner(column, column).w.sqrt();
+        if !(norm > ZERO_TOLERANCE * scale && norm > 0.0) {
+            return Err(Error::ZeroVector { vector: index });
+        }
+        column.iter_mut().for_each(|c| *c = c.scale(1.0 / norm));
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod quaternion_test {
+    use crate::{gram_schmidt_quaternion, quaternion_inner, Error, Quaternion};
+
+    #[test]
+    fn hamilton_product() {
+        let i = Quaternion::new(0.0, 1.0, 0.0, 0.0);
+        let j = Quaternion::new(0.0, 0.0, 1.0, 0.0);
+        assert_eq!(i * j, Quaternion::new(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(j * i, Quaternion::new(0.0, 0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn orthonormal_under_quaternion_inner_product() {
+        let mut columns = vec![
+            vec![Quaternion::new(1.0, 2.0, 0.0, -1.0), Quaternion::new(0.5, 0.0, 1.0, 0.0)],
+            vec![Quaternion::new(0.0, 1.0, 1.0, 1.0), Quaternion::new(2.0, -1.0, 0.0, 3.0)],
+        ];
+        gram_schmidt_quaternion(&mut columns).unwrap();
+        for i in 0..2 {
+            for j in 0..2 {
+                let inner = quaternion_inner(&columns[i], &columns[j]);
+                let expected = if i == j { Quaternion::real(1.0) } else { Quaternion::default() };
+                assert!((inner - expected).norm_squared() < 1e-24);
+            }
+        }
+    }
+
+    #[test]
+    fn dependent_columns_are_reported() {
+        let first = vec![Quaternion::new(1.0, 2.0, 0.0, -1.0), Quaternion::new(0.5, 0.0, 1.0, 0.0)];
+        // A right multiple of the first column lies in its span.
+        let multiple = first.iter().map(|c| *c * Quaternion::new(0.0, 1.0, -2.0, 0.5)).collect();
+        let mut columns = vec![first, multiple];
+        assert_eq!(gram_schmidt_quaternion(&mut columns), Err(Error::ZeroVector { vector: 1 }));
+        let mut zero = vec![vec![Quaternion::default(); 2]];
+        assert_eq!(gram_schmidt_quaternion(&mut zero), Err(Error::ZeroVector { vector: 0 }));
+    }
+}
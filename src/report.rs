@@ -0,0 +1,119 @@
+use std::fmt;
+
+use crate::{gram_schmidt_pivoted, Vector};
+
+// Residual tolerance, relative to the largest input norm, used for the numerical rank.
+const RANK_TOLERANCE: f64 = 1e-10;
+
+// Diagnostics comparing the input and output of an orthogonalization, meant for bug reports and teaching. `Display`
+// renders it as text and `to_json` as a JSON object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BasisReport {
+    pub norms_before: Vec<f64>,
+    pub norms_after: Vec<f64>,
+    pub gram_before: Vec<Vec<f64>>,
+    pub gram_after: Vec<Vec<f64>>,
+    // Numerical rank of the input vectors.
+    pub rank: usize,
+    // Largest `|<q_i, q_j>|` over distinct output vectors.
+    pub max_off_diagonal_after: f64,
+    // Pairwise angle between output vectors farthest from 90 degrees.
+    pub worst_angle_degrees: f64,
+}
+
+fn gram<V: Vector>(vectors: &[V]) -> Vec<Vec<f64>> {
+    return vectors.iter().map(|a| vectors.iter().map(|b| V::dot_product(a, b)).collect()).collect();
+}
+
+impl BasisReport {
+    pub fn new<V: Vector>(before: &[V], after: &[V]) -> Self {
+        let gram_after = gram(after);
+        let mut max_off_diagonal_after: f64 = 0.0;
+        let mut worst_angle_degrees = 90.0;
+        for (i, a) in after.iter().enumerate() {
+            for (j, b) in after.iter().enumerate().skip(i + 1) {
+                max_off_diagonal_after = max_off_diagonal_after.max(gram_after[i][j].abs());
+                let angle = a.angle_between(b).to_degrees();
+                if (angle - 90.0).abs() > (worst_angle_degrees - 90.0_f64).abs() {
+                    worst_angle_degrees = angle;
+                }
+            }
+        }
+        let (_, rank) = gram_schmidt_pivoted(&mut before.to_vec(), RANK_TOLERANCE);
+        return Self {
+            norms_before: before.iter().map(|v| v.length()).collect(),
+            norms_after: after.iter().map(|v| v.length()).collect(),
+            gram_before: gram(before),
+            gram_after,
+            rank,
+            max_off_diagonal_after,
+            worst_angle_degrees,
+        };
+    }
+
+    pub fn to_json(&self) -> String {
+        let number = |x: f64| if x.is_finite() { format!("{:?}", x) } else { "null".to_string() };
+        let list = |xs: &[f64]| format!("[{}]", xs.iter().map(|x| number(*x)).collect::<Vec<_>>().join(","));
+        let matrix = |rows: &[Vec<f64>]| format!("[{}]", rows.iter().map(|r| list(r)).collect::<Vec<_>>().join(","));
+        return format!(
+            "{{\"norms_before\":{},\"norms_after\":{},\"gram_before\":{},\"gram_after\":{},\"rank\":{},\
+             \"max_off_diagonal_after\":{},\"worst_angle_degrees\":{}}}",
+            list(&self.norms_before),
+            list(&self.norms_after),
+            matrix(&self.gram_before),
+            matrix(&self.gram_after),
+            self.rank,
+            number(self.max_off_diagonal_after),
+            number(self.worst_angle_degrees),
+        );
+    }
+}
+
+impl fmt::Display for BasisReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "vectors: {}, numerical rank: {}", self.norms_before.len(), self.rank)?;
+        for (i, (before, after)) in self.norms_before.iter().zip(&self.norms_after).enumerate() {
+            writeln!(f, "  |v{}| = {:.6e} -> |q{}| = {:.6e}", i, before, i, after)?;
+        }
+        for (label, gram) in [("inner products before", &self.gram_before), ("inner products after", &self.gram_after)] {
+            writeln!(f, "{}:", label)?;
+            for row in gram.iter() {
+                let cells: Vec<String> = row.iter().map(|x| format!("{:>13.6e}", x)).collect();
+                writeln!(f, "  {}", cells.join(" "))?;
+            }
+        }
+        writeln!(f, "max |<q_i, q_j>|, i != j: {:.3e}", self.max_off_diagonal_after)?;
+        return write!(f, "worst pairwise angle: {:.9} degrees", self.worst_angle_degrees);
+    }
+}
+
+pub fn debug_report<V: Vector>(before: &[V], after: &[V]) -> String {
+    return BasisReport::new(before, after).to_string();
+}
+
+pub fn debug_report_json<V: Vector>(before: &[V], after: &[V]) -> String {
+    return BasisReport::new(before, after).to_json();
+}
+
+#[cfg(test)]
+mod report_test {
+    use crate::{debug_report, debug_report_json, BasisReport, Vector3};
+
+    #[test]
+    fn summarizes_run() {
+        let before = vec![Vector3::new([3.0, 4.0, 0.0]), Vector3::new([1.0, 0.0, 0.0]), Vector3::new([2.0, 0.0, 0.0])];
+        let mut after = before.clone();
+        Vector3::gram_schmidt(&mut after[..2]);
+        let report = BasisReport::new(&before, &after[..2]);
+        assert_eq!(report.rank, 2);
+        assert_eq!(report.norms_before[0], 5.0);
+        assert!(report.max_off_diagonal_after < 1e-12);
+        assert!((report.worst_angle_degrees - 90.0).abs() < 1e-9);
+
+        let text = debug_report(&before[..2], &after[..2]);
+        assert!(text.starts_with("vectors: 2, numerical rank: 2\n"));
+        let json = debug_report_json(&before[..1], &after[..1]);
+        assert_eq!(json, "{\"norms_before\":[5.0],\"norms_after\":[1.0],\"gram_before\":[[25.0]],\"gram_after\":[[1.0]],\
+                          \"rank\":1,\"max_off_diagonal_after\":0.0,\"worst_angle_degrees\":90.0}");
+    }
+}
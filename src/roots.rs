@@ -0,0 +1,96 @@
+use num_complex::Complex64;
+
+use crate::{schur, Error, Matrix};
+
+// Roots of `c[0] x^n + c[1] x^(n-1) + ... + c[n]` (highest degree first), as the eigenvalues of the companion matrix.
+// Leading zero coefficients are ignored, and each trailing zero contributes a root at 0. Sorted by real part, then
+// imaginary part. Fails if the eigenvalue iteration does not converge, e.g. for non-finite coefficients.
+pub fn roots(coefficients: &[f64]) -> Result<Vec<Complex64>, Error> {
+    let start = coefficients.iter().position(|c| *c != 0.0).unwrap_or(coefficients.len());
+    let coefficients = &coefficients[start..];
+    let end = coefficients.iter().rposition(|c| *c != 0.0).map_or(0, |i| i + 1);
+    let mut roots = vec![Complex64::new(0.0, 0.0); coefficients.len().saturating_sub(end)];
+
+    let degree = end.saturating_sub(1);
+    if degree > 0 {
+        let mut companion = Matrix::zeros(degree, degree);
+        for j in 0..degree {
+            companion[(0, j)] = -coefficients[j + 1] / coefficients[0];
+        }
+        for i in 1..degree {
+            companion[(i, i - 1)] = 1.0;
+        }
+        roots.extend(eigenvalues(&companion)?);
+    }
+    roots.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+    return Ok(roots);
+}
+
+// Eigenvalues of a square matrix, read off the diagonal blocks of its real Schur form.
+pub fn eigenvalues(a: &Matrix) -> Result<Vec<Complex64>, Error> {
+    let (_, t) = schur(a)?;
+    let n = t.rows();
+    let mut eigenvalues = Vec::with_capacity(n);
+    let mut i = 0;
+    while i < n {
+        if i + 1 < n && t[(i + 1, i)] != 0.0 {
+            eigenvalues.extend(eigenvalues_2x2(t[(i, i)], t[(i, i + 1)], t[(i + 1, i)], t[(i + 1, i + 1)]));
+            i += 2;
+        } else {
+            eigenvalues.push(Complex64::new(t[(i, i)], 0.0));
+            i += 1;
+        }
+    }
+    return Ok(eigenvalues);
+}
+
+// Eigenvalues of `[[a, b], [c, d]]`.
+fn eigenvalues_2x2(a: f64, b: f64, c: f64, d: f64) -> [Complex64; 2] {
+    let mean = 0.5 * (a + d);
+    let discriminant = 0.25 * (a - d) * (a - d) + b * c;
+    if discriminant >= 0.0 {
+        // Avoid cancellation by computing the smaller root from the product `ad - bc`.
+        let larger = mean + mean.signum() * discriminant.sqrt();
+        let smaller = if larger == 0.0 { 0.0 } else { (a * d - b * c) / larger };
+        return [Complex64::new(larger, 0.0), Complex64::new(smaller, 0.0)];
+    }
+    let imaginary = (-discriminant).sqrt();
+    return [Complex64::new(mean, imaginary), Complex64::new(mean, -imaginary)];
+}
+
+#[cfg(test)]
+mod roots_test {
+    use num_complex::Complex64;
+
+    use crate::roots::roots;
+    use crate::Error;
+
+    fn assert_roots(coefficients: &[f64], expected: &[(f64, f64)]) {
+        let found = roots(coefficients).unwrap();
+        assert_eq!(found.len(), expected.len());
+        for (root, (re, im)) in found.iter().zip(expected) {
+            assert!((root - Complex64::new(*re, *im)).norm() < 1e-10, "{} != {} + {}i", root, re, im);
+        }
+    }
+
+    #[test]
+    fn real_and_complex_roots() {
+        assert_roots(&[1.0, -6.0, 11.0, -6.0], &[(1.0, 0.0), (2.0, 0.0), (3.0, 0.0)]);
+        assert_roots(&[2.0, 0.0, 2.0], &[(0.0, -1.0), (0.0, 1.0)]);
+        assert_roots(&[1.0, 0.0, 0.0, 0.0, -1.0], &[(-1.0, 0.0), (0.0, -1.0), (0.0, 1.0), (1.0, 0.0)]);
+        // (x - 1)(x² + 2x + 5)(x + 4), with roots -1 ± 2i.
+        assert_roots(&[1.0, 5.0, 7.0, 7.0, -20.0], &[(-4.0, 0.0), (-1.0, -2.0), (-1.0, 2.0), (1.0, 0.0)]);
+    }
+
+    #[test]
+    fn leading_and_trailing_zeros() {
+        assert_roots(&[0.0, 1.0, -1.0, 0.0], &[(0.0, 0.0), (1.0, 0.0)]);
+        assert_roots(&[0.0, 3.0], &[]);
+        assert_roots(&[], &[]);
+    }
+
+    #[test]
+    fn non_finite_coefficients() {
+        assert!(matches!(roots(&[1.0, f64::NAN, 1.0, 1.0]), Err(Error::NotConverged { .. })));
+    }
+}
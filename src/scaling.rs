@@ -0,0 +1,30 @@
+use crate::Vector;
+
+// Scales every vector to unit infinity-norm, so that inputs of wildly different magnitudes are orthogonalized on equal
+// footing and absolute tolerances mean the same thing for all of them. Returns the original infinity-norms;
+// multiplying vector `i` by `scales[i]` undoes the scaling. Zero vectors are left as they are with scale 1.
+pub fn equilibrate_columns<V: Vector>(vectors: &mut [V]) -> Vec<f64> {
+    return vectors.iter_mut().map(|v| {
+        let scale = v.norm_linf();
+        if scale == 0.0 {
+            return 1.0;
+        }
+        *v = v.clone() / scale;
+        return scale;
+    }).collect();
+}
+
+#[cfg(test)]
+mod scaling_test {
+    use crate::{equilibrate_columns, Vector3};
+
+    #[test]
+    fn unit_infinity_norm() {
+        let mut vectors = [Vector3::new([1e12, -4e12, 2e12]), Vector3::new([0.0; 3]), Vector3::new([0.0, 1e-9, -2e-9])];
+        let scales = equilibrate_columns(&mut vectors);
+        assert_eq!(scales, vec![4e12, 1.0, 2e-9]);
+        assert_eq!(vectors[0], Vector3::new([0.25, -1.0, 0.5]));
+        assert_eq!(vectors[1], Vector3::new([0.0; 3]));
+        assert_eq!(vectors[2], Vector3::new([0.0, 0.5, -1.0]));
+    }
+}
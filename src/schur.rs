@@ -0,0 +1,175 @@
+use crate::{hessenberg, householder_qr, Error, Matrix};
+
+// Iterations without a deflation after which an exceptional shift is used to break cycles.
+const EXCEPTIONAL_SHIFT_PERIOD: usize = 10;
+const MAX_ITERATIONS_PER_EIGENVALUE: usize = 100;
+
+// Real Schur decomposition `A = Q T Qᵀ` of a square matrix: `Q` is orthogonal and `T` is quasi upper triangular, with
+// 1x1 diagonal blocks for the real eigenvalues and 2x2 blocks for the complex conjugate pairs. Starts from the
+// Hessenberg form and runs double-shifted QR steps on the unreduced trailing window, each factoring
+// `(H - σ₁)(H - σ₂)` with the crate's Householder QR. Meant for small dense matrices: every step costs O(n³). Fails
+// with `NotConverged` if a window does not split within `MAX_ITERATIONS_PER_EIGENVALUE` steps per row, which in
+// practice means the input is not finite.
+pub fn schur(a: &Matrix) -> Result<(Matrix, Matrix), Error> {
+    let (mut t, mut q) = hessenberg(a);
+    let n = t.rows();
+    let mut hi = n;
+    let mut iterations = 0;
+    while hi > 0 {
+        // Active window `lo..hi`: the trailing block whose subdiagonal has no negligible entries.
+        let mut lo = hi - 1;
+        while lo > 0 && !negligible(&t, lo) {
+            lo -= 1;
+        }
+        if lo > 0 {
+            t[(lo, lo - 1)] = 0.0;
+        }
+        let size = hi - lo;
+        if size <= 2 {
+            if size == 2 {
+                standardize_2x2(&mut t, &mut q, lo);
+            }
+            hi = lo;
+            iterations = 0;
+            continue;
+        }
+        if iterations >= MAX_ITERATIONS_PER_EIGENVALUE * size {
+            return Err(Error::NotConverged { iterations });
+        }
+        iterations += 1;
+        double_shift_step(&mut t, &mut q, lo, hi, iterations % EXCEPTIONAL_SHIFT_PERIOD == 0);
+    }
+    return Ok((q, t));
+}
+
+fn negligible(t: &Matrix, k: usize) -> bool {
+    return t[(k, k - 1)].abs() <= f64::EPSILON * (t[(k - 1, k - 1)].abs() + t[(k, k)].abs());
+}
+
+// One explicit double-shift QR step on the window `lo..hi`, applied to the whole of `T` and accumulated into `Q`.
+fn double_shift_step(t: &mut Matrix, q: &mut Matrix, lo: usize, hi: usize, exceptional: bool) {
+    let m = hi - lo;
+    let w = |i: usize, j: usize| t[(lo + i, lo + j)];
+    let (s, p) = if exceptional {
+        let x = w(m - 1, m - 2).abs() + w(m - 2, m - 3).abs();
+        (1.5 * x, x * x)
+    } else {
+        (w(m - 2, m - 2) + w(m - 1, m - 1), w(m - 2, m - 2) * w(m - 1, m - 1) - w(m - 2, m - 1) * w(m - 1, m - 2))
+    };
+    // `M = W² - s W + p I` for the window `W`; real even when the shifts are a complex pair.
+    let mut shifted = Matrix::zeros(m, m);
+    for j in 0..m {
+        for i in 0..m {
+            let square: f64 = (0..m).map(|k| w(i, k) * w(k, j)).sum();
+            shifted[(i, j)] = square - s * w(i, j) + if i == j { p } else { 0.0 };
+        }
+    }
+    let (z, _) = householder_qr(&shifted);
+    rotate(t, q, lo, &z);
+    for j in lo..hi {
+        for i in j + 2..hi {
+            t[(i, j)] = 0.0;
+        }
+    }
+}
+
+// `T ← Zᵀ T Z` and `Q ← Q Z` for an orthogonal `Z` acting on the indices `lo..lo + Z.rows()`.
+fn rotate(t: &mut Matrix, q: &mut Matrix, lo: usize, z: &Matrix) {
+    let (n, m) = (t.rows(), z.rows());
+    let mut buffer = vec![0.0; m];
+    for j in 0..n {
+        for (i, value) in buffer.iter_mut().enumerate() {
+            *value = (0..m).map(|k| z[(k, i)] * t[(lo + k, j)]).sum();
+        }
+        for (i, value) in buffer.iter().enumerate() {
+            t[(lo + i, j)] = *value;
+        }
+    }
+    for target in [t, q] {
+        for i in 0..n {
+            for (j, value) in buffer.iter_mut().enumerate() {
+                *value = (0..m).map(|k| target[(i, lo + k)] * z[(k, j)]).sum();
+            }
+            for (j, value) in buffer.iter().enumerate() {
+                target[(i, lo + j)] = *value;
+            }
+        }
+    }
+}
+
+// Splits a 2x2 diagonal block at `k` with real eigenvalues into two 1x1 blocks by a rotation whose first column is an
+// eigenvector; blocks of complex pairs are left as they are.
+fn standardize_2x2(t: &mut Matrix, q: &mut Matrix, k: usize) {
+    let (a, b, c, d) = (t[(k, k)], t[(k, k + 1)], t[(k + 1, k)], t[(k + 1, k + 1)]);
+    let discriminant = 0.25 * (a - d) * (a - d) + b * c;
+    if c == 0.0 || discriminant < 0.0 {
+        return;
+    }
+    let mean = 0.5 * (a + d);
+    let lambda = mean + if a >= d { 1.0 } else { -1.0 } * discriminant.sqrt();
+    // `(λ - d, c)` is an eigenvector, and not zero because `c ≠ 0`.
+    let (x, y) = (lambda - d, c);
+    let norm = x.hypot(y);
+    let rotation = Matrix::from_rows(&[vec![x / norm, -y / norm], vec![y / norm, x / norm]]);
+    rotate(t, q, k, &rotation);
+    t[(k + 1, k)] = 0.0;
+}
+
+#[cfg(test)]
+mod schur_test {
+    use crate::{schur, Error, Matrix};
+
+    fn check(a: &Matrix) -> Matrix {
+        let n = a.rows();
+        let (q, t) = schur(a).unwrap();
+        let qt = q.transpose();
+        let identity = &qt * &q;
+        let reconstructed = &(&q * &t) * &qt;
+        for j in 0..n {
+            for i in 0..n {
+                assert!((identity[(i, j)] - if i == j { 1.0 } else { 0.0 }).abs() < 1e-13);
+                assert!((reconstructed[(i, j)] - a[(i, j)]).abs() < 1e-12);
+                if i > j + 1 {
+                    assert_eq!(t[(i, j)], 0.0);
+                }
+            }
+        }
+        // 2x2 blocks do not overlap.
+        for i in 2..n {
+            assert!(t[(i, i - 1)] == 0.0 || t[(i - 1, i - 2)] == 0.0);
+        }
+        return t;
+    }
+
+    #[test]
+    fn real_eigenvalues_give_triangular_form() {
+        // Symmetric, so all eigenvalues are real and `T` is diagonal up to rounding.
+        let a = Matrix::from_rows(&[
+            vec![4.0, 1.0, -2.0, 2.0],
+            vec![1.0, 2.0, 0.0, 1.0],
+            vec![-2.0, 0.0, 3.0, -2.0],
+            vec![2.0, 1.0, -2.0, -1.0],
+        ]);
+        let t = check(&a);
+        for i in 1..4 {
+            assert_eq!(t[(i, i - 1)], 0.0);
+        }
+    }
+
+    #[test]
+    fn complex_pair_gives_2x2_block() {
+        // Eigenvalues 1 ± 2i and 3.
+        let a = Matrix::from_rows(&[vec![1.0, -2.0, 3.0], vec![2.0, 1.0, 0.5], vec![0.0, 0.0, 3.0]]);
+        let t = check(&a);
+        let blocks = (1..3).filter(|&i| t[(i, i - 1)] != 0.0).count();
+        assert_eq!(blocks, 1);
+        let trace: f64 = (0..3).map(|i| t[(i, i)]).sum();
+        assert!((trace - 5.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn non_finite_input_does_not_converge() {
+        let a = Matrix::from_rows(&[vec![1.0, f64::NAN, 0.0], vec![1.0, 2.0, 3.0], vec![0.0, 1.0, 4.0]]);
+        assert_eq!(schur(&a).map(|_| ()), Err(Error::NotConverged { iterations: 300 }));
+    }
+}
@@ -0,0 +1,141 @@
+use std::io::Read;
+use std::pin::Pin;
+
+use axum::body::{Body, Bytes, HttpBody};
+use axum::http::StatusCode;
+use axum::routing::post;
+use axum::Router;
+use gram_schmidt::json::{BasisDocument, FactorizationDocument};
+use gram_schmidt::{try_householder_qr, Error, Matrix, UpperTriangular};
+
+// Requests larger than this are rejected before they are read completely.
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+// Body chunks received but not yet consumed by the parser; bounds the text held in memory per request.
+const PENDING_CHUNKS: usize = 4;
+
+// Diagonal entries of `R` below this fraction of the largest one count as rank deficiency.
+const RANK_TOLERANCE: f64 = 1e-10;
+
+// Blocking `Read` over the chunks forwarded by `read_document`; ends when the sender is dropped.
+struct ChunkReader {
+    receiver: tokio::sync::mpsc::Receiver<Bytes>,
+    chunk: Bytes,
+}
+
+impl Read for ChunkReader {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+        let n = buffer.len().min(self.chunk.len());
+        buffer[..n].copy_from_slice(&self.chunk.split_to(n));
+        return Ok(n);
+    }
+}
+
+// Parses the request body as it arrives: chunks are handed to a blocking task running the JSON parser, so only the
+// parsed numbers and a few pending chunks are in memory, never the whole text. Bodies over `MAX_BODY_BYTES` are
+// rejected as soon as the limit is crossed.
+async fn read_document(mut body: Body) -> Result<BasisDocument, (StatusCode, String)> {
+    let (sender, receiver) = tokio::sync::mpsc::channel(PENDING_CHUNKS);
+    let parser = tokio::task::spawn_blocking(move || {
+        return BasisDocument::from_reader(ChunkReader { receiver, chunk: Bytes::new() });
+    });
+    let mut received = 0;
+    while let Some(frame) = std::future::poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await {
+        let frame = frame.map_err(|error| (StatusCode::BAD_REQUEST, error.to_string()))?;
+        let Ok(data) = frame.into_data() else { continue };
+        received += data.len();
+        if received > MAX_BODY_BYTES {
+            return Err((StatusCode::PAYLOAD_TOO_LARGE, format!("body exceeds {} bytes", MAX_BODY_BYTES)));
+        }
+        // The parser stops reading at the first syntax error; its result is reported below.
+        if sender.send(data).await.is_err() {
+            break;
+        }
+    }
+    drop(sender);
+    let document = parser.await.map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?;
+    return respond(document);
+}
+
+// Factors the matrix whose columns are the vectors of a basis document.
+fn factor(document: &BasisDocument) -> Result<(Matrix, Matrix), Error> {
+    if document.vectors.is_empty() {
+        return Err(Error::Empty);
+    }
+    return try_householder_qr(&Matrix::from_columns(&document.vectors));
+}
+
+// `POST /qr`: a basis document in, a factorization document out.
+fn qr(document: &BasisDocument) -> Result<String, Error> {
+    let (q, r) = factor(document)?;
+    return Ok(FactorizationDocument::from_qr(&q, &r).to_json());
+}
+
+// `POST /diagnostics`: a basis document in, its numerical rank and the condition number of its `R` factor out.
+fn diagnostics(document: &BasisDocument) -> Result<String, Error> {
+    let (_, r) = factor(document)?;
+    let diagonal: Vec<f64> = (0..r.rows()).map(|i| r[(i, i)].abs()).collect();
+    let largest = diagonal.iter().copied().fold(0.0, f64::max);
+    let rank = diagonal.iter().filter(|d| **d > RANK_TOLERANCE * largest).count();
+    let condition = UpperTriangular::new(r).condition();
+    let condition = if condition.is_finite() { format!("{:?}", condition) } else { "null".to_string() };
+    return Ok(format!("{{\"vectors\":{},\"rank\":{},\"condition\":{}}}", diagonal.len(), rank, condition));
+}
+
+fn respond<T>(result: Result<T, Error>) -> Result<T, (StatusCode, String)> {
+    return result.map_err(|error| (StatusCode::UNPROCESSABLE_ENTITY, error.to_string()));
+}
+
+pub fn router() -> Router {
+    return Router::new()
+        .route("/qr", post(|body: Body| async move { respond(qr(&read_document(body).await?)) }))
+        .route("/diagnostics", post(|body: Body| async move { respond(diagnostics(&read_document(body).await?)) }));
+}
+
+pub fn serve(address: &str) -> std::io::Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    return runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(address).await?;
+        eprintln!("listening on {}", listener.local_addr()?);
+        return axum::serve(listener, router()).await;
+    });
+}
+
+#[cfg(test)]
+mod server_test {
+    use super::{diagnostics, qr, read_document, MAX_BODY_BYTES};
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use gram_schmidt::json::{BasisDocument, FactorizationDocument};
+    use gram_schmidt::Error;
+
+    fn document(json: &str) -> BasisDocument {
+        return BasisDocument::from_json(json).unwrap();
+    }
+
+    #[test]
+    fn endpoints() {
+        let response = qr(&document(r#"{"dim": 2, "vectors": [[3, 4]]}"#)).unwrap();
+        let factorization = FactorizationDocument::from_json(&response).unwrap();
+        assert_eq!(factorization.r, vec![vec![-5.0]]);
+        assert_eq!(qr(&document(r#"{"dim": 1, "vectors": []}"#)), Err(Error::Empty));
+        let report = diagnostics(&document(r#"{"dim": 3, "vectors": [[1, 0, 0], [2, 0, 0]]}"#)).unwrap();
+        assert!(report.starts_with("{\"vectors\":2,\"rank\":1,"));
+    }
+
+    #[tokio::test]
+    async fn bodies_are_parsed_while_streaming() {
+        let parsed = read_document(Body::from(r#"{"dim": 2, "vectors": [[3, 4]]}"#)).await.unwrap();
+        assert_eq!(parsed, document(r#"{"dim": 2, "vectors": [[3, 4]]}"#));
+        let mismatch = read_document(Body::from(r#"{"dim": 1, "vectors": [[1], [2, 3]]}"#)).await.unwrap_err();
+        assert_eq!(mismatch.0, StatusCode::UNPROCESSABLE_ENTITY);
+        let oversized = read_document(Body::from(vec![b' '; MAX_BODY_BYTES + 1])).await.unwrap_err();
+        assert_eq!(oversized.0, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+}
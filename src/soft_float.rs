@@ -0,0 +1,384 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::Vector;
+
+const SIGN: u64 = 1 << 63;
+const EXPONENT_MASK: u64 = 0x7ff;
+const FRACTION_MASK: u64 = (1 << 52) - 1;
+const CANONICAL_NAN: u64 = 0x7ff8_0000_0000_0000;
+const INFINITY: u64 = 0x7ff0_0000_0000_0000;
+
+// IEEE 754 binary64 with every operation carried out in integer arithmetic and rounded to nearest, ties to even. The
+// results are the correctly rounded ones, so they match hardware `f64` in the default environment, but they also do
+// not depend on the FPU state, x87 extended precision or FMA contraction. The one intended difference: every NaN result
+// is the canonical quiet NaN, where hardware propagates payloads in platform-specific ways.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct SoftF64(f64);
+
+// A finite nonzero magnitude `m * 2^e`, with the implicit bit at position 52 for normal numbers.
+struct Unpacked {
+    sign: bool,
+    m: u64,
+    e: i32,
+}
+
+impl SoftF64 {
+    pub const ZERO: Self = Self(0.0);
+    pub const ONE: Self = Self(1.0);
+
+    pub const fn from_f64(value: f64) -> Self {
+        return Self(value);
+    }
+
+    pub const fn to_f64(self) -> f64 {
+        return self.0;
+    }
+
+    fn bits(self) -> u64 {
+        return self.0.to_bits();
+    }
+
+    fn from_bits(bits: u64) -> Self {
+        return Self(f64::from_bits(bits));
+    }
+
+    fn is_nan(self) -> bool {
+        return self.bits() & !SIGN > INFINITY;
+    }
+
+    fn is_infinite(self) -> bool {
+        return self.bits() & !SIGN == INFINITY;
+    }
+
+    fn is_zero(self) -> bool {
+        return self.bits() & !SIGN == 0;
+    }
+
+    fn sign(self) -> bool {
+        return self.bits() & SIGN != 0;
+    }
+
+    fn signed(sign: bool, magnitude: u64) -> Self {
+        return Self::from_bits(if sign { magnitude | SIGN } else { magnitude });
+    }
+
+    // Only for finite nonzero values.
+    fn unpack(self) -> Unpacked {
+        let bits = self.bits();
+        let exponent = ((bits >> 52) & EXPONENT_MASK) as i32;
+        let fraction = bits & FRACTION_MASK;
+        return if exponent == 0 {
+            Unpacked { sign: self.sign(), m: fraction, e: -1074 }
+        } else {
+            Unpacked { sign: self.sign(), m: fraction | (1 << 52), e: exponent - 1075 }
+        };
+    }
+
+    // Like `unpack`, with subnormals shifted so the leading bit is at position 52 as well.
+    fn unpack_normalized(self) -> Unpacked {
+        let mut u = self.unpack();
+        let shift = u.m.leading_zeros() as i32 - 11;
+        u.m <<= shift;
+        u.e -= shift;
+        return u;
+    }
+
+    pub fn sqrt(self) -> Self {
+        if self.is_nan() || (self.sign() && !self.is_zero()) {
+            return Self::from_bits(CANONICAL_NAN);
+        }
+        if self.is_zero() || self.is_infinite() {
+            return self;
+        }
+        let Unpacked { mut m, mut e, .. } = self.unpack_normalized();
+        if e % 2 != 0 {
+            m <<= 1;
+            e -= 1;
+        }
+        let (root, exact) = isqrt((m as u128) << 64);
+        return round_pack(false, e / 2 - 32, root | !exact as u128);
+    }
+}
+
+// Integer square root and whether it is exact, one result bit per iteration.
+fn isqrt(n: u128) -> (u128, bool) {
+    let mut remainder = n;
+    let mut root: u128 = 0;
+    let mut bit: u128 = 1 << 126;
+    while bit > n {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if remainder >= root + bit {
+            remainder -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    return (root, remainder == 0);
+}
+
+// `a >> distance`, with the lowest bit set if any of the shifted out bits were set.
+fn shift_right_jam(a: u128, distance: u32) -> u128 {
+    if distance >= 128 {
+        return (a != 0) as u128;
+    }
+    return (a >> distance) | ((a & ((1 << distance) - 1)) != 0) as u128;
+}
+
+// Rounds `sig * 2^e` (with the lowest bit of `sig` possibly standing for discarded nonzero bits) to the nearest double.
+fn round_pack(sign: bool, e: i32, sig: u128) -> SoftF64 {
+    if sig == 0 {
+        return SoftF64::signed(sign, 0);
+    }
+    // Bring the leading bit to position 62, leaving 10 rounding bits below the 52 fraction bits.
+    let leading = 127 - sig.leading_zeros() as i32;
+    let shift = leading - 62;
+    let mut sig = if shift > 0 { shift_right_jam(sig, shift as u32) } else { sig << -shift } as u64;
+    // Biased exponent minus one: packing adds the implicit bit into the exponent field.
+    let mut exponent = e + shift + 1084;
+    if exponent < 0 {
+        sig = shift_right_jam(sig as u128, (-exponent) as u32) as u64;
+        exponent = 0;
+    } else if exponent > 0x7fd || (exponent == 0x7fd && sig + 0x200 >= 1 << 63) {
+        return SoftF64::signed(sign, INFINITY);
+    }
+    let round_bits = sig & 0x3ff;
+    sig = (sig + 0x200) >> 10;
+    if round_bits == 0x200 {
+        sig &= !1;
+    }
+    if sig == 0 {
+        exponent = 0;
+    }
+    return SoftF64::signed(sign, ((exponent as u64) << 52) + sig);
+}
+
+impl Add for SoftF64 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        if self.is_nan() || rhs.is_nan() {
+            return Self::from_bits(CANONICAL_NAN);
+        }
+        if self.is_infinite() || rhs.is_infinite() {
+            if self.is_infinite() && rhs.is_infinite() && self.sign() != rhs.sign() {
+                return Self::from_bits(CANONICAL_NAN);
+            }
+            return if self.is_infinite() { self } else { rhs };
+        }
+        if self.is_zero() && rhs.is_zero() {
+            return Self::signed(self.sign() && rhs.sign(), 0);
+        }
+        if self.is_zero() {
+            return rhs;
+        }
+        if rhs.is_zero() {
+            return self;
+        }
+        let (mut a, mut b) = (self.unpack(), rhs.unpack());
+        if a.e < b.e {
+            std::mem::swap(&mut a, &mut b);
+        }
+        // Align exactly when the exponents are close. Otherwise `a` is normal and `b` lies entirely below its rounding
+        // bits, so it only contributes a sticky bit.
+        let distance = (a.e - b.e) as u32;
+        let (x, y, e) = if distance <= 64 {
+            ((a.m as u128) << distance, b.m as u128, b.e)
+        } else {
+            ((a.m as u128) << 64, shift_right_jam(b.m as u128, distance - 64), a.e - 64)
+        };
+        if a.sign == b.sign {
+            return round_pack(a.sign, e, x + y);
+        }
+        return match x.cmp(&y) {
+            std::cmp::Ordering::Greater => round_pack(a.sign, e, x - y),
+            std::cmp::Ordering::Less => round_pack(b.sign, e, y - x),
+            std::cmp::Ordering::Equal => Self::ZERO,
+        };
+    }
+}
+
+impl Sub for SoftF64 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        return self + -rhs;
+    }
+}
+
+impl Neg for SoftF64 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        return Self::from_bits(self.bits() ^ SIGN);
+    }
+}
+
+impl Mul for SoftF64 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let sign = self.sign() != rhs.sign();
+        if self.is_nan() || rhs.is_nan() {
+            return Self::from_bits(CANONICAL_NAN);
+        }
+        if self.is_infinite() || rhs.is_infinite() {
+            if self.is_zero() || rhs.is_zero() {
+                return Self::from_bits(CANONICAL_NAN);
+            }
+            return Self::signed(sign, INFINITY);
+        }
+        if self.is_zero() || rhs.is_zero() {
+            return Self::signed(sign, 0);
+        }
+        let (a, b) = (self.unpack(), rhs.unpack());
+        return round_pack(sign, a.e + b.e, a.m as u128 * b.m as u128);
+    }
+}
+
+impl Div for SoftF64 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        let sign = self.sign() != rhs.sign();
+        if self.is_nan() || rhs.is_nan() {
+            return Self::from_bits(CANONICAL_NAN);
+        }
+        if self.is_infinite() {
+            return if rhs.is_infinite() { Self::from_bits(CANONICAL_NAN) } else { Self::signed(sign, INFINITY) };
+        }
+        if rhs.is_infinite() {
+            return Self::signed(sign, 0);
+        }
+        if rhs.is_zero() {
+            return if self.is_zero() { Self::from_bits(CANONICAL_NAN) } else { Self::signed(sign, INFINITY) };
+        }
+        if self.is_zero() {
+            return Self::signed(sign, 0);
+        }
+        let (a, b) = (self.unpack_normalized(), rhs.unpack_normalized());
+        // At least 64 quotient bits, plus a sticky bit for the remainder.
+        let numerator = (a.m as u128) << 64;
+        let quotient = numerator / b.m as u128;
+        let exact = numerator.is_multiple_of(b.m as u128);
+        return round_pack(sign, a.e - b.e - 64, quotient | !exact as u128);
+    }
+}
+
+// `Vector::gram_schmidt` with every operation carried out in `SoftF64`, so the resulting basis is bit-identical on
+// every platform, independent of the FPU state and of the `no-fma` feature. Much slower than the hardware version.
+// Enabling this module also switches the shared kernels (`dot_product`, `axpy`, `normalize`, `storage_*`) to
+// `SoftF64`, so `Vector::gram_schmidt` itself returns the same bits as this function.
+pub fn gram_schmidt_soft_float<V: Vector>(basis: &mut [V]) {
+    let mut soft: Vec<Vec<SoftF64>> = basis.iter()
+        .map(|v| (0..V::DIM).map(|i| SoftF64::from_f64(v[i])).collect())
+        .collect();
+    for index in 0..soft.len() {
+        let (a, previous) = soft[..=index].split_last_mut().unwrap();
+        for b in previous.iter() {
+            let dot = soft_dot(a, b);
+            for (ai, bi) in a.iter_mut().zip(b) {
+                *ai = *ai - dot * *bi;
+            }
+        }
+        let length = soft_dot(a, a).sqrt();
+        a.iter_mut().for_each(|c| *c = *c / length);
+    }
+    for (v, s) in basis.iter_mut().zip(&soft) {
+        for i in 0..V::DIM {
+            v[i] = s[i].to_f64();
+        }
+    }
+}
+
+fn soft_dot(a: &[SoftF64], b: &[SoftF64]) -> SoftF64 {
+    return a.iter().zip(b).fold(SoftF64::ZERO, |sum, (x, y)| sum + *x * *y);
+}
+
+#[cfg(test)]
+mod soft_float_test {
+    use crate::soft_float::{gram_schmidt_soft_float, SoftF64};
+    use crate::{gram_schmidt_storage, FpEnvironment, Vector, Vector16, Vector4};
+
+    // Deterministic mix of ordinary, tiny, huge and special values.
+    fn samples() -> Vec<f64> {
+        let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut values = vec![
+            0.0, -0.0, 1.0, -1.0, 0.5, 3.0, 1e308, -1e308, f64::MAX, f64::MIN_POSITIVE, 5e-324, -5e-324, 2.2e-308,
+            f64::INFINITY, f64::NEG_INFINITY, f64::EPSILON, 1.0 + f64::EPSILON, 0.1, 1.0 / 3.0,
+        ];
+        for _ in 0..400 {
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = state;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            let value = f64::from_bits(z ^ (z >> 31));
+            if !value.is_nan() {
+                values.push(value);
+                values.push(f64::from_bits(0x3ff0_0000_0000_0000 | (z & 0xf_ffff_ffff_ffff)));
+            }
+        }
+        return values;
+    }
+
+    fn same(soft: SoftF64, hard: f64) -> bool {
+        return if hard.is_nan() { soft.to_f64().is_nan() } else { soft.to_f64().to_bits() == hard.to_bits() };
+    }
+
+    #[test]
+    fn matches_hardware_rounding() {
+        let values = samples();
+        for &x in &values {
+            let sx = SoftF64::from_f64(x);
+            assert!(same(sx.sqrt(), x.sqrt()), "sqrt({:e})", x);
+            for &y in &values {
+                let sy = SoftF64::from_f64(y);
+                assert!(same(sx + sy, x + y), "{:e} + {:e}", x, y);
+                assert!(same(sx - sy, x - y), "{:e} - {:e}", x, y);
+                assert!(same(sx * sy, x * y), "{:e} * {:e}", x, y);
+                assert!(same(sx / sy, x / y), "{:e} / {:e}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn orthonormalizes() {
+        let input = vec![
+            Vector4::new([1.0, 1.0, 1.0, 1.0]),
+            Vector4::new([0.0, 1.0, 0.0, 1.0]),
+            Vector4::new([0.0, 0.0, 1.0, 1.0]),
+            Vector4::new([0.0, 0.0, 0.0, 1.0]),
+        ];
+        let mut soft = input.clone();
+        gram_schmidt_soft_float(&mut soft);
+        assert_eq!(soft[3], Vector4::new([0.5, -0.5, -0.5, 0.5]));
+        let mut hard = input;
+        Vector4::gram_schmidt(&mut hard);
+        for (s, h) in soft.iter().zip(&hard) {
+            assert!((*s - *h).norm_linf() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn shared_kernels_are_soft() {
+        assert!(!FpEnvironment::detect().fused_multiply_add);
+        let input: Vec<Vector16> = (0..16)
+            .map(|j| Vector16::from_fn(|i| ((i * 16 + j) as f64).sin() * 10f64.powi(j as i32 % 5 - 2)))
+            .collect();
+        let mut soft = input.clone();
+        gram_schmidt_soft_float(&mut soft);
+        let mut shared = input.clone();
+        Vector16::gram_schmidt(&mut shared);
+        let mut storage: Vec<[f64; 16]> = input.iter().map(|v| v.components).collect();
+        gram_schmidt_storage(&mut storage);
+        for ((s, v), w) in soft.iter().zip(&shared).zip(&storage) {
+            for i in 0..16 {
+                assert_eq!(s[i].to_bits(), v[i].to_bits());
+                assert_eq!(s[i].to_bits(), w[i].to_bits());
+            }
+        }
+    }
+}
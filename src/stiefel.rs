@@ -0,0 +1,243 @@
+use crate::qr::positive_qr;
+use crate::{schur, Error, Matrix};
+
+// Utilities for the Stiefel manifold of `n x p` matrices with orthonormal columns.
+
+// Projects `v` onto the tangent space at `point`: `v - X sym(Xᵀ v)` with `sym(A) = (A + Aᵀ) / 2`.
+pub fn project_to_tangent(point: &Matrix, v: &Matrix) -> Matrix {
+    let inner = &point.transpose() * v;
+    let mut sym = Matrix::zeros(inner.rows(), inner.cols());
+    for j in 0..inner.cols() {
+        for i in 0..inner.rows() {
+            sym[(i, j)] = 0.5 * (inner[(i, j)] + inner[(j, i)]);
+        }
+    }
+    return v - &(point * &sym);
+}
+
+// QR retraction: the `Q` factor of `point + tangent` with the sign convention of Gram-Schmidt (non-negative `R`
+// diagonal), which maps the tangent step back onto the manifold.
+pub fn retract_qr(point: &Matrix, tangent: &Matrix) -> Matrix {
+    return positive_qr(&(point + tangent)).0;
+}
+
+// Solves `A X = B` by Gaussian elimination with partial pivoting. `A` must be square and non-singular.
+fn solve(a: &Matrix, b: &Matrix) -> Matrix {
+    let n = a.rows();
+    let mut a = a.clone();
+    let mut x = b.clone();
+    for k in 0..n {
+        let pivot = (k..n).max_by(|&i, &j| a[(i, k)].abs().total_cmp(&a[(j, k)].abs())).unwrap();
+        for (m, cols) in [(&mut a, n), (&mut x, b.cols())] {
+            for j in 0..cols {
+                let tmp = m[(k, j)];
+                m[(k, j)] = m[(pivot, j)];
+                m[(pivot, j)] = tmp;
+            }
+        }
+        for i in k + 1..n {
+            let factor = a[(i, k)] / a[(k, k)];
+            for j in k..n {
+                a[(i, j)] -= factor * a[(k, j)];
+            }
+            for j in 0..b.cols() {
+                x[(i, j)] -= factor * x[(k, j)];
+            }
+        }
+    }
+    for j in 0..b.cols() {
+        for i in (0..n).rev() {
+            let mut sum = x[(i, j)];
+            for k in i + 1..n {
+                sum -= a[(i, k)] * x[(k, j)];
+            }
+            x[(i, j)] = sum / a[(i, i)];
+        }
+    }
+    return x;
+}
+
+// Cayley transform `(I - S)⁻¹ (I + S)` of a skew-symmetric `S`, an orthogonal matrix without eigenvalue -1.
+pub fn cayley(skew: &Matrix) -> Matrix {
+    let identity = Matrix::identity(skew.rows());
+    return solve(&(&identity - skew), &(&identity + skew));
+}
+
+// Squarings needed to bring the norm of any finite matrix below 1/2.
+const MAX_SQUARINGS: i32 = 1026;
+
+// Matrix exponential of a skew-symmetric `S` (a rotation) by scaling and squaring: the Taylor series is summed for
+// `S / 2^s` with norm below 1/2 and the result is squared `s` times, taking the `Q` factor after every squaring so
+// that rounding errors are not doubled along with it. Meant for small matrices. Fails with `NonFinite` (vector =
+// column, component = row) for a NaN or infinite entry.
+pub fn expm_skew(skew: &Matrix) -> Result<Matrix, Error> {
+    let n = skew.rows();
+    for j in 0..skew.cols() {
+        if let Some(i) = skew.column(j).iter().position(|x| !x.is_finite()) {
+            return Err(Error::NonFinite { vector: j, component: i });
+        }
+    }
+    let norm = (0..n).map(|j| skew.column(j).iter().map(|x| x.abs()).sum::<f64>()).fold(0.0, f64::max);
+    let mut squarings = 0;
+    while squarings < MAX_SQUARINGS && norm * 0.5_f64.powi(squarings) > 0.5 {
+        squarings += 1;
+    }
+    let scale = 0.5_f64.powi(squarings);
+
+    let mut result = Matrix::identity(n);
+    let mut term = Matrix::identity(n);
+    for k in 1..=18 {
+        term = &term * skew;
+        for j in 0..n {
+            term.column_mut(j).iter_mut().for_each(|x| *x *= scale / k as f64);
+        }
+        result = &result + &term;
+    }
+    for _ in 0..squarings {
+        result = positive_qr(&(&result * &result)).0;
+    }
+    return Ok(result);
+}
+
+// Point at `t` on a path from `q0` (t = 0) to `q1` (t = 1) between two bases with orthonormal columns, for animating
+// frames and for continuation methods. Square bases follow the geodesic `Q0 exp(t log(Q0ᵀ Q1))` on the orthogonal
+// group, with the logarithm read off the real Schur form of the relative rotation. Tall bases use the QR retraction
+// of the chord `(1 - t) Q0 + t Q1`, which passes through both ends but is not a geodesic. Returns `None` when no path
+// exists this way: square bases of opposite orientation, a chord that loses rank, or inputs so far from orthogonal
+// (or not finite) that the Schur iteration does not converge.
+pub fn interpolate_bases(q0: &Matrix, q1: &Matrix, t: f64) -> Option<Matrix> {
+    assert!(q0.rows() == q1.rows() && q0.cols() == q1.cols(), "interpolate_bases needs bases of the same shape");
+    if q0.rows() != q0.cols() {
+        let mut chord = Matrix::zeros(q0.rows(), q0.cols());
+        for j in 0..q0.cols() {
+            for i in 0..q0.rows() {
+                chord[(i, j)] = (1.0 - t) * q0[(i, j)] + t * q1[(i, j)];
+            }
+        }
+        let (q, r) = positive_qr(&chord);
+        if (0..r.cols()).any(|k| r[(k, k)] <= f64::EPSILON.sqrt()) {
+            return None;
+        }
+        return Some(q);
+    }
+    let mut log = log_rotation(&(&q0.transpose() * q1))?;
+    for j in 0..log.cols() {
+        log.column_mut(j).iter_mut().for_each(|x| *x *= t);
+    }
+    return Some(q0 * &expm_skew(&log).ok()?);
+}
+
+// Skew-symmetric logarithm of a rotation. The Schur form of an orthogonal matrix is block diagonal with 2x2 rotation
+// blocks and ±1 entries; pairs of -1 entries are taken as half turns. `None` if the determinant is -1 or the Schur
+// form cannot be computed.
+fn log_rotation(rotation: &Matrix) -> Option<Matrix> {
+    let (z, t) = schur(rotation).ok()?;
+    let n = t.rows();
+    let mut planes = Vec::new();
+    let mut half_turns = Vec::new();
+    let mut i = 0;
+    while i < n {
+        if i + 1 < n && t[(i + 1, i)] != 0.0 {
+            let angle = (0.5 * (t[(i + 1, i)] - t[(i, i + 1)])).atan2(0.5 * (t[(i, i)] + t[(i + 1, i + 1)]));
+            planes.push((i, i + 1, angle));
+            i += 2;
+        } else {
+            if t[(i, i)] < 0.0 {
+                half_turns.push(i);
+            }
+            i += 1;
+        }
+    }
+    if half_turns.len() % 2 == 1 {
+        return None;
+    }
+    planes.extend(half_turns.chunks(2).map(|pair| (pair[0], pair[1], std::f64::consts::PI)));
+
+    let mut log = Matrix::zeros(n, n);
+    for (a, b, angle) in planes {
+        log[(b, a)] = angle;
+        log[(a, b)] = -angle;
+    }
+    let log = &(&z * &log) * &z.transpose();
+    // Restore exact skew symmetry lost to rounding.
+    let mut skew = Matrix::zeros(n, n);
+    for j in 0..n {
+        for i in 0..n {
+            skew[(i, j)] = 0.5 * (log[(i, j)] - log[(j, i)]);
+        }
+    }
+    return Some(skew);
+}
+
+#[cfg(test)]
+mod stiefel_test {
+    use crate::{cayley, expm_skew, interpolate_bases, project_to_tangent, retract_qr, Error, Matrix};
+
+    fn max_abs(m: &Matrix) -> f64 {
+        return (0..m.cols()).flat_map(|j| m.column(j).to_vec()).fold(0.0, |a, b| a.max(b.abs()));
+    }
+
+    #[test]
+    fn tangent_projection_and_retraction() {
+        let point = Matrix::from_rows(&[vec![1.0, 0.0], vec![0.0, 0.6], vec![0.0, 0.8]]);
+        let v = Matrix::from_rows(&[vec![0.3, 1.0], vec![-2.0, 0.5], vec![1.0, 0.2]]);
+        let tangent = project_to_tangent(&point, &v);
+        // Tangent vectors satisfy `Xᵀ ξ + ξᵀ X = 0`.
+        let inner = &point.transpose() * &tangent;
+        assert!(max_abs(&(&inner + &inner.transpose())) < 1e-12);
+        assert!(max_abs(&(&project_to_tangent(&point, &tangent) - &tangent)) < 1e-12);
+
+        let next = retract_qr(&point, &tangent);
+        assert!(max_abs(&(&(&next.transpose() * &next) - &Matrix::identity(2))) < 1e-12);
+        assert_eq!(retract_qr(&point, &Matrix::zeros(3, 2)).column(0), point.column(0));
+    }
+
+    #[test]
+    fn skew_generators() {
+        let t: f64 = 2.5;
+        let rotation = expm_skew(&Matrix::from_rows(&[vec![0.0, -t], vec![t, 0.0]])).unwrap();
+        let expected = Matrix::from_rows(&[vec![t.cos(), -t.sin()], vec![t.sin(), t.cos()]]);
+        assert!(max_abs(&(&rotation - &expected)) < 1e-12);
+
+        let skew = Matrix::from_rows(&[vec![0.0, 1.0, -2.0], vec![-1.0, 0.0, 0.5], vec![2.0, -0.5, 0.0]]);
+        for q in [cayley(&skew), expm_skew(&skew).unwrap()] {
+            assert!(max_abs(&(&(&q.transpose() * &q) - &Matrix::identity(3))) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn exponential_of_large_and_non_finite_generators() {
+        for t in [1e10, 1e300, f64::MAX] {
+            let q = expm_skew(&Matrix::from_rows(&[vec![0.0, -t], vec![t, 0.0]])).unwrap();
+            assert!(max_abs(&(&(&q.transpose() * &q) - &Matrix::identity(2))) < 1e-12);
+        }
+
+        let nan = Matrix::from_rows(&[vec![0.0, f64::NAN], vec![1.0, 0.0]]);
+        assert_eq!(expm_skew(&nan), Err(Error::NonFinite { vector: 1, component: 0 }));
+    }
+
+    #[test]
+    fn interpolation_between_bases() {
+        let rotation = |angle: f64| {
+            let (s, c) = angle.sin_cos();
+            return Matrix::from_rows(&[vec![c, -s, 0.0], vec![s, c, 0.0], vec![0.0, 0.0, 1.0]]);
+        };
+        // The geodesic between rotations about a common axis turns at constant speed.
+        let (q0, q1) = (rotation(0.2), rotation(1.4));
+        for t in [0.0, 0.25, 0.5, 1.0] {
+            let q = interpolate_bases(&q0, &q1, t).unwrap();
+            assert!(max_abs(&(&q - &rotation(0.2 + 1.2 * t))) < 1e-12);
+        }
+        let reflection = Matrix::from_rows(&[vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![0.0, 0.0, -1.0]]);
+        assert!(interpolate_bases(&q0, &reflection, 0.5).is_none());
+        // A half turn still has a path.
+        let half = interpolate_bases(&Matrix::identity(3), &rotation(std::f64::consts::PI), 1.0).unwrap();
+        assert!(max_abs(&(&half - &rotation(std::f64::consts::PI))) < 1e-12);
+
+        let tall0 = Matrix::from_rows(&[vec![1.0, 0.0], vec![0.0, 1.0], vec![0.0, 0.0]]);
+        let tall1 = Matrix::from_rows(&[vec![1.0, 0.0], vec![0.0, 0.0], vec![0.0, 1.0]]);
+        assert!(max_abs(&(&interpolate_bases(&tall0, &tall1, 1.0).unwrap() - &tall1)) < 1e-12);
+        let middle = interpolate_bases(&tall0, &tall1, 0.5).unwrap();
+        assert!(max_abs(&(&(&middle.transpose() * &middle) - &Matrix::identity(2))) < 1e-12);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{divide, fuse_multiply_add, multiply_add, square_root, Error};
+
+// Contiguous components of a vector, independent of who owns the memory. Implemented for fixed-size arrays, `Vec`,
+// boxed slices, plain and borrowed slices (e.g. carved out of an arena or a larger buffer) and, behind the `smallvec`
+// feature, `SmallVec`, so the kernels below are written once for all of them.
+pub trait Storage {
+    fn as_slice(&self) -> &[f64];
+
+    fn as_mut_slice(&mut self) -> &mut [f64];
+}
+
+impl<const N: usize> Storage for [f64; N] {
+    fn as_slice(&self) -> &[f64] {
+        return self;
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        return self;
+    }
+}
+
+impl Storage for Vec<f64> {
+    fn as_slice(&self) -> &[f64] {
+        return self;
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        return self;
+    }
+}
+
+impl Storage for Box<[f64]> {
+    fn as_slice(&self) -> &[f64] {
+        return self;
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        return self;
+    }
+}
+
+impl Storage for [f64] {
+    fn as_slice(&self) -> &[f64] {
+        return self;
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        return self;
+    }
+}
+
+impl Storage for &mut [f64] {
+    fn as_slice(&self) -> &[f64] {
+        return self;
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        return self;
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<const N: usize> Storage for smallvec::SmallVec<[f64; N]> {
+    fn as_slice(&self) -> &[f64] {
+        return self;
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [f64] {
+        return self;
+    }
+}
+
+// The kernels below panic if the operands differ in length. With the `unchecked` feature they skip the per-element
+// bounds checks after that one check.
+#[cfg(not(feature = "unchecked"))]
+pub fn storage_dot<A: Storage + ?Sized, B: Storage + ?Sized>(a: &A, b: &B) -> f64 {
+    assert_eq!(a.as_slice().len(), b.as_slice().len(), "operands must have the same length");
+    let fused = fuse_multiply_add();
+    return a.as_slice().iter().zip(b.as_slice()).fold(0.0, |sum, (x, y)| multiply_add(fused, *x, *y, sum));
+}
+
+#[cfg(feature = "unchecked")]
+pub fn storage_dot<A: Storage + ?Sized, B: Storage + ?Sized>(a: &A, b: &B) -> f64 {
+    let (a, b) = (a.as_slice(), b.as_slice());
+    assert_eq!(a.len(), b.len(), "operands must have the same length");
+    let fused = fuse_multiply_add();
+    let mut sum = 0.0;
+    for i in 0..a.len() {
+        // SAFETY: `i < a.len()`, and `a.len() == b.len()` was asserted above.
+        sum = unsafe { multiply_add(fused, *a.get_unchecked(i), *b.get_unchecked(i), sum) };
+    }
+    return sum;
+}
+
+// `y += alpha * x`
+#[cfg(not(feature = "unchecked"))]
+pub fn storage_axpy<X: Storage + ?Sized, Y: Storage + ?Sized>(alpha: f64, x: &X, y: &mut Y) {
+    assert_eq!(x.as_slice().len(), y.as_slice().len(), "operands must have the same length");
+    let fused = fuse_multiply_add();
+    for (yi, xi) in y.as_mut_slice().iter_mut().zip(x.as_slice()) {
+        *yi = multiply_add(fused, alpha, *xi, *yi);
+    }
+}
+
+#[cfg(feature = "unchecked")]
+pub fn storage_axpy<X: Storage + ?Sized, Y: Storage + ?Sized>(alpha: f64, x: &X, y: &mut Y) {
+    let (x, y) = (x.as_slice(), y.as_mut_slice());
+    assert_eq!(x.len(), y.len(), "operands must have the same length");
+    let fused = fuse_multiply_add();
+    for i in 0..y.len() {
+        // SAFETY: `i < y.len()`, and `x.len() == y.len()` was asserted above.
+        unsafe { *y.get_unchecked_mut(i) = multiply_add(fused, alpha, *x.get_unchecked(i), *y.get_unchecked(i)) };
+    }
+}
+
+fn check_lengths(expected: usize, found: usize) -> Result<(), Error> {
+    if expected != found {
+        return Err(Error::DimensionMismatch { expected, found });
+    }
+    return Ok(());
+}
+
+// Checked versions of the kernels above for storages whose lengths are only known at runtime, reporting a length
+// mismatch instead of panicking.
+pub fn try_storage_dot<A: Storage + ?Sized, B: Storage + ?Sized>(a: &A, b: &B) -> Result<f64, Error> {
+    check_lengths(a.as_slice().len(), b.as_slice().len())?;
+    return Ok(storage_dot(a, b));
+}
+
+pub fn try_storage_axpy<X: Storage + ?Sized, Y: Storage + ?Sized>(alpha: f64, x: &X, y: &mut Y) -> Result<(), Error> {
+    check_lengths(y.as_slice().len(), x.as_slice().len())?;
+    storage_axpy(alpha, x, y);
+    return Ok(());
+}
+
+pub fn storage_normalize<S: Storage + ?Sized>(v: &mut S) {
+    let len = square_root(storage_dot(v, v));
+    v.as_mut_slice().iter_mut().for_each(|c| *c = divide(*c, len));
+}
+
+// The same algorithm as `Vector::gram_schmidt` on any storage.
+pub fn gram_schmidt_storage<S: Storage>(basis: &mut [S]) {
+    #[cfg(feature = "metrics")]
+    let _timer = crate::telemetry::orthogonalization("gram_schmidt_storage");
+    for index in 0..basis.len() {
+        let (first_half, second_half) = basis.split_at_mut(index);
+        let a = &mut second_half[0];
+        for b in first_half.iter() {
+            let dot = storage_dot(a, b);
+            storage_axpy(-dot, b, a);
+        }
+        storage_normalize(a);
+    }
+}
+
+// Like `gram_schmidt_storage`, but reports vectors whose length differs from the first one instead of mixing them.
+pub fn try_gram_schmidt_storage<S: Storage>(basis: &mut [S]) -> Result<(), Error> {
+    if let Some(first) = basis.first() {
+        let dim = first.as_slice().len();
+        for v in basis.iter() {
+            check_lengths(dim, v.as_slice().len())?;
+        }
+    }
+    gram_schmidt_storage(basis);
+    return Ok(());
+}
+
+#[cfg(test)]
+mod storage_test {
+    use crate::{gram_schmidt_storage, try_gram_schmidt_storage, try_storage_axpy, try_storage_dot, Error, Vector4};
+
+    const EXPECTED: [[f64; 4]; 4] = [
+        [0.5, 0.5, 0.5, 0.5],
+        [-0.5, 0.5, -0.5, 0.5],
+        [-0.5, -0.5, 0.5, 0.5],
+        [0.5, -0.5, -0.5, 0.5],
+    ];
+
+    const INPUT: [[f64; 4]; 4] = [
+        [1.0, 1.0, 1.0, 1.0],
+        [0.0, 1.0, 0.0, 1.0],
+        [0.0, 0.0, 1.0, 1.0],
+        [0.0, 0.0, 0.0, 1.0],
+    ];
+
+    #[test]
+    fn same_result_for_every_storage() {
+        let mut arrays = INPUT;
+        gram_schmidt_storage(&mut arrays);
+        assert_eq!(arrays, EXPECTED);
+
+        let mut vecs: Vec<Vec<f64>> = INPUT.iter().map(|v| v.to_vec()).collect();
+        gram_schmidt_storage(&mut vecs);
+        assert_eq!(vecs, EXPECTED.iter().map(|v| v.to_vec()).collect::<Vec<_>>());
+
+        let mut arena = INPUT.concat();
+        let mut slices: Vec<&mut [f64]> = arena.chunks_mut(4).collect();
+        gram_schmidt_storage(&mut slices);
+        assert_eq!(arena, EXPECTED.concat());
+
+        let mut vectors: Vec<Vector4> = INPUT.iter().map(|v| Vector4::new(*v)).collect();
+        gram_schmidt_storage(&mut vectors);
+        assert_eq!(vectors, EXPECTED.iter().map(|v| Vector4::new(*v)).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn small_vec_storage() {
+        let mut small: Vec<smallvec::SmallVec<[f64; 4]>> = INPUT.iter().map(|v| smallvec::SmallVec::from_buf(*v)).collect();
+        gram_schmidt_storage(&mut small);
+        assert_eq!(small.iter().map(|v| v.to_vec()).collect::<Vec<_>>(), EXPECTED.iter().map(|v| v.to_vec()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn length_mismatch_is_reported() {
+        let mismatch = Err(Error::DimensionMismatch { expected: 3, found: 2 });
+        assert_eq!(try_storage_dot(&vec![1.0, 2.0, 3.0], &vec![1.0, 2.0]), mismatch);
+        assert_eq!(try_storage_dot(&[1.0, 2.0], &[3.0, 4.0]), Ok(11.0));
+        let mut y = vec![0.0; 3];
+        assert_eq!(try_storage_axpy(2.0, &vec![1.0, 2.0], &mut y), mismatch.map(|_: f64| ()));
+        let mut ragged = vec![vec![1.0, 0.0, 0.0], vec![1.0, 1.0]];
+        assert_eq!(try_gram_schmidt_storage(&mut ragged), mismatch.map(|_: f64| ()));
+        assert_eq!(ragged[0], vec![1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "same length")]
+    fn kernels_reject_mismatched_lengths() {
+        crate::storage_dot(&[1.0, 2.0], &[1.0]);
+    }
+}
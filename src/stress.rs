@@ -0,0 +1,168 @@
+use std::time::Instant;
+
+use gram_schmidt::{gram_schmidt_storage, householder_qr, Matrix};
+
+// `gram-schmidt stress --dim <n> --vectors <k> --cond <c> --seed <s> --method <mgs|cgs|cgs2|householder>`: builds a
+// reproducible `n x k` test matrix with 2-norm condition number `c` and prints how long the method took and how far
+// the result is from orthonormal.
+pub fn run(args: &[String]) -> Result<(), String> {
+    let mut config = Config { dim: 512, vectors: 512, cond: 1e12, seed: 42, method: Method::Mgs };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = args.next().ok_or(format!("missing value for {}", arg))?;
+        let invalid = || format!("invalid value {} for {}", value, arg);
+        match arg.as_str() {
+            "--dim" => config.dim = value.parse().map_err(|_| invalid())?,
+            "--vectors" => config.vectors = value.parse().map_err(|_| invalid())?,
+            "--cond" => config.cond = value.parse().map_err(|_| invalid())?,
+            "--seed" => config.seed = value.parse().map_err(|_| invalid())?,
+            "--method" => config.method = Method::parse(value).ok_or_else(invalid)?,
+            _ => return Err(format!("unknown argument {}", arg)),
+        }
+    }
+    if config.vectors > config.dim || config.vectors == 0 {
+        return Err("--vectors must be between 1 and --dim".to_string());
+    }
+    let outcome = stress(&config);
+    println!(
+        "method={:?} dim={} vectors={} cond={:e} seed={} time={:.6}s orthogonality_error={:e}",
+        config.method, config.dim, config.vectors, config.cond, config.seed, outcome.seconds,
+        outcome.orthogonality_error,
+    );
+    return Ok(());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Method {
+    Mgs,
+    Cgs,
+    Cgs2,
+    Householder,
+}
+
+impl Method {
+    fn parse(name: &str) -> Option<Self> {
+        return match name {
+            "mgs" => Some(Self::Mgs),
+            "cgs" => Some(Self::Cgs),
+            "cgs2" => Some(Self::Cgs2),
+            "householder" => Some(Self::Householder),
+            _ => None,
+        };
+    }
+}
+
+struct Config {
+    dim: usize,
+    vectors: usize,
+    cond: f64,
+    seed: u64,
+    method: Method,
+}
+
+struct Outcome {
+    seconds: f64,
+    // `max |QᵀQ - I|`
+    orthogonality_error: f64,
+}
+
+// SplitMix64, so a seed gives the same matrix on every platform and version.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_f64(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+        return (z >> 11) as f64 / (1u64 << 53) as f64;
+    }
+
+    fn gaussian(&mut self) -> f64 {
+        let u = 1.0 - self.next_f64();
+        let v = self.next_f64();
+        return (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos();
+    }
+}
+
+fn random_orthonormal(rows: usize, cols: usize, rng: &mut SplitMix64) -> Matrix {
+    let columns: Vec<Vec<f64>> = (0..cols).map(|_| (0..rows).map(|_| rng.gaussian()).collect()).collect();
+    return householder_qr(&Matrix::from_columns(&columns)).0;
+}
+
+// `U Σ Vᵀ` with random orthonormal `U`, `V` and singular values spaced logarithmically from 1 down to `1 / cond`.
+fn test_matrix(config: &Config) -> Matrix {
+    let mut rng = SplitMix64(config.seed);
+    let mut u = random_orthonormal(config.dim, config.vectors, &mut rng);
+    let v = random_orthonormal(config.vectors, config.vectors, &mut rng);
+    for j in 0..config.vectors {
+        let exponent = if config.vectors > 1 { j as f64 / (config.vectors - 1) as f64 } else { 0.0 };
+        let sigma = config.cond.powf(-exponent);
+        u.column_mut(j).iter_mut().for_each(|x| *x *= sigma);
+    }
+    return &u * &v.transpose();
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    return a.iter().zip(b).map(|(x, y)| x * y).sum();
+}
+
+// Classical Gram-Schmidt, all projections of a column computed from its original values; `passes == 2` is CGS2.
+fn classical(columns: &mut [Vec<f64>], passes: usize) {
+    for index in 0..columns.len() {
+        let (done, rest) = columns.split_at_mut(index);
+        let column = &mut rest[0];
+        for _ in 0..passes {
+            let dots: Vec<f64> = done.iter().map(|q| dot(q, column)).collect();
+            for (q, d) in done.iter().zip(dots) {
+                column.iter_mut().zip(q).for_each(|(c, qi)| *c -= d * qi);
+            }
+        }
+        let norm = dot(column, column).sqrt();
+        column.iter_mut().for_each(|c| *c /= norm);
+    }
+}
+
+fn stress(config: &Config) -> Outcome {
+    let a = test_matrix(config);
+    let mut columns: Vec<Vec<f64>> = (0..a.cols()).map(|j| a.column(j).to_vec()).collect();
+    let start = Instant::now();
+    match config.method {
+        Method::Mgs => gram_schmidt_storage(&mut columns),
+        Method::Cgs => classical(&mut columns, 1),
+        Method::Cgs2 => classical(&mut columns, 2),
+        Method::Householder => {
+            let q = householder_qr(&a).0;
+            columns = (0..q.cols()).map(|j| q.column(j).to_vec()).collect();
+        }
+    }
+    let seconds = start.elapsed().as_secs_f64();
+
+    let mut orthogonality_error: f64 = 0.0;
+    for (i, a) in columns.iter().enumerate() {
+        for (j, b) in columns.iter().enumerate() {
+            let expected = if i == j { 1.0 } else { 0.0 };
+            orthogonality_error = orthogonality_error.max((dot(a, b) - expected).abs());
+        }
+    }
+    return Outcome { seconds, orthogonality_error };
+}
+
+#[cfg(test)]
+mod stress_test {
+    use super::{stress, test_matrix, Config, Method};
+
+    fn config(method: Method) -> Config {
+        return Config { dim: 12, vectors: 8, cond: 1e8, seed: 7, method };
+    }
+
+    #[test]
+    fn methods_order_by_stability() {
+        assert_eq!(test_matrix(&config(Method::Mgs)), test_matrix(&config(Method::Cgs)));
+        let error = |method| stress(&config(method)).orthogonality_error;
+        let (cgs, mgs) = (error(Method::Cgs), error(Method::Mgs));
+        assert!(cgs > mgs && mgs > error(Method::Cgs2));
+        assert!(error(Method::Cgs2) < 1e-13 && error(Method::Householder) < 1e-13);
+    }
+}
@@ -0,0 +1,133 @@
+use std::time::Instant;
+
+// Metrics emitted through the `metrics` facade when the `metrics` feature is on. Install any `metrics` recorder
+// (e.g. a Prometheus exporter) to collect them; without one they cost next to nothing. Every metric carries a
+// `kernel` label naming the routine, such as `gram_schmidt`, `gram_schmidt_with_policy` or `householder_qr`.
+
+// Counter: completed or attempted orthogonalizations, one per call.
+pub const ORTHOGONALIZATIONS: &str = "gram_schmidt_orthogonalizations_total";
+// Counter: input vectors found to be linearly dependent on the ones before them.
+pub const RANK_DEFICIENCIES: &str = "gram_schmidt_rank_deficiencies_total";
+// Counter: extra projection passes over a vector to restore orthogonality lost to cancellation.
+pub const REORTHOGONALIZATION_PASSES: &str = "gram_schmidt_reorthogonalization_passes_total";
+// Histogram: wall-clock time of one call in seconds.
+pub const KERNEL_SECONDS: &str = "gram_schmidt_kernel_seconds";
+
+const KERNEL: &str = "kernel";
+
+// Counts one orthogonalization and records its duration when dropped.
+pub(crate) struct KernelTimer {
+    kernel: &'static str,
+    start: Instant,
+}
+
+impl Drop for KernelTimer {
+    fn drop(&mut self) {
+        metrics::histogram!(KERNEL_SECONDS, KERNEL => self.kernel).record(self.start.elapsed().as_secs_f64());
+    }
+}
+
+pub(crate) fn orthogonalization(kernel: &'static str) -> KernelTimer {
+    metrics::counter!(ORTHOGONALIZATIONS, KERNEL => kernel).increment(1);
+    return KernelTimer { kernel, start: Instant::now() };
+}
+
+pub(crate) fn rank_deficiencies(kernel: &'static str, count: u64) {
+    if count > 0 {
+        metrics::counter!(RANK_DEFICIENCIES, KERNEL => kernel).increment(count);
+    }
+}
+
+pub(crate) fn reorthogonalization_passes(kernel: &'static str, count: u64) {
+    if count > 0 {
+        metrics::counter!(REORTHOGONALIZATION_PASSES, KERNEL => kernel).increment(count);
+    }
+}
+
+#[cfg(test)]
+mod telemetry_test {
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    use metrics::{
+        Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder, SharedString, Unit,
+    };
+
+    use crate::telemetry::{KERNEL_SECONDS, ORTHOGONALIZATIONS, RANK_DEFICIENCIES, REORTHOGONALIZATION_PASSES};
+    use crate::{
+        gram_schmidt_with_policy, orthonormalize_cancellable, Matrix, OrthonormalizeOptions, Vector3, ZeroVectorPolicy,
+    };
+
+    // Sums every counter and counts every histogram sample under `name{kernel}`.
+    #[derive(Default)]
+    struct Totals(Mutex<HashMap<String, u64>>);
+
+    struct Handle(Arc<Totals>, String);
+
+    impl CounterFn for Handle {
+        fn increment(&self, value: u64) {
+            *self.0 .0.lock().unwrap().entry(self.1.clone()).or_default() += value;
+        }
+
+        fn absolute(&self, _: u64) {}
+    }
+
+    impl HistogramFn for Handle {
+        fn record(&self, _: f64) {
+            self.increment(1);
+        }
+    }
+
+    #[derive(Default)]
+    struct TestRecorder(Arc<Totals>);
+
+    impl TestRecorder {
+        fn handle(&self, key: &Key) -> Arc<Handle> {
+            let kernel = key.labels().map(|label| label.value().to_string()).collect::<String>();
+            return Arc::new(Handle(self.0.clone(), format!("{}{{{}}}", key.name(), kernel)));
+        }
+
+        fn get(&self, name: &str, kernel: &str) -> u64 {
+            return self.0 .0.lock().unwrap().get(&format!("{}{{{}}}", name, kernel)).copied().unwrap_or(0);
+        }
+    }
+
+    impl Recorder for TestRecorder {
+        fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+        fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+            return Counter::from_arc(self.handle(key));
+        }
+
+        fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+            return Gauge::noop();
+        }
+
+        fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+            return Histogram::from_arc(self.handle(key));
+        }
+    }
+
+    #[test]
+    fn emits_counters_and_timings() {
+        let recorder = TestRecorder::default();
+        metrics::with_local_recorder(&recorder, || {
+            let mut basis = vec![Vector3::E0, Vector3::E1];
+            Vector3::gram_schmidt(&mut basis);
+            Vector3::gram_schmidt(&mut basis);
+            let mut dependent = vec![Vector3::E0, Vector3::E0 * 2.0, Vector3::E1];
+            gram_schmidt_with_policy(&mut dependent, ZeroVectorPolicy::Skip).unwrap();
+            let options = OrthonormalizeOptions { reorthogonalize: true, ..Default::default() };
+            let matrix = Matrix::from_columns(&[vec![1.0, 0.0], vec![1.0, 1.0]]);
+            orthonormalize_cancellable(&matrix, &options).unwrap();
+        });
+        assert_eq!(recorder.get(ORTHOGONALIZATIONS, "gram_schmidt"), 2);
+        assert_eq!(recorder.get(KERNEL_SECONDS, "gram_schmidt"), 2);
+        assert_eq!(recorder.get(RANK_DEFICIENCIES, "gram_schmidt_with_policy"), 1);
+        assert_eq!(recorder.get(REORTHOGONALIZATION_PASSES, "orthonormalize_cancellable"), 2);
+    }
+}
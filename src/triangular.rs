@@ -0,0 +1,172 @@
+use std::ops::Index;
+
+use crate::{Error, Matrix};
+
+// Square upper triangular matrix, such as the `R` factor of a QR factorization. Entries below the diagonal are
+// ignored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpperTriangular {
+    matrix: Matrix,
+}
+
+impl UpperTriangular {
+    // Takes the upper triangle of the square `matrix`.
+    pub fn new(mut matrix: Matrix) -> Self {
+        assert_eq!(matrix.rows(), matrix.cols(), "triangular matrices must be square");
+        for j in 0..matrix.cols() {
+            matrix.column_mut(j)[j + 1..].iter_mut().for_each(|x| *x = 0.0);
+        }
+        return Self { matrix };
+    }
+
+    pub fn dim(&self) -> usize {
+        return self.matrix.rows();
+    }
+
+    pub fn as_matrix(&self) -> &Matrix {
+        return &self.matrix;
+    }
+
+    // Solves `R x = b` by back substitution.
+    pub fn solve(&self, b: &[f64]) -> Vec<f64> {
+        let n = self.dim();
+        assert_eq!(b.len(), n, "right-hand side must match the dimension");
+        let mut x = b.to_vec();
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] -= self.matrix[(i, j)] * x[j];
+            }
+            x[i] /= self.matrix[(i, i)];
+        }
+        return x;
+    }
+
+    // Like `solve`, but reports a right-hand side of the wrong length instead of panicking.
+    pub fn try_solve(&self, b: &[f64]) -> Result<Vec<f64>, Error> {
+        if b.len() != self.dim() {
+            return Err(Error::DimensionMismatch { expected: self.dim(), found: b.len() });
+        }
+        return Ok(self.solve(b));
+    }
+
+    // Solves `Rᵀ x = b` by forward substitution.
+    pub fn solve_transpose(&self, b: &[f64]) -> Vec<f64> {
+        let n = self.dim();
+        assert_eq!(b.len(), n, "right-hand side must match the dimension");
+        let mut x = b.to_vec();
+        for i in 0..n {
+            let column = self.matrix.column(i);
+            x[i] = (x[i] - (0..i).map(|j| column[j] * x[j]).sum::<f64>()) / column[i];
+        }
+        return x;
+    }
+
+    pub fn inverse(&self) -> Self {
+        let n = self.dim();
+        let mut inverse = Matrix::zeros(n, n);
+        for j in 0..n {
+            let mut e = vec![0.0; n];
+            e[j] = 1.0;
+            inverse.column_mut(j).copy_from_slice(&self.solve(&e));
+        }
+        return Self::new(inverse);
+    }
+
+    // Condition number `‖R‖₁ ‖R⁻¹‖₁` in the 1-norm, computed from the explicit inverse, which costs O(n³) and is meant
+    // for the small `R` factors of tall-skinny problems. Infinite for a singular matrix.
+    pub fn condition(&self) -> f64 {
+        if (0..self.dim()).any(|i| self.matrix[(i, i)] == 0.0) {
+            return f64::INFINITY;
+        }
+        return one_norm(&self.matrix) * one_norm(&self.inverse().matrix);
+    }
+}
+
+impl TryFrom<Matrix> for UpperTriangular {
+    type Error = Error;
+
+    // Fails for a non-square matrix.
+    fn try_from(matrix: Matrix) -> Result<Self, Self::Error> {
+        if matrix.rows() != matrix.cols() {
+            return Err(Error::DimensionMismatch { expected: matrix.rows(), found: matrix.cols() });
+        }
+        return Ok(Self::new(matrix));
+    }
+}
+
+// First-order estimate of how far the Q factor of `A = Q R` can move, in the Frobenius norm, when `A` is perturbed by
+// `ΔA` with `‖ΔA‖ ≤ eps ‖A‖`: `‖ΔQ‖_F ≲ √2 κ(R) eps`. Uses the 1-norm condition number, which is within a factor of
+// the dimension of the 2-norm one. Infinite once `κ(R) eps ≥ 1`, where the perturbation can make `A` rank deficient.
+pub fn perturbation_bound(r: &UpperTriangular, eps: f64) -> f64 {
+    let amplification = r.condition() * eps;
+    if amplification >= 1.0 {
+        return f64::INFINITY;
+    }
+    return std::f64::consts::SQRT_2 * amplification;
+}
+
+fn one_norm(m: &Matrix) -> f64 {
+    return (0..m.cols()).map(|j| m.column(j).iter().map(|x| x.abs()).sum::<f64>()).fold(0.0, f64::max);
+}
+
+impl Index<(usize, usize)> for UpperTriangular {
+    type Output = f64;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        return &self.matrix[index];
+    }
+}
+
+#[cfg(test)]
+mod triangular_test {
+    use crate::{perturbation_bound, IncrementalGramSchmidt, Matrix, UpperTriangular, Vector, Vector3};
+
+    #[test]
+    fn solves_and_inverts() {
+        let r = UpperTriangular::new(Matrix::from_rows(&[
+            vec![2.0, 1.0, -1.0],
+            vec![9.0, 4.0, 2.0],
+            vec![9.0, 9.0, 0.5],
+        ]));
+        assert_eq!(r[(1, 0)], 0.0);
+        let x = r.solve(&[-2.0, 12.0, 1.0]);
+        assert_eq!(x, vec![-1.0, 2.0, 2.0]);
+        let y = r.solve_transpose(&[2.0, 5.0, 2.0]);
+        assert_eq!(y, vec![1.0, 1.0, 2.0]);
+
+        let product = r.as_matrix() * r.inverse().as_matrix();
+        assert_eq!(product, Matrix::identity(3));
+        assert_eq!(r.condition(), 5.0 * 4.5);
+    }
+
+    #[test]
+    fn perturbation_bound_covers_observed_change() {
+        let columns = [Vector3::new([1.0, 0.0, 0.0]), Vector3::new([1.0, 1e-3, 0.0]), Vector3::new([0.0, 1.0, 1.0])];
+        let factor = |columns: &[Vector3]| {
+            let mut gs = IncrementalGramSchmidt::new();
+            columns.iter().for_each(|c| {
+                gs.push(*c).unwrap();
+            });
+            return gs;
+        };
+        let gs = factor(&columns);
+        let mut r = Matrix::zeros(3, 3);
+        for (j, column) in gs.r().iter().enumerate() {
+            r.column_mut(j)[..column.len()].copy_from_slice(column);
+        }
+        let r = UpperTriangular::new(r);
+
+        let eps = 1e-9;
+        let mut perturbed = columns;
+        perturbed[1][2] += eps;
+        let moved: f64 = gs.basis().iter().zip(factor(&perturbed).basis())
+            .map(|(q, p)| Vector::dot_product(&(*q - *p), &(*q - *p)))
+            .sum::<f64>()
+            .sqrt();
+        let bound = perturbation_bound(&r, eps);
+        assert!(moved > 100.0 * eps && moved <= bound);
+
+        assert_eq!(perturbation_bound(&UpperTriangular::new(Matrix::identity(2)), 0.5), 0.5 * std::f64::consts::SQRT_2);
+        assert_eq!(perturbation_bound(&r, 1.0), f64::INFINITY);
+    }
+}
@@ -0,0 +1,748 @@
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::iter::Sum;
+use std::ops::{Add, ControlFlow, Div, Index, IndexMut, Mul, Neg, Sub};
+
+use crate::{validate_finite, Error, Warning};
+
+pub trait Vector where
+    Self: Sized
+    + Index<usize, Output=f64>
+    + IndexMut<usize>
+    + Clone
+    + Mul<f64, Output=Self>
+    + Add<Output=Self>
+    + Sub<Output=Self>
+    + Neg<Output=Self>
+    + Div<f64, Output=Self>
+    + Sum<Self> {
+    const DIM: usize;
+
+    fn get_component(&self, index: usize) -> f64;
+
+    fn get_components_mut(&mut self) -> &mut [f64];
+
+    fn zero() -> Self {
+        return std::iter::empty().sum();
+    }
+
+    fn unit(index: usize) -> Self {
+        let mut v = Self::zero();
+        v[index] = 1.0;
+        return v;
+    }
+
+    fn dot_product(v1: &Self, v2: &Self) -> f64 {
+        let fused = fuse_multiply_add();
+        let mut sum = 0.0;
+        for i in 0..Self::DIM {
+            sum = multiply_add(fused, v1[i], v2[i], sum);
+        }
+        return sum;
+    }
+
+    fn scale_with_dot_prod(&mut self, v2: &Self) {
+        for i in 0..Self::DIM {
+            self[i] = self[i] * self[i] * v2[i];
+        }
+    }
+
+
+    fn length(&self) -> f64 {
+        return square_root(Self::dot_product(self, self));
+    }
+
+    fn norm_l1(&self) -> f64 {
+        let mut sum = 0.0;
+        for i in 0..Self::DIM {
+            sum += self[i].abs();
+        }
+        return sum;
+    }
+
+    fn norm_linf(&self) -> f64 {
+        let mut max: f64 = 0.0;
+        for i in 0..Self::DIM {
+            max = max.max(self[i].abs());
+        }
+        return max;
+    }
+
+    fn norm_lp(&self, p: f64) -> f64 {
+        if p == f64::INFINITY {
+            return self.norm_linf();
+        }
+        let mut sum = 0.0;
+        for i in 0..Self::DIM {
+            sum += self[i].abs().powf(p);
+        }
+        return sum.powf(1.0 / p);
+    }
+
+    // Rounding can push the quotient slightly outside [-1, 1], which would make `acos` return NaN.
+    fn cosine_similarity(&self, other: &Self) -> f64 {
+        let cos = Self::dot_product(self, other) / (self.length() * other.length());
+        return cos.clamp(-1.0, 1.0);
+    }
+
+    fn angle_between(&self, other: &Self) -> f64 {
+        return self.cosine_similarity(other).acos();
+    }
+
+    fn normalize(&mut self) {
+        let len = self.length();
+        self.get_components_mut().iter_mut().for_each(|c| *c = divide(*c, len));
+    }
+
+    fn lerp(a: &Self, b: &Self, t: f64) -> Self {
+        return a.clone() * (1.0 - t) + b.clone() * t;
+    }
+
+    // Expects unit vectors. Falls back to `lerp` when they are (anti)parallel and the great circle is undefined.
+    fn slerp(a: &Self, b: &Self, t: f64) -> Self {
+        let omega = a.angle_between(b);
+        let sin_omega = omega.sin();
+        if sin_omega.abs() < f64::EPSILON {
+            return Self::lerp(a, b, t);
+        }
+        let wa = ((1.0 - t) * omega).sin() / sin_omega;
+        let wb = (t * omega).sin() / sin_omega;
+        return a.clone() * wa + b.clone() * wb;
+    }
+
+    fn scale(self, lambda: f64) -> Self {
+        return self * lambda;
+    }
+
+    fn sub(&mut self, other: &Self) {
+        for i in 0..Self::DIM {
+            self[i] -= other[i];
+        }
+    }
+
+    // `self += alpha * x`
+    fn axpy(&mut self, alpha: f64, x: &Self) {
+        let fused = fuse_multiply_add();
+        for i in 0..Self::DIM {
+            self[i] = multiply_add(fused, alpha, x[i], self[i]);
+        }
+    }
+
+    // `self += alpha * x` with a single rounding per component, independent of the target and the `no-fma` feature.
+    fn axpy_fused(&mut self, alpha: f64, x: &Self) {
+        for i in 0..Self::DIM {
+            self[i] = alpha.mul_add(x[i], self[i]);
+        }
+    }
+
+    fn gram_schmidt(basis: &mut [Self]) {
+        Self::gram_schmidt_with_progress(basis, |_, _| ControlFlow::Continue(()));
+    }
+
+    // Like `gram_schmidt`, but first checks that every component is finite, so a single NaN or infinity is reported
+    // instead of spreading through the whole basis. The basis is left untouched on error.
+    fn try_gram_schmidt(basis: &mut [Self]) -> Result<(), Error> {
+        validate_finite(basis)?;
+        Self::gram_schmidt(basis);
+        return Ok(());
+    }
+
+    // Like `gram_schmidt`, but calls `on_warning` whenever a vector loses so much of its norm to the projections that
+    // its residual is likely contaminated by rounding.
+    fn gram_schmidt_with_warnings<F: FnMut(Warning)>(basis: &mut [Self], mut on_warning: F) {
+        let threshold = f64::EPSILON.sqrt();
+        for index in 0..basis.len() {
+            let (a, previous) = basis[..=index].split_last_mut().unwrap();
+            let original = a.length();
+            for b in previous.iter() {
+                let dot = Self::dot_product(a, b);
+                a.axpy(-dot, b);
+            }
+            let residual_ratio = a.length() / original;
+            if residual_ratio < threshold {
+                on_warning(Warning::LossOfOrthogonality { vector: index, residual_ratio });
+            }
+            a.normalize();
+        }
+    }
+
+    fn gram_schmidt_collect_warnings(basis: &mut [Self]) -> Vec<Warning> {
+        let mut warnings = vec![];
+        Self::gram_schmidt_with_warnings(basis, |warning| warnings.push(warning));
+        return warnings;
+    }
+
+    // Calls `progress(done, total)` after every vector. Returns how many leading vectors have been orthonormalized,
+    // which is less than `basis.len()` if `progress` broke off the run.
+    fn gram_schmidt_with_progress<F>(basis: &mut [Self], mut progress: F) -> usize
+        where F: FnMut(usize, usize) -> ControlFlow<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("gram_schmidt", vectors = basis.len(), dim = Self::DIM).entered();
+        #[cfg(feature = "metrics")]
+        let _timer = crate::telemetry::orthogonalization("gram_schmidt");
+        let total = basis.len();
+        for index in 0..total {
+            let (a, previous) = basis[..=index].split_last_mut().unwrap();
+            for b in previous.iter() {
+                let dot = Self::dot_product(a, b);
+                a.axpy(-dot, b);
+            }
+            #[cfg(feature = "tracing")]
+            tracing::trace!(index, residual_norm = a.length(), "orthogonalized vector");
+            a.normalize();
+            if progress(index + 1, total).is_break() {
+                return index + 1;
+            }
+        }
+        return total;
+    }
+
+    // Orders by the first differing component using `f64::total_cmp`, so NaN and signed zeros are ordered too.
+    fn lexicographic_cmp(&self, other: &Self) -> Ordering {
+        for i in 0..Self::DIM {
+            let ordering = self[i].total_cmp(&other[i]);
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        return Ordering::Equal;
+    }
+
+    fn component_mul(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for i in 0..Self::DIM {
+            result[i] *= other[i];
+        }
+        return result;
+    }
+
+    fn component_div(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for i in 0..Self::DIM {
+            result[i] /= other[i];
+        }
+        return result;
+    }
+}
+
+// Whether `a * b + c` is fused: only when the target has hardware FMA, unless the `no-fma` or `soft-float` feature or
+// strict mode (`set_strict_fp`) asks for bit-reproducible results across targets. Without hardware support
+// `f64::mul_add` falls back to a slow software routine, so it is not used. Kernels call this once and pass the result
+// to `multiply_add`.
+#[inline(always)]
+pub(crate) fn fuse_multiply_add() -> bool {
+    let hardware = cfg!(all(target_feature = "fma", not(feature = "no-fma"), not(feature = "soft-float")));
+    return hardware && !crate::strict_fp();
+}
+
+// `a * b + c`, with a single rounding if `fused`.
+#[cfg(not(feature = "soft-float"))]
+#[inline(always)]
+pub(crate) fn multiply_add(fused: bool, a: f64, b: f64, c: f64) -> f64 {
+    return if fused { a.mul_add(b, c) } else { a * b + c };
+}
+
+// With the `soft-float` feature the shared kernels (`dot_product`, `axpy`, `length`, `normalize` and the `storage_*`
+// functions) do their arithmetic in `SoftF64`, so everything built on them is bit-identical on every platform.
+#[cfg(feature = "soft-float")]
+pub(crate) fn multiply_add(_fused: bool, a: f64, b: f64, c: f64) -> f64 {
+    use crate::soft_float::SoftF64;
+    return (SoftF64::from_f64(a) * SoftF64::from_f64(b) + SoftF64::from_f64(c)).to_f64();
+}
+
+#[cfg(not(feature = "soft-float"))]
+#[inline(always)]
+pub(crate) fn divide(a: f64, b: f64) -> f64 {
+    return a / b;
+}
+
+#[cfg(feature = "soft-float")]
+pub(crate) fn divide(a: f64, b: f64) -> f64 {
+    use crate::soft_float::SoftF64;
+    return (SoftF64::from_f64(a) / SoftF64::from_f64(b)).to_f64();
+}
+
+#[cfg(not(feature = "soft-float"))]
+#[inline(always)]
+pub(crate) fn square_root(x: f64) -> f64 {
+    return x.sqrt();
+}
+
+#[cfg(feature = "soft-float")]
+pub(crate) fn square_root(x: f64) -> f64 {
+    return crate::soft_float::SoftF64::from_f64(x).sqrt().to_f64();
+}
+
+// Defines a fixed-size vector type. The struct is `#[repr(C)]` with the components as its only field, so it has the
+// layout of `[f64; DIM]`; extra attributes such as `#[repr(align(32))]` can be given before the name.
+#[macro_export]
+macro_rules! vector {
+    ($(#[$attr:meta])* $name:ident, $dim:expr, [$($basis:ident = $index:expr),*]) => {
+        #[derive(Debug, PartialEq, Clone, Copy)]
+        #[repr(C)]
+        $(#[$attr])*
+        pub struct $name {
+            pub components: [f64; $dim],
+        }
+
+        impl $name {
+            pub const DIM: usize = $dim;
+
+            $(pub const $basis: Self = Self::unit($index);)*
+
+            pub const fn new(components: [f64; Self::DIM]) -> Self {
+                return Self { components };
+            }
+
+            pub const fn empty() -> Self {
+                return Self { components: [0.0; Self::DIM] };
+            }
+
+            pub const fn unit(index: usize) -> Self {
+                let mut components = [0.0; Self::DIM];
+                components[index] = 1.0;
+                return Self { components };
+            }
+
+            // Builds the components in place, without zero-filling them first.
+            pub fn from_fn<F: FnMut(usize) -> f64>(f: F) -> Self {
+                return Self { components: ::core::array::from_fn(f) };
+            }
+
+            pub fn write_uninit<F: FnMut(usize) -> f64>(out: &mut ::core::mem::MaybeUninit<Self>, f: F) -> &mut Self {
+                return out.write(Self::from_fn(f));
+            }
+
+            // Copies the components into the uninitialized buffer `out`, which must have length `DIM`, and returns
+            // it as initialized.
+            pub fn write_components<'a>(&self, out: &'a mut [::core::mem::MaybeUninit<f64>]) -> &'a mut [f64] {
+                assert_eq!(out.len(), Self::DIM, "output buffer must have length DIM");
+                for (slot, component) in out.iter_mut().zip(&self.components) {
+                    slot.write(*component);
+                }
+                // SAFETY: every element was initialized above and `MaybeUninit<f64>` has the layout of `f64`.
+                return unsafe { &mut *(out as *mut [::core::mem::MaybeUninit<f64>] as *mut [f64]) };
+            }
+
+            // The `DIM` components, contiguous and in order, starting at the address of the vector itself.
+            pub const fn as_slice(&self) -> &[f64] {
+                return &self.components;
+            }
+
+            pub const fn as_ptr(&self) -> *const f64 {
+                return self.components.as_ptr();
+            }
+
+            pub fn as_mut_ptr(&mut self) -> *mut f64 {
+                return self.components.as_mut_ptr();
+            }
+
+            pub fn scaled_copy(&self, lambda: f64) -> Self {
+                return Self::from_fn(|i| self.components[i] * lambda);
+            }
+
+            pub fn gram_schmidt(basis: &mut [Self]) {
+                <Self as $crate::Vector>::gram_schmidt(basis);
+            }
+        }
+
+        impl ::std::ops::Add for $name {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                return Self::from_fn(|i| self.components[i] + rhs.components[i]);
+            }
+        }
+
+        impl ::std::ops::Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                return Self::from_fn(|i| self.components[i] - rhs.components[i]);
+            }
+        }
+
+        impl ::std::ops::Neg for $name {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                return Self::from_fn(|i| -self.components[i]);
+            }
+        }
+
+        impl ::std::iter::Sum for $name {
+            fn sum<I: Iterator<Item=Self>>(iter: I) -> Self {
+                return iter.fold(
+                    Self::empty(),
+                    |a, b| a + b,
+                );
+            }
+        }
+
+        impl ::std::ops::Mul<f64> for $name {
+            type Output = Self;
+
+            fn mul(self, rhs: f64) -> Self::Output {
+                return Self::from_fn(|i| self.components[i] * rhs);
+            }
+        }
+
+        impl ::std::ops::Mul<$name> for f64 {
+            type Output = $name;
+
+            fn mul(self, rhs: $name) -> Self::Output {
+                return rhs * self;
+            }
+        }
+
+        impl ::std::ops::Div<f64> for $name {
+            type Output = Self;
+
+            fn div(self, rhs: f64) -> Self::Output {
+                return Self::from_fn(|i| self.components[i] / rhs);
+            }
+        }
+
+        impl ::std::ops::Index<usize> for $name {
+            type Output = f64;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                return &self.components[index];
+            }
+        }
+
+        impl ::std::ops::IndexMut<usize> for $name {
+            fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+                return &mut self.components[index];
+            }
+        }
+
+        impl $crate::Storage for $name {
+            fn as_slice(&self) -> &[f64] {
+                return &self.components;
+            }
+
+            fn as_mut_slice(&mut self) -> &mut [f64] {
+                return &mut self.components;
+            }
+        }
+
+        impl $crate::Vector for $name {
+
+            const DIM: usize = $dim;
+
+            fn dot_product(v1: &Self, v2: &Self) -> f64 {
+                return $crate::storage_dot(v1, v2);
+            }
+
+            fn axpy(&mut self, alpha: f64, x: &Self) {
+                $crate::storage_axpy(alpha, x, self);
+            }
+
+            fn get_component(&self, index: usize) -> f64 {
+                return self.components[index];
+            }
+
+            fn get_components_mut(&mut self) -> &mut [f64] {
+                return &mut self.components;
+            }
+        }
+    };
+}
+
+// Usage
+vector!(Vector2, 2, [E0 = 0, E1 = 1]);
+vector!(Vector3, 3, [E0 = 0, E1 = 1, E2 = 2]);
+vector!(Vector4, 4, [E0 = 0, E1 = 1, E2 = 2, E3 = 3]);
+vector!(Vector5, 5, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4]);
+vector!(Vector6, 6, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5]);
+vector!(Vector7, 7, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6]);
+vector!(Vector8, 8, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7]);
+vector!(Vector9, 9, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7, E8 = 8]);
+vector!(Vector10, 10, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7, E8 = 8, E9 = 9]);
+vector!(Vector11, 11, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7, E8 = 8, E9 = 9, E10 = 10]);
+vector!(Vector12, 12, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7, E8 = 8, E9 = 9, E10 = 10, E11 = 11]);
+vector!(Vector13, 13, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7, E8 = 8, E9 = 9, E10 = 10, E11 = 11, E12 = 12]);
+vector!(Vector14, 14, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7, E8 = 8, E9 = 9, E10 = 10, E11 = 11, E12 = 12, E13 = 13]);
+vector!(Vector15, 15, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7, E8 = 8, E9 = 9, E10 = 10, E11 = 11, E12 = 12, E13 = 13, E14 = 14]);
+vector!(Vector16, 16, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7, E8 = 8, E9 = 9, E10 = 10, E11 = 11, E12 = 12, E13 = 13, E14 = 14, E15 = 15]);
+
+// Variants aligned to 32 bytes, the width of an AVX register, so SIMD loads of their components are aligned and
+// buffers shared with C or GPU code need no repacking. Only dimensions that are a multiple of 4 are provided: their
+// size is a multiple of the alignment, so arrays of them have no padding between elements.
+vector!(#[repr(align(32))] AlignedVector4, 4, [E0 = 0, E1 = 1, E2 = 2, E3 = 3]);
+vector!(#[repr(align(32))] AlignedVector8, 8, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7]);
+vector!(#[repr(align(32))] AlignedVector16, 16, [E0 = 0, E1 = 1, E2 = 2, E3 = 3, E4 = 4, E5 = 5, E6 = 6, E7 = 7, E8 = 8, E9 = 9, E10 = 10, E11 = 11, E12 = 12, E13 = 13, E14 = 14, E15 = 15]);
+
+impl Vector3 {
+    pub fn cross(&self, other: &Self) -> Self {
+        let (a, b) = (&self.components, &other.components);
+        return Self::new([
+            a[1] * b[2] - a[2] * b[1],
+            a[2] * b[0] - a[0] * b[2],
+            a[0] * b[1] - a[1] * b[0],
+        ]);
+    }
+}
+
+// Compares and hashes the bit patterns of the components: `0.0` and `-0.0` are distinct, identical NaNs are equal.
+#[derive(Debug, Clone)]
+pub struct HashableVector<V: Vector>(pub V);
+
+impl<V: Vector> PartialEq for HashableVector<V> {
+    fn eq(&self, other: &Self) -> bool {
+        for i in 0..V::DIM {
+            if self.0[i].to_bits() != other.0[i].to_bits() {
+                return false;
+            }
+        }
+        return true;
+    }
+}
+
+impl<V: Vector> Eq for HashableVector<V> {}
+
+impl<V: Vector> Hash for HashableVector<V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for i in 0..V::DIM {
+            self.0[i].to_bits().hash(state);
+        }
+    }
+}
+
+impl<V: Vector> PartialOrd for HashableVector<V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl<V: Vector> Ord for HashableVector<V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.0.lexicographic_cmp(&other.0);
+    }
+}
+
+#[cfg(test)]
+mod vec3_test {
+    use std::mem::{align_of, size_of};
+
+    use crate::{AlignedVector16, AlignedVector4, AlignedVector8, Vector, Vector16, Vector2, Vector3, Vector4};
+
+    #[test]
+    fn test_dot_product() {
+        let v1 = Vector4::new([1.0, 2.0, 3.0, 6.0]);
+        let v2 = Vector4::new([3.0, 4.0, 5.0, 7.0]);
+        assert_eq!(Vector4::dot_product(&v1, &v2), 68.0);
+    }
+
+    #[test]
+    fn scale_with_dot_prod() {
+        let mut v1 = Vector4::new([1.0, 2.0, 3.0, 6.0]);
+        let v2 = Vector4::new([3.0, 4.0, 5.0, 7.0]);
+        v1.scale_with_dot_prod(&v2);
+        assert_eq!(v1, Vector4::new([3.0, 16.0, 45.0, 252.0]));
+    }
+
+    #[test]
+    fn test_normalize() {
+        let mut v1 = Vector4::new([4.0, 4.0, 4.0, 4.0]);
+        v1.normalize();
+        assert_eq!(v1, Vector4::new([4.0 / 8.0, 4.0 / 8.0, 4.0 / 8.0, 4.0 / 8.0]));
+    }
+
+    #[test]
+    fn test_length() {
+        let v1 = Vector4::new([4.0, 4.0, 4.0, 4.0]);
+        let v2 = Vector4::new([3.0, 4.0, 5.0, 7.0]);
+        assert_eq!(v1.length(), 8.0);
+        assert_eq!(v2.length(), 99_f64.sqrt());
+    }
+
+    #[test]
+    fn test_other_dimensions() {
+        crate::vector!(Vector20, 20, [E0 = 0]);
+
+        let v1 = Vector2::new([3.0, 4.0]);
+        let v2 = Vector16::E15 * 2.0;
+        let v3 = Vector20::E0 + Vector20::unit(19);
+        assert_eq!(v1.length(), 5.0);
+        assert_eq!(v2.length(), 2.0);
+        assert_eq!(v3.norm_l1(), 2.0);
+    }
+
+    #[test]
+    fn aligned_layout() {
+        assert_eq!((align_of::<AlignedVector4>(), size_of::<AlignedVector4>()), (32, 32));
+        assert_eq!((align_of::<AlignedVector16>(), size_of::<AlignedVector16>()), (32, 128));
+        assert_eq!(size_of::<Vector3>(), size_of::<[f64; 3]>());
+        let vectors = [AlignedVector8::E7, AlignedVector8::unit(1) * 2.0];
+        for v in &vectors {
+            assert_eq!(v.as_ptr() as usize % 32, 0);
+            assert_eq!(v.as_ptr(), v as *const AlignedVector8 as *const f64);
+        }
+        assert_eq!(vectors[1].as_slice(), [0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+        let mut basis = vec![AlignedVector4::new([1.0, 1.0, 0.0, 0.0]), AlignedVector4::new([1.0, 0.0, 0.0, 0.0])];
+        AlignedVector4::gram_schmidt(&mut basis);
+        assert!((basis[1][1] + 0.5f64.sqrt()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_norms() {
+        let v1 = Vector4::new([3.0, -4.0, 0.0, 0.0]);
+        assert_eq!(v1.norm_l1(), 7.0);
+        assert_eq!(v1.norm_linf(), 4.0);
+        assert_eq!(v1.norm_lp(1.0), 7.0);
+        assert_eq!(v1.norm_lp(2.0), 5.0);
+        assert_eq!(v1.norm_lp(f64::INFINITY), 4.0);
+    }
+
+    #[test]
+    fn test_angle_between() {
+        let v1 = Vector4::new([1.0, 0.0, 0.0, 0.0]);
+        let v2 = Vector4::new([0.0, 2.0, 0.0, 0.0]);
+        let v3 = Vector4::new([3.0, 3.0, 3.0, 3.0]);
+        assert_eq!(v1.cosine_similarity(&v2), 0.0);
+        assert_eq!(v1.angle_between(&v2), std::f64::consts::FRAC_PI_2);
+        assert_eq!(v3.cosine_similarity(&v3), 1.0);
+        assert_eq!(v3.angle_between(&v3), 0.0);
+    }
+
+    #[test]
+    fn test_cross() {
+        assert_eq!(Vector3::E0.cross(&Vector3::E1), Vector3::E2);
+        assert_eq!(Vector3::E2.cross(&Vector3::E1), -Vector3::E0);
+    }
+
+    #[test]
+    fn test_neg() {
+        let v1 = Vector4::new([1.0, -2.0, 0.0, 6.0]);
+        assert_eq!(-v1, Vector4::new([-1.0, 2.0, -0.0, -6.0]));
+    }
+
+    #[test]
+    fn test_const_constructors() {
+        const ORIGIN: Vector4 = Vector4::empty();
+        static DIAGONAL: Vector4 = Vector4::new([1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(ORIGIN, Vector4::new([0.0; 4]));
+        assert_eq!(Vector4::E2, Vector4::new([0.0, 0.0, 1.0, 0.0]));
+        assert_eq!(Vector4::E0 + Vector4::E1 + Vector4::E2 + Vector4::E3, DIAGONAL);
+    }
+
+    #[test]
+    fn test_uninit_constructors() {
+        let v = Vector4::from_fn(|i| i as f64);
+        assert_eq!(v, Vector4::new([0.0, 1.0, 2.0, 3.0]));
+
+        let mut slot = std::mem::MaybeUninit::uninit();
+        let w = Vector4::write_uninit(&mut slot, |i| 2.0 * i as f64);
+        assert_eq!(*w, v * 2.0);
+
+        let mut buffer = [std::mem::MaybeUninit::uninit(); 4];
+        assert_eq!(v.write_components(&mut buffer), &[0.0, 1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_axpy() {
+        let mut v1 = Vector4::new([-1.0, 1.0, 0.0, 0.0]);
+        let v2 = Vector4::new([10.0, 2.0, 0.0, 1.0]);
+        v1.axpy(0.5, &v2);
+        assert_eq!(v1, Vector4::new([4.0, 2.0, 0.0, 0.5]));
+
+        // 0.1 * 10.0 rounds to exactly 1.0, so only the fused version keeps the representation error of 0.1.
+        let mut fused = Vector4::new([-1.0, 0.0, 0.0, 0.0]);
+        fused.axpy_fused(0.1, &Vector4::new([10.0, 0.0, 0.0, 0.0]));
+        assert_eq!(fused[0], 0.1_f64.mul_add(10.0, -1.0));
+        assert!(fused[0] != 0.0);
+    }
+
+    #[test]
+    fn test_scalar_mul() {
+        let v1 = Vector4::new([1.0, 2.0, 3.0, 6.0]);
+        assert_eq!(2.0 * v1, v1 * 2.0);
+        assert_eq!(2.0 * v1, Vector4::new([2.0, 4.0, 6.0, 12.0]));
+    }
+
+    #[test]
+    fn test_component_mul_div() {
+        let v1 = Vector4::new([1.0, 2.0, 3.0, 6.0]);
+        let v2 = Vector4::new([3.0, 4.0, 5.0, 7.0]);
+        assert_eq!(v1.component_mul(&v2), Vector4::new([3.0, 8.0, 15.0, 42.0]));
+        assert_eq!(v1.component_mul(&v2).component_div(&v2), v1);
+    }
+
+    #[test]
+    fn test_lerp_slerp() {
+        let v1 = Vector4::new([1.0, 0.0, 0.0, 0.0]);
+        let v2 = Vector4::new([0.0, 1.0, 0.0, 0.0]);
+        assert_eq!(Vector4::lerp(&v1, &v2, 0.25), Vector4::new([0.75, 0.25, 0.0, 0.0]));
+        let mid = Vector4::slerp(&v1, &v2, 0.5);
+        assert!((mid.length() - 1.0).abs() < 1e-12);
+        assert!((mid[0] - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+        assert!((mid[1] - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-12);
+        assert_eq!(Vector4::slerp(&v1, &v1, 0.5), v1);
+    }
+}
+
+#[cfg(test)]
+mod hashable_vector_test {
+    use std::collections::HashSet;
+    use crate::{HashableVector, Vector4};
+
+    #[test]
+    fn dedup_and_sort() {
+        let mut set = HashSet::new();
+        set.insert(HashableVector(Vector4::new([1.0, 0.0, 0.0, 0.0])));
+        set.insert(HashableVector(Vector4::new([1.0, 0.0, 0.0, 0.0])));
+        set.insert(HashableVector(Vector4::new([1.0, -0.0, 0.0, 0.0])));
+        set.insert(HashableVector(Vector4::new([0.5, 2.0, 0.0, 0.0])));
+        assert_eq!(set.len(), 3);
+
+        let mut sorted: Vec<_> = set.into_iter().collect();
+        sorted.sort();
+        assert_eq!(sorted.into_iter().map(|v| v.0).collect::<Vec<_>>(), vec![
+            Vector4::new([0.5, 2.0, 0.0, 0.0]),
+            Vector4::new([1.0, -0.0, 0.0, 0.0]),
+            Vector4::new([1.0, 0.0, 0.0, 0.0]),
+        ]);
+    }
+}
+
+#[cfg(test)]
+mod grim_schmidt_test {
+    use std::ops::ControlFlow;
+    use crate::{Vector, Vector4};
+
+    #[test]
+    fn basic_test() {
+        let mut basis = vec![
+            Vector4::new([1.0, 1.0, 1.0, 1.0]),
+            Vector4::new([0.0, 1.0, 0.0, 1.0]),
+            Vector4::new([0.0, 0.0, 1.0, 1.0]),
+            Vector4::new([0.0, 0.0, 0.0, 1.0]),
+        ];
+        Vector4::gram_schmidt(&mut basis);
+        assert_eq!(vec![
+            Vector4::new([0.5, 0.5, 0.5, 0.5]),
+            Vector4::new([-0.5, 0.5, -0.5, 0.5]),
+            Vector4::new([-0.5, -0.5, 0.5, 0.5]),
+            Vector4::new([0.5, -0.5, -0.5, 0.5]),
+        ], basis);
+    }
+
+    #[test]
+    fn cancel_mid_run() {
+        let mut basis = vec![
+            Vector4::new([2.0, 0.0, 0.0, 0.0]),
+            Vector4::new([1.0, 1.0, 0.0, 0.0]),
+            Vector4::new([1.0, 1.0, 1.0, 0.0]),
+        ];
+        let mut reported = vec![];
+        let done = Vector4::gram_schmidt_with_progress(&mut basis, |done, total| {
+            reported.push((done, total));
+            return if done == 2 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) };
+        });
+        assert_eq!(done, 2);
+        assert_eq!(reported, vec![(1, 3), (2, 3)]);
+        assert_eq!(basis, vec![Vector4::E0, Vector4::E1, Vector4::new([1.0, 1.0, 1.0, 0.0])]);
+    }
+}
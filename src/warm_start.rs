@@ -0,0 +1,142 @@
+use crate::Vector;
+
+// Largest accepted bound on `‖G - I‖₂` for the Gram matrix `G` of the projected-and-corrected basis. Below it the
+// eigenvalues of `G` lie in (0.5, 1.5), so `G` is positive definite with a condition number below 3 and the Cholesky
+// correction stays accurate to working precision.
+const MAX_GRAM_DEVIATION: f64 = 0.5;
+
+// Orthonormalizes `basis` using `previous_q`, the orthonormal basis from a nearby earlier input (e.g. the previous
+// time step), as the starting guess:
+// 1. project: `R' = upper(Q_prevᵀ A)`, the coordinates of the inputs in the previous basis;
+// 2. correct: `Q1 = A R'⁻¹`, which is nearly orthonormal when the inputs changed only slightly;
+// 3. re-orthogonalize: `Q = Q1 R2⁻¹` with `R2ᵀ R2 = Q1ᵀ Q1` (one Cholesky QR step).
+// `A = Q (R2 R')` with a positive triangular factor, so the result is the one `Vector::gram_schmidt` computes, but
+// every step is a batch of independent dot products and updates. It does about twice the flops of the cold path and
+// does not save work: on one core it is about 2.7x slower for 16 vectors of dimension 16 (`warm_start` benchmarks),
+// so it only pays off where the independent operations are batched or spread out. Falls back to `gram_schmidt` when
+// the lengths differ or the previous basis is too far off; returns whether the warm start was used.
+pub fn gram_schmidt_warm_start<V: Vector>(basis: &mut [V], previous_q: &[V]) -> bool {
+    if basis.len() != previous_q.len() || !try_warm_start(basis, previous_q) {
+        V::gram_schmidt(basis);
+        return false;
+    }
+    return true;
+}
+
+// Leaves `basis` untouched when it returns false.
+fn try_warm_start<V: Vector>(basis: &mut [V], previous_q: &[V]) -> bool {
+    let k = basis.len();
+    let projected: Vec<Vec<f64>> = (0..k)
+        .map(|i| (0..k).map(|j| if i <= j { V::dot_product(&previous_q[i], &basis[j]) } else { 0.0 }).collect())
+        .collect();
+    if (0..k).any(|j| projected[j][j] <= 0.0) {
+        return false;
+    }
+    let mut corrected = basis.to_vec();
+    solve_upper_right(&mut corrected, &projected);
+
+    let mut gram = vec![vec![0.0; k]; k];
+    for i in 0..k {
+        for j in i..k {
+            gram[i][j] = V::dot_product(&corrected[i], &corrected[j]);
+            gram[j][i] = gram[i][j];
+        }
+    }
+    let deviation = gram_deviation(&gram);
+    if deviation.is_nan() || deviation >= MAX_GRAM_DEVIATION {
+        return false;
+    }
+    let Some(cholesky) = cholesky_upper(&gram) else {
+        return false;
+    };
+    solve_upper_right(&mut corrected, &cholesky);
+    basis.clone_from_slice(&corrected);
+    return true;
+}
+
+// Upper bound on `‖G - I‖₂` for a symmetric `G`: by Gershgorin, every eigenvalue of `G - I` is at most its largest
+// absolute row sum away from zero.
+fn gram_deviation(gram: &[Vec<f64>]) -> f64 {
+    return gram.iter()
+        .enumerate()
+        .map(|(i, row)| row.iter().enumerate().map(|(j, g)| (g - if i == j { 1.0 } else { 0.0 }).abs()).sum::<f64>())
+        .fold(0.0, f64::max);
+}
+
+// Replaces the vectors `x` by the solution `y` of `Y R = X` for upper triangular `R`, column by column.
+fn solve_upper_right<V: Vector>(x: &mut [V], r: &[Vec<f64>]) {
+    for j in 0..x.len() {
+        let (y, solved) = x[..=j].split_last_mut().unwrap();
+        for (i, q) in solved.iter().enumerate() {
+            y.axpy(-r[i][j], q);
+        }
+        *y = y.clone() / r[j][j];
+    }
+}
+
+// Upper triangular `R` with `Rᵀ R = gram`, or `None` if `gram` is not numerically positive definite.
+pub(crate) fn cholesky_upper(gram: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let k = gram.len();
+    let mut r = vec![vec![0.0; k]; k];
+    for j in 0..k {
+        for i in 0..j {
+            let sum: f64 = (0..i).map(|l| r[l][i] * r[l][j]).sum();
+            r[i][j] = (gram[i][j] - sum) / r[i][i];
+        }
+        let diagonal = gram[j][j] - (0..j).map(|l| r[l][j] * r[l][j]).sum::<f64>();
+        if diagonal.is_nan() || diagonal <= 0.0 {
+            return None;
+        }
+        r[j][j] = diagonal.sqrt();
+    }
+    return Some(r);
+}
+
+#[cfg(test)]
+mod warm_start_test {
+    use crate::warm_start::{cholesky_upper, gram_deviation, MAX_GRAM_DEVIATION};
+    use crate::{gram_schmidt_warm_start, Vector, Vector4};
+
+    fn input(t: f64) -> Vec<Vector4> {
+        return vec![
+            Vector4::new([1.0, 1.0 + t, 1.0, 1.0]),
+            Vector4::new([0.0, 1.0, t, 1.0]),
+            Vector4::new([t, 0.0, 1.0, 1.0]),
+            Vector4::new([0.0, 0.0, 0.0, 1.0 - t]),
+        ];
+    }
+
+    #[test]
+    fn matches_cold_start_for_nearby_input() {
+        let mut previous = input(0.0);
+        Vector4::gram_schmidt(&mut previous);
+
+        let mut warm = input(0.01);
+        let mut cold = warm.clone();
+        assert!(gram_schmidt_warm_start(&mut warm, &previous));
+        Vector4::gram_schmidt(&mut cold);
+        for (w, c) in warm.iter().zip(&cold) {
+            assert!((*w - *c).norm_linf() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn falls_back_when_previous_is_unrelated() {
+        let previous = vec![-Vector4::E0, Vector4::E1, Vector4::E2, Vector4::E3];
+        let mut warm = input(0.0);
+        let mut cold = warm.clone();
+        assert!(!gram_schmidt_warm_start(&mut warm, &previous));
+        assert!(!gram_schmidt_warm_start(&mut input(0.0), &previous[..2]));
+        Vector4::gram_schmidt(&mut cold);
+        assert_eq!(warm, cold);
+    }
+
+    // Every entry is within 0.45 of the identity, but the matrix has the eigenvalue 1 - 3 * 0.45 < 0.
+    #[test]
+    fn deviation_bounds_the_spectrum() {
+        let gram: Vec<Vec<f64>> = (0..4).map(|i| (0..4).map(|j| if i == j { 1.0 } else { -0.45 }).collect()).collect();
+        assert!((gram_deviation(&gram) - 1.35).abs() < 1e-15);
+        assert!(gram_deviation(&gram) >= MAX_GRAM_DEVIATION);
+        assert!(cholesky_upper(&gram).is_none());
+    }
+}
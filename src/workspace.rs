@@ -0,0 +1,58 @@
+use crate::Vector;
+
+// Owns the buffers used by `gram_schmidt_with_workspace`. Once it has grown to the largest basis it is used with,
+// further calls do not allocate.
+#[derive(Debug, Clone)]
+pub struct GsWorkspace<V: Vector> {
+    basis: Vec<V>,
+}
+
+impl<V: Vector> GsWorkspace<V> {
+    pub fn new() -> Self {
+        return Self { basis: vec![] };
+    }
+
+    pub fn with_capacity(vectors: usize) -> Self {
+        return Self { basis: Vec::with_capacity(vectors) };
+    }
+}
+
+// Written out because the derive would require `V: Default`.
+impl<V: Vector> Default for GsWorkspace<V> {
+    fn default() -> Self {
+        return Self::with_capacity(0);
+    }
+}
+
+// Orthonormalizes a copy of `input` held by the workspace and returns it, leaving `input` untouched.
+pub fn gram_schmidt_with_workspace<'a, V: Vector>(ws: &'a mut GsWorkspace<V>, input: &[V]) -> &'a [V] {
+    ws.basis.clear();
+    ws.basis.extend_from_slice(input);
+    V::gram_schmidt(&mut ws.basis);
+    return &ws.basis;
+}
+
+#[cfg(test)]
+mod workspace_test {
+    use crate::{gram_schmidt_with_workspace, GsWorkspace, Vector4};
+
+    #[test]
+    fn reuses_buffer() {
+        let input = [
+            Vector4::new([1.0, 1.0, 1.0, 1.0]),
+            Vector4::new([0.0, 1.0, 0.0, 1.0]),
+            Vector4::new([0.0, 0.0, 1.0, 1.0]),
+            Vector4::new([0.0, 0.0, 0.0, 1.0]),
+        ];
+        let mut ws = GsWorkspace::with_capacity(4);
+        let first = gram_schmidt_with_workspace(&mut ws, &input).to_vec();
+        let pointer = ws.basis.as_ptr();
+        let second = gram_schmidt_with_workspace(&mut ws, &input);
+        assert_eq!(first, second);
+        assert_eq!(second[1], Vector4::new([-0.5, 0.5, -0.5, 0.5]));
+        assert_eq!(pointer, ws.basis.as_ptr());
+
+        let mut fresh = GsWorkspace::<Vector4>::default();
+        assert_eq!(gram_schmidt_with_workspace(&mut fresh, &input), first);
+    }
+}
@@ -0,0 +1,70 @@
+// Compares `householder_qr` against stored reference factorizations in `tests/golden`. Each file holds the sections
+// `A`, `Q` and `R` as whitespace-separated rows; lines starting with `#` are comments. The references are not LAPACK
+// output: they were computed with mpmath at 60 significant digits from the f64 values of `A` and rounded to the
+// nearest f64, so they agree with the exact factorization to about 1e-16. They use the sign convention of a
+// non-negative `R` diagonal, so the computed factors are normalized the same way.
+//
+// The error of a backward stable QR in `Q` grows with the condition number of `A`, so the tolerance for `Q` is
+// `m κ(A) ε` per file, with `κ(A) = κ(R)` taken in the 1-norm from the reference `R`.
+
+#![allow(clippy::needless_return)]
+
+use std::fs;
+use std::path::Path;
+
+use gram_schmidt::{householder_qr, Matrix, UpperTriangular};
+
+struct Golden {
+    a: Matrix,
+    q: Matrix,
+    r: Matrix,
+}
+
+fn parse(text: &str) -> Golden {
+    let mut sections: Vec<(String, Vec<Vec<f64>>)> = vec![];
+    for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+        if line.chars().all(char::is_alphabetic) {
+            sections.push((line.to_string(), vec![]));
+            continue;
+        }
+        let row = line.split_whitespace().map(|x| x.parse().expect("invalid number")).collect();
+        sections.last_mut().expect("row before the first section").1.push(row);
+    }
+    let section = |name: &str| {
+        let rows = &sections.iter().find(|(label, _)| label == name).expect("missing section").1;
+        return Matrix::from_rows(rows);
+    };
+    return Golden { a: section("A"), q: section("Q"), r: section("R") };
+}
+
+fn max_abs_difference(a: &Matrix, b: &Matrix) -> f64 {
+    assert_eq!((a.rows(), a.cols()), (b.rows(), b.cols()));
+    let difference = a - b;
+    return (0..difference.cols()).flat_map(|j| difference.column(j).to_vec()).fold(0.0, |m, x| m.max(x.abs()));
+}
+
+#[test]
+fn matches_reference_factorizations() {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+    let mut checked = 0;
+    for entry in fs::read_dir(directory).unwrap() {
+        let path = entry.unwrap().path();
+        let golden = parse(&fs::read_to_string(&path).unwrap());
+        let (mut q, mut r) = householder_qr(&golden.a);
+        for k in 0..r.rows() {
+            if r[(k, k)] < 0.0 {
+                q.column_mut(k).iter_mut().for_each(|x| *x = -*x);
+                for j in k..r.cols() {
+                    r[(k, j)] = -r[(k, j)];
+                }
+            }
+        }
+        let scale = golden.r[(0, 0)].abs().max(1.0);
+        let kappa = UpperTriangular::try_from(golden.r.clone()).unwrap().condition();
+        let q_tolerance = golden.a.rows() as f64 * kappa * f64::EPSILON;
+        assert!(max_abs_difference(&q, &golden.q) < q_tolerance, "{}: Q differs", path.display());
+        assert!(max_abs_difference(&r, &golden.r) < 1e-13 * scale, "{}: R differs", path.display());
+        checked += 1;
+    }
+    assert!(checked > 0);
+}